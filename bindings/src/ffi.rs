@@ -0,0 +1,101 @@
+use std::ffi::{c_char, CStr, CString};
+use std::ptr::null_mut;
+
+/// compresses a game given as PGN movetext, null if the PGN can't be compressed.
+/// The returned string has to be released with [chess_compress_free_string].
+///
+/// # Safety
+///
+/// `pgn` has to be null or point to a nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn chess_compress_pgn(pgn: *const c_char) -> *mut c_char {
+    convert(pgn, crate::compress_pgn)
+}
+
+/// the PGN of a compressed game, null if the game can't be decompressed.
+/// The returned string has to be released with [chess_compress_free_string].
+///
+/// # Safety
+///
+/// `encoded_game` has to be null or point to a nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn chess_decompress_to_pgn(encoded_game: *const c_char) -> *mut c_char {
+    convert(encoded_game, crate::decompress_to_pgn)
+}
+
+/// the FEN of the final position of a compressed game, null if the game can't be decompressed.
+/// The returned string has to be released with [chess_compress_free_string].
+///
+/// # Safety
+///
+/// `encoded_game` has to be null or point to a nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn chess_final_fen(encoded_game: *const c_char) -> *mut c_char {
+    convert(encoded_game, crate::final_fen)
+}
+
+/// releases a string returned by one of the functions above
+///
+/// # Safety
+///
+/// `string` has to be null or a string returned by this library that wasn't released yet
+#[no_mangle]
+pub unsafe extern "C" fn chess_compress_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+unsafe fn convert<E>(input: *const c_char, function: impl FnOnce(&str) -> Result<String, E>) -> *mut c_char {
+    if input.is_null() {
+        return null_mut();
+    }
+    let Ok(input) = CStr::from_ptr(input).to_str() else {
+        return null_mut();
+    };
+    match function(input).ok().and_then(|output| CString::new(output).ok()) {
+        Some(output) => output.into_raw(),
+        None => null_mut(),
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    unsafe fn call(function: unsafe extern "C" fn(*const c_char) -> *mut c_char, input: &CStr) -> Option<String> {
+        let output = function(input.as_ptr());
+        if output.is_null() {
+            return None;
+        }
+        let output_string = CStr::from_ptr(output).to_str().unwrap().to_string();
+        chess_compress_free_string(output);
+        Some(output_string)
+    }
+
+    #[rstest(
+        function, input, expected_output,
+        case(chess_compress_pgn, c"1. e4 e5 2. Nf3", Some("ckGV")),
+        case(chess_compress_pgn, c"1. e4 e4", None),
+        case(chess_decompress_to_pgn, c"ckGV", Some("1. e4 e5 2. Nf3 *")),
+        case(chess_decompress_to_pgn, c"ck!", None),
+        case(chess_final_fen, c"ckGV", Some("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2")),
+        ::trace::notrace(function) //This leads to the arguments (except the function) being printed in front of the test result.
+    )]
+    fn test_ffi_functions(
+        function: unsafe extern "C" fn(*const c_char) -> *mut c_char,
+        input: &CStr,
+        expected_output: Option<&str>,
+    ) {
+        assert_eq!(unsafe { call(function, input) }.as_deref(), expected_output);
+    }
+
+    #[test]
+    fn test_null_input() {
+        assert!(unsafe { chess_compress_pgn(std::ptr::null()) }.is_null());
+        unsafe { chess_compress_free_string(null_mut()) };
+    }
+}
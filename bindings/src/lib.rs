@@ -0,0 +1,33 @@
+// the functions of chess_compress_urlsafe that are made available to other languages:
+// C (see ffi), JavaScript through wasm-bindgen (see wasm) and python through pyo3 (see python)
+use chess_compress_urlsafe_codec::decompress::{decompress_with_options, DecompressOptions};
+use chess_compress_urlsafe_core::base::errors::ChessError;
+
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use chess_compress_urlsafe_codec::decompress::final_fen;
+pub use chess_compress_urlsafe_codec::pgn::compress_pgn;
+
+/// the PGN movetext of a compressed game followed by its result
+pub fn decompress_to_pgn(encoded_game: &str) -> Result<String, ChessError> {
+    decompress_with_options(encoded_game, &DecompressOptions::default()).map(|game| game.to_pgn())
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pgn_round_trip() {
+        let encoded_game = compress_pgn("1. e4 e5 2. Nf3 Nc6").unwrap();
+        assert_eq!(encoded_game, "ckGV5q");
+        assert_eq!(decompress_to_pgn(&encoded_game).unwrap(), "1. e4 e5 2. Nf3 Nc6 *");
+        assert_eq!(final_fen(&encoded_game).unwrap(), "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+    }
+}
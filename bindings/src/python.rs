@@ -0,0 +1,38 @@
+// the code generated by #[pyfunction] converts PyErr into itself
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use chess_compress_urlsafe_core::base::errors::ChessError;
+
+/// compresses a game given as PGN movetext, raises a ValueError if the PGN can't be compressed
+#[pyfunction]
+fn compress_pgn(pgn: &str) -> PyResult<String> {
+    crate::compress_pgn(pgn).map_err(to_value_error)
+}
+
+/// the PGN of a compressed game, raises a ValueError if the game can't be decompressed
+#[pyfunction]
+fn decompress_to_pgn(encoded_game: &str) -> PyResult<String> {
+    crate::decompress_to_pgn(encoded_game).map_err(to_value_error)
+}
+
+/// the FEN of the final position of a compressed game, raises a ValueError if the game can't be decompressed
+#[pyfunction]
+fn final_fen(encoded_game: &str) -> PyResult<String> {
+    crate::final_fen(encoded_game).map_err(to_value_error)
+}
+
+fn to_value_error(error: ChessError) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// the python module is named after the library (so that python finds its init function)
+#[pymodule]
+fn chess_compress_urlsafe_bindings(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(compress_pgn, module)?)?;
+    module.add_function(wrap_pyfunction!(decompress_to_pgn, module)?)?;
+    module.add_function(wrap_pyfunction!(final_fen, module)?)?;
+    Ok(())
+}
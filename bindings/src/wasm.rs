@@ -0,0 +1,19 @@
+use wasm_bindgen::prelude::*;
+
+/// compresses a game given as PGN movetext, throws if the PGN can't be compressed
+#[wasm_bindgen(js_name = compressPgn)]
+pub fn compress_pgn(pgn: &str) -> Result<String, JsError> {
+    crate::compress_pgn(pgn).map_err(|error| JsError::new(&error.to_string()))
+}
+
+/// the PGN of a compressed game, throws if the game can't be decompressed
+#[wasm_bindgen(js_name = decompressToPgn)]
+pub fn decompress_to_pgn(encoded_game: &str) -> Result<String, JsError> {
+    crate::decompress_to_pgn(encoded_game).map_err(|error| JsError::new(&error.to_string()))
+}
+
+/// the FEN of the final position of a compressed game, throws if the game can't be decompressed
+#[wasm_bindgen(js_name = finalFen)]
+pub fn final_fen(encoded_game: &str) -> Result<String, JsError> {
+    crate::final_fen(encoded_game).map_err(|error| JsError::new(&error.to_string()))
+}
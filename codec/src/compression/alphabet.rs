@@ -0,0 +1,216 @@
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::compression::base64::{decode_base64_index, encode_base64_index};
+
+/// the character set an encoded game is written in.
+///
+/// Games are always compressed to url-safe base64 first (the canonical form),
+/// other alphabets are derived from that by regrouping its bits.
+/// Games written in an alphabet other than url-safe base64 start with a format header
+/// (see [FormatHeader](crate::header::FormatHeader)), so decompress detects the alphabet on its own.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Alphabet {
+    /// a-z, A-Z, 0-9, '-' and '_' (6 bits per char)
+    #[default]
+    UrlSafeBase64,
+    /// 0-9 and A-V (5 bits per char, so ~20% longer than base64).
+    /// All characters are part of the alphanumeric mode of QR codes, which stores a char in 5.5 bits
+    /// instead of the 8 bits needed in byte mode, so the resulting QR code gets a lot smaller.
+    /// Decoding also accepts lowercase chars.
+    QrAlphanumeric,
+    /// A-Z and 2-7 ([rfc4648](https://datatracker.ietf.org/doc/html/rfc4648#section-6) base32, 5 bits per char).
+    /// Decoding is case-insensitive, so values survive channels that mangle case (voice, handwriting, ..).
+    /// The digits 0, 1 and 8 are left out, so they can't be confused with O, I and B.
+    Base32,
+}
+
+/// the variant of base64 that the chars of an encoded game were converted to after compression.
+///
+/// Compression always produces url-safe base64, the standard variant only exists to decode legacy data
+/// that was converted to it outside of this crate.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum AlphabetVariant {
+    /// '-' and '_' as the last two chars
+    #[default]
+    UrlSafe,
+    /// [rfc4648](https://datatracker.ietf.org/doc/html/rfc4648#section-4) base64 with '+' and '/' as the last two chars,
+    /// trailing '=' padding is ignored
+    Standard,
+}
+
+impl AlphabetVariant {
+    /// converts an encoded game written in this variant back into the url-safe variant
+    pub fn to_url_safe(&self, encoded: &str) -> String {
+        match self {
+            AlphabetVariant::UrlSafe => encoded.to_string(),
+            AlphabetVariant::Standard => encoded.trim_end_matches('=').chars().map(|c| match c {
+                '+' => '-',
+                '/' => '_',
+                _ => c,
+            }).collect(),
+        }
+    }
+}
+
+static QR_ALPHANUMERIC_CHARS: [char; 32] = [
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F',
+    'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V',
+];
+
+static BASE32_CHARS: [char; 32] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '2', '3', '4', '5', '6', '7',
+];
+
+impl Alphabet {
+    /// the chars of the 5bit alphabets (index == value), None for url-safe base64
+    pub(crate) fn base32_chars(&self) -> Option<&'static [char; 32]> {
+        match self {
+            Alphabet::UrlSafeBase64 => None,
+            Alphabet::QrAlphanumeric => Some(&QR_ALPHANUMERIC_CHARS),
+            Alphabet::Base32 => Some(&BASE32_CHARS),
+        }
+    }
+}
+
+/// converts a canonical (url-safe base64) encoded game into the given alphabet
+pub fn to_alphabet(canonical: &str, alphabet: Alphabet) -> Result<String, ChessError> {
+    let Some(alphabet_chars) = alphabet.base32_chars() else {
+        return Ok(canonical.to_string());
+    };
+    let values = canonical.chars().map(|c| {
+        decode_base64_index(c).map(|index| index as u8)
+    }).collect::<Result<Vec<u8>, ChessError>>()?;
+    let regrouped = regroup_bits(&values, 6, 5);
+    Ok(regrouped.iter().map(|value| alphabet_chars[*value as usize]).collect())
+}
+
+/// converts an encoded game written in the given alphabet back into its canonical (url-safe base64) form
+pub fn from_alphabet(encoded: &str, alphabet: Alphabet) -> Result<String, ChessError> {
+    let Some(alphabet_chars) = alphabet.base32_chars() else {
+        return Ok(encoded.to_string());
+    };
+    let values = encoded.chars().map(|c| {
+        // the 5bit alphabets only contain uppercase letters, so decoding them is case-insensitive
+        let upper_c = c.to_ascii_uppercase();
+        match alphabet_chars.iter().position(|alphabet_char| *alphabet_char == upper_c) {
+            Some(value) => Ok(value as u8),
+            None => Err(ChessError::new(
+                ErrorKind::IllegalFormat,
+                format!("not a char of the {alphabet:?} alphabet ({}): {c}", alphabet_chars.iter().collect::<String>()),
+            )),
+        }
+    }).collect::<Result<Vec<u8>, ChessError>>()?;
+    let regrouped = regroup_bits(&values, 5, 6);
+    Ok(regrouped.iter().map(|value| encode_base64_index(*value as usize)).collect())
+}
+
+/// reinterprets a sequence of values with `from_bits` bits each as a sequence of values with `to_bits` bits each.
+///
+/// When going to smaller values the last value is padded with zero bits, when going to bigger values
+/// incomplete trailing bits are dropped, so regrouping back and forth returns the original values.
+pub(crate) fn regroup_bits(values: &[u8], from_bits: u32, to_bits: u32) -> Vec<u8> {
+    let mut regrouped = Vec::with_capacity((values.len() * from_bits as usize).div_ceil(to_bits as usize));
+    let mut buffer: u32 = 0;
+    let mut bits_in_buffer: u32 = 0;
+    for value in values {
+        buffer = (buffer << from_bits) | (*value as u32);
+        bits_in_buffer += from_bits;
+        while bits_in_buffer >= to_bits {
+            bits_in_buffer -= to_bits;
+            regrouped.push(((buffer >> bits_in_buffer) & ((1 << to_bits) - 1)) as u8);
+        }
+    }
+    if bits_in_buffer > 0 && to_bits < from_bits {
+        regrouped.push(((buffer << (to_bits - bits_in_buffer)) & ((1 << to_bits) - 1)) as u8);
+    }
+    regrouped
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        canonical, expected_qr,
+        case("", ""),
+        case("A", "00"),
+        case("_", "VG"),
+        case("ckGV5qh", "E90PBPL88"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_qr_alphanumeric(canonical: &str, expected_qr: &str) {
+        let actual_qr = to_alphabet(canonical, Alphabet::QrAlphanumeric).unwrap();
+        assert_eq!(actual_qr, expected_qr);
+    }
+
+    #[rstest(
+        canonical,
+        case(""),
+        case("a"),
+        case("KS"),
+        case("T2uCU"),
+        case("Y3vghpnyfWW7Q"),
+        case("T2uCU-tDL8_EAabcdefghijklmnopqrstuvwxyz0123456789"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_qr_alphanumeric_round_trip(canonical: &str) {
+        let qr = to_alphabet(canonical, Alphabet::QrAlphanumeric).unwrap();
+        assert!(qr.chars().all(|c| QR_ALPHANUMERIC_CHARS.contains(&c)), "{qr} contains non-qr chars");
+        assert_eq!(qr.len(), (canonical.len() * 6).div_ceil(5));
+        assert_eq!(from_alphabet(&qr, Alphabet::QrAlphanumeric).unwrap(), canonical);
+        assert_eq!(from_alphabet(&qr.to_lowercase(), Alphabet::QrAlphanumeric).unwrap(), canonical);
+    }
+
+    #[rstest(
+        canonical, expected_base32,
+        case("", ""),
+        case("A", "AA"),
+        case("_", "7Q"),
+        case("ckGV5qh", "OJAZLZVII"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_base32(canonical: &str, expected_base32: &str) {
+        let actual_base32 = to_alphabet(canonical, Alphabet::Base32).unwrap();
+        assert_eq!(actual_base32, expected_base32);
+        assert_eq!(from_alphabet(&actual_base32.to_lowercase(), Alphabet::Base32).unwrap(), canonical);
+    }
+
+    #[rstest(
+        illegal_base32,
+        case("A1"),
+        case("A8"),
+        case("A0"),
+        case("A-"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_base32_rejects_illegal_chars(illegal_base32: &str) {
+        assert!(from_alphabet(illegal_base32, Alphabet::Base32).is_err());
+    }
+
+    #[rstest(
+        illegal_qr,
+        case("W0"),
+        case("0-"),
+        case("0_"),
+        case("0 "),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_qr_alphanumeric_rejects_illegal_chars(illegal_qr: &str) {
+        assert!(from_alphabet(illegal_qr, Alphabet::QrAlphanumeric).is_err());
+    }
+
+    #[rstest(
+        variant, encoded, expected_url_safe,
+        case(AlphabetVariant::UrlSafe, "GV-tVGt-c", "GV-tVGt-c"),
+        case(AlphabetVariant::Standard, "GV+tVGt+c", "GV-tVGt-c"),
+        case(AlphabetVariant::Standard, "ckGV90Fa+tHG/+==", "ckGV90Fa-tHG_-"),
+        case(AlphabetVariant::Standard, ".F.8+8+4k3+8+8+4K3+4P3+8/w/+/+/0/1.k=", ".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.k"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_alphabet_variant_to_url_safe(variant: AlphabetVariant, encoded: &str, expected_url_safe: &str) {
+        assert_eq!(variant.to_url_safe(encoded), expected_url_safe);
+    }
+}
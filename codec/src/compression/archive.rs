@@ -0,0 +1,137 @@
+use std::io::Write;
+use std::str;
+use crate::base::errors::{ChessError, ErrorKind};
+
+/// the first line of every archive
+pub const ARCHIVE_MAGIC: &str = "chess-compress-archive v1\n";
+
+/// writes the encoded games as an archive: the [ARCHIVE_MAGIC] line followed by one encoded game per line.
+///
+/// Encoded games never contain whitespace, so an archive can be read without unescaping anything
+/// and every game is a slice of the archive (see [ArchiveReader]).
+pub fn write_archive<S: AsRef<str>>(mut writer: impl Write, encoded_games: impl IntoIterator<Item = S>) -> Result<(), ChessError> {
+    let to_chess_error = |error: std::io::Error| ChessError::new(ErrorKind::Io, format!("couldn't write archive: {error}")).with_source(error);
+    writer.write_all(ARCHIVE_MAGIC.as_bytes()).map_err(to_chess_error)?;
+    for encoded_game in encoded_games {
+        let encoded_game = encoded_game.as_ref();
+        if encoded_game.contains(char::is_whitespace) {
+            return Err(ChessError::new(ErrorKind::IllegalFormat, format!("'{encoded_game}' isn't an encoded game, it contains whitespace")));
+        }
+        writeln!(writer, "{encoded_game}").map_err(to_chess_error)?;
+    }
+    writer.flush().map_err(to_chess_error)
+}
+
+/// reads the games of an archive (see [write_archive]) without copying them.
+///
+/// The games aren't decoded, so scanning an archive is cheap and only the games
+/// that are actually of interest have to be decompressed.
+#[derive(Debug, Copy, Clone)]
+pub struct ArchiveReader<'a> {
+    games: &'a [u8],
+}
+
+impl<'a> ArchiveReader<'a> {
+    pub fn new(archive: &'a [u8]) -> Result<ArchiveReader<'a>, ChessError> {
+        let Some(games) = archive.strip_prefix(ARCHIVE_MAGIC.as_bytes()) else {
+            return Err(ChessError::new(ErrorKind::IllegalFormat, format!("an archive has to start with '{}'", ARCHIVE_MAGIC.trim_end())));
+        };
+        Ok(ArchiveReader { games })
+    }
+
+    /// the encoded games of the archive in the order they were written
+    pub fn games(&self) -> impl Iterator<Item = Result<&'a str, ChessError>> {
+        // every game is terminated by a line break, so an empty archive has no (empty) game to split off
+        let lines = (!self.games.is_empty()).then(|| {
+            let games: &'a [u8] = self.games.strip_suffix(b"\n").unwrap_or(self.games);
+            games.split(|byte| *byte == b'\n')
+        });
+        lines.into_iter().flatten().enumerate().map(|(index, line)| str::from_utf8(line).map_err(|error| ChessError::new(
+            ErrorKind::IllegalFormat,
+            format!("game {index} of the archive isn't valid utf-8"),
+        ).with_source(error)))
+    }
+
+    /// the number of games in the archive
+    pub fn len(&self) -> usize {
+        self.games().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+}
+
+/// an archive file that is mapped into memory (instead of being read into it),
+/// so huge archives can be scanned with little RAM.
+#[cfg(feature = "mmap")]
+pub struct MmapArchive {
+    mmap: memmap2::Mmap,
+}
+
+#[cfg(feature = "mmap")]
+impl MmapArchive {
+    /// maps the archive file into memory. The file must not be modified while it's mapped.
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<MmapArchive, ChessError> {
+        let path = path.as_ref();
+        let to_chess_error = |error: std::io::Error| ChessError::new(
+            ErrorKind::Io,
+            format!("couldn't map archive {}: {error}", path.display()),
+        ).with_source(error);
+        let file = std::fs::File::open(path).map_err(to_chess_error)?;
+        // Safety: the mapped bytes are only ever read (and validated before use),
+        // the caller is responsible for not modifying the file while it's mapped.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(to_chess_error)?;
+        ArchiveReader::new(&mmap)?;
+        Ok(MmapArchive { mmap })
+    }
+
+    pub fn reader(&self) -> ArchiveReader<'_> {
+        ArchiveReader::new(&self.mmap).expect("the archive was validated when it was opened")
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        encoded_games,
+        case(vec![]),
+        case(vec![""]),
+        case(vec!["ckGV5qh", "", ".C.ckGVE4", "GV-tVGt-c"]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_archive_round_trip(encoded_games: Vec<&str>) {
+        let mut archive: Vec<u8> = Vec::new();
+        write_archive(&mut archive, &encoded_games).unwrap();
+        let reader = ArchiveReader::new(&archive).unwrap();
+        let read_games: Vec<&str> = reader.games().collect::<Result<Vec<&str>, ChessError>>().unwrap();
+        assert_eq!(read_games, encoded_games);
+        assert_eq!(reader.len(), encoded_games.len());
+    }
+
+    #[test]
+    fn test_archive_rejects_illegal_content() {
+        assert!(write_archive(Vec::new(), ["ckGV 5qh"]).is_err());
+        assert!(ArchiveReader::new(b"ckGV5qh\n").is_err());
+        let reader = ArchiveReader::new(b"chess-compress-archive v1\nckGV\n\xFF\n").unwrap();
+        let games: Vec<Result<&str, ChessError>> = reader.games().collect();
+        assert_eq!(games.len(), 2);
+        assert!(games[1].is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap_archive() {
+        let path = std::env::temp_dir().join(format!("chess-compress-archive-{}.txt", std::process::id()));
+        write_archive(std::fs::File::create(&path).unwrap(), ["ckGV5qh", "GV-tVGt-c"]).unwrap();
+        let archive = MmapArchive::open(&path).unwrap();
+        let games: Vec<&str> = archive.reader().games().map(|game| game.unwrap()).collect();
+        assert_eq!(games, vec!["ckGV5qh", "GV-tVGt-c"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,5 +1,4 @@
 use std::collections::HashSet;
-use std::sync::OnceLock;
 use crate::base::errors::{ChessError, ErrorKind};
 use crate::base::position::Position;
 // using url safe base 64 encoding without the padding character since it's not needed
@@ -93,10 +92,7 @@ pub fn decode_base64(character: char) -> Result<Position, ChessError> {
         '-' => { 62 }
         '_' => { 63 }
         _ => {
-            return Err(ChessError {
-                msg: format!("not a url safe base64 char: {character}"),
-                kind: ErrorKind::IllegalFormat
-            })
+            return Err(ChessError::new(ErrorKind::IllegalFormat, format!("not a url safe base64 char: {character}")))
         }
     };
     let column_index = decoded % 8;
@@ -104,12 +100,24 @@ pub fn decode_base64(character: char) -> Result<Position, ChessError> {
     Ok(Position::new_unchecked(column_index, row_index))
 }
 
+/// decodes a url safe base64 char into its 6bit value (0..64)
+pub fn decode_base64_index(character: char) -> Result<usize, ChessError> {
+    decode_base64(character).map(|position| position.index.get())
+}
+
 pub fn encode_base64(position: Position) -> char {
-    static ONCE: OnceLock<[char; 64]> = OnceLock::new();
-    let url_safe_base64_chars: &[char; 64] = ONCE.get_or_init(|| {
-        ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '-', '_']
-    });
-    url_safe_base64_chars[position.index]
+    encode_base64_index(position.index.get())
+}
+
+pub(crate) static URL_SAFE_BASE64_CHARS: [char; 64] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '-', '_',
+];
+
+/// encodes a 6bit value (0..64) as url safe base64 char
+pub fn encode_base64_index(index: usize) -> char {
+    URL_SAFE_BASE64_CHARS[index]
 }
 
 pub fn assert_is_url_safe_base64(str: &str) -> Result<(), ChessError> {
@@ -127,14 +135,15 @@ pub fn assert_is_url_safe_base64(str: &str) -> Result<(), ChessError> {
         illegal_chars
     };
 
-    if illegal_chars_found.is_empty() {
-        Ok(())
-    } else {
-        let illegal_chars: String = illegal_chars_found.iter().collect();
-        Err(ChessError {
-            msg: format!("provided value {str} contains {} illegal characters: [{illegal_chars}]! Only the following characters are expected: a-z, A-Z, 0-1, -, _", illegal_chars.len()),
-            kind: ErrorKind::IllegalFormat,
-        })
+    match str.chars().enumerate().find(|(_, c)| !is_url_safe_base64_char(*c)) {
+        None => Ok(()),
+        Some((char_index, found)) => {
+            let illegal_chars: String = illegal_chars_found.iter().collect();
+            Err(ChessError::new(
+                ErrorKind::InvalidChar { char_index, found },
+                format!("provided value {str} contains {} illegal characters: [{illegal_chars}]! Only the following characters are expected: a-z, A-Z, 0-1, -, _", illegal_chars.len()),
+            ))
+        }
     }
 }
 
@@ -143,6 +152,7 @@ pub fn assert_is_url_safe_base64(str: &str) -> Result<(), ChessError> {
 #[cfg(test)]
 mod tests {
     use rstest::*;
+    use crate::base::errors::ErrorKind;
     use crate::compression::base64::assert_is_url_safe_base64;
 
     #[rstest(
@@ -189,4 +199,20 @@ mod tests {
             }
         };
     }
-}
\ No newline at end of file
+
+    #[rstest(
+        value, expected_char_index, expected_char,
+        case(" asldkf9", 0, ' '),
+        case("asldkf9+", 7, '+'),
+        case("^fI6$", 0, '^'),
+        case("ckG=V=", 3, '='),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_illegal_char_is_located(value: &str, expected_char_index: usize, expected_char: char) {
+        let error = assert_is_url_safe_base64(value).unwrap_err();
+        let ErrorKind::InvalidChar { char_index, found } = error.kind else {
+            panic!("{error} should point to the illegal char");
+        };
+        assert_eq!((char_index, found), (expected_char_index, expected_char));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::compression::base64::encode_base64_index;
+use crate::compression::encoded_game::fnv1a_64;
+
+// 2 base64 chars carry 12 bits, so a random alteration goes unnoticed with a chance of 1/4096.
+pub const CHECKSUM_CHARS: usize = 2;
+
+// distinguishes the checksum hash from the short_hash of EncodedGame
+const CHECKSUM_SEED: u64 = 0xc5;
+
+/// appends the checksum chars to a url-safe base64 value
+pub fn append_checksum(base64_value: &str) -> String {
+    format!("{base64_value}{}", checksum(base64_value))
+}
+
+/// removes the checksum chars from a value created by [append_checksum],
+/// fails with [ErrorKind::ChecksumMismatch] if they don't match the rest of the value.
+pub fn verify_and_strip_checksum(with_checksum: &str) -> Result<&str, ChessError> {
+    let Some(split_index) = with_checksum.len().checked_sub(CHECKSUM_CHARS) else {
+        return Err(ChessError::new(ErrorKind::IllegalFormat, format!("value {with_checksum} is too short to contain the checksum")));
+    };
+    let (value, found_checksum) = with_checksum.split_at(split_index);
+    let expected_checksum = checksum(value);
+    if found_checksum != expected_checksum {
+        return Err(ChessError::new(
+            ErrorKind::ChecksumMismatch,
+            format!("checksum of {with_checksum} should be {expected_checksum} but is {found_checksum}"),
+        ));
+    }
+    Ok(value)
+}
+
+fn checksum(base64_value: &str) -> String {
+    let hash = fnv1a_64(CHECKSUM_SEED, base64_value.as_bytes());
+    // fold all 64 bits into the 12 that are kept
+    let folded = (hash ^ (hash >> 12) ^ (hash >> 24) ^ (hash >> 36) ^ (hash >> 48) ^ (hash >> 60)) as usize;
+    (0..CHECKSUM_CHARS).map(|i| encode_base64_index((folded >> (6 * i)) & 63)).collect()
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        value,
+        case(""),
+        case("a"),
+        case("ckGV5qh"),
+        case("T2uCU-tDL8_EA"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_round_trip(value: &str) {
+        let with_checksum = append_checksum(value);
+        assert_eq!(with_checksum.len(), value.len() + CHECKSUM_CHARS);
+        assert_eq!(verify_and_strip_checksum(&with_checksum).unwrap(), value);
+    }
+
+    #[rstest(
+        altered,
+        case("ckGV5qi"),
+        case("ckVG5qh"),
+        case("ckGV5q"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_altered_values_are_rejected(altered: &str) {
+        let with_checksum = append_checksum("ckGV5qh");
+        let altered_with_checksum = format!("{altered}{}", &with_checksum[with_checksum.len() - CHECKSUM_CHARS..]);
+        let error = verify_and_strip_checksum(&altered_with_checksum).unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::ChecksumMismatch));
+    }
+
+    #[test]
+    fn test_too_short_values_are_rejected() {
+        let error = verify_and_strip_checksum("A").unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::IllegalFormat));
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+use crate::base::color::Color;
+use crate::compression::decompress::DecompressedGame;
+
+/// a player with less time left than this is in a time scramble
+pub const TIME_SCRAMBLE_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// the clocks of both players after a half move
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ClockSnapshot {
+    pub half_move_index: usize,
+    /// None as long as no clock record of the player was found
+    pub white_time_left: Option<Duration>,
+    /// None as long as no clock record of the player was found
+    pub black_time_left: Option<Duration>,
+    /// the player who made the move has less than [TIME_SCRAMBLE_THRESHOLD] left
+    pub is_time_scramble: bool,
+}
+
+impl DecompressedGame {
+    /// reconstructs the clocks of both players after every half move from the clock records of the game.
+    /// A player's time is carried over from their last move until their next clock record.
+    /// Returns an empty Vec if the game has no clock records.
+    pub fn clock_timeline(&self) -> Vec<ClockSnapshot> {
+        if self.extensions.clocks.is_empty() {
+            return Vec::new();
+        }
+        let mut active_color = if self.positions[0].fen.split(' ').nth(1) == Some("b") {Color::Black} else {Color::White};
+        let mut white_time_left: Option<Duration> = None;
+        let mut black_time_left: Option<Duration> = None;
+        let mut timeline: Vec<ClockSnapshot> = Vec::with_capacity(self.moves.len());
+        for half_move_index in 0..self.moves.len() {
+            let time_left = match active_color {
+                Color::White => &mut white_time_left,
+                Color::Black => &mut black_time_left,
+            };
+            if let Some(recorded_time_left) = self.extensions.clocks.get(&(half_move_index + 1)) {
+                *time_left = Some(*recorded_time_left);
+            }
+            let is_time_scramble = time_left.is_some_and(|time_left| time_left < TIME_SCRAMBLE_THRESHOLD);
+            timeline.push(ClockSnapshot { half_move_index, white_time_left, black_time_left, is_time_scramble });
+            active_color = active_color.toggle();
+        }
+        timeline
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use super::*;
+    use crate::base::a_move::Move;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::{compress_with_options, CompressOptions};
+    use crate::compression::decompress::{decompress_with_options, DecompressOptions};
+    use crate::compression::extension::Extensions;
+
+    #[test]
+    fn test_clock_timeline() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3, b8c6", ",").unwrap();
+        let extensions = Extensions {
+            clocks: BTreeMap::from([
+                (1, Duration::from_secs(60)),
+                (2, Duration::from_secs(25)),
+                (4, Duration::from_secs(12)),
+            ]),
+            ..Extensions::default()
+        };
+        let encoded_game = compress_with_options(given_moves, &CompressOptions { extensions, ..CompressOptions::default() }).unwrap();
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+
+        let secs = |secs: u64| Some(Duration::from_secs(secs));
+        assert_eq!(decompressed_game.clock_timeline(), vec![
+            ClockSnapshot { half_move_index: 0, white_time_left: secs(60), black_time_left: None, is_time_scramble: false },
+            ClockSnapshot { half_move_index: 1, white_time_left: secs(60), black_time_left: secs(25), is_time_scramble: true },
+            ClockSnapshot { half_move_index: 2, white_time_left: secs(60), black_time_left: secs(25), is_time_scramble: false },
+            ClockSnapshot { half_move_index: 3, white_time_left: secs(60), black_time_left: secs(12), is_time_scramble: true },
+        ]);
+    }
+
+    #[test]
+    fn test_clock_timeline_of_game_without_clocks() {
+        let decompressed_game = decompress_with_options("ckGV", &DecompressOptions::default()).unwrap();
+        assert!(decompressed_game.clock_timeline().is_empty());
+    }
+}
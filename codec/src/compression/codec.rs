@@ -0,0 +1,115 @@
+use crate::base::a_move::Move;
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::compression::alphabet::Alphabet;
+use crate::compression::compress::{compress_with_warnings, CompressOptions, CompressedGame};
+use crate::compression::decompress::{decompress_with_options, DecompressOptions, DecompressedGame};
+
+/// bundles the options of compression and decompression, so that a configuration can be set up once
+/// (e.g. per tenant of a multi-tenant server) and passed around explicitly.
+///
+/// The crate has no global state, so any number of differently configured codecs (each with its own [Metrics](crate::metrics::Metrics))
+/// can be used side by side.
+#[derive(Debug, Clone, Default)]
+pub struct Codec {
+    pub compress_options: CompressOptions,
+    pub decompress_options: DecompressOptions,
+}
+
+impl Codec {
+    /// a codec that compresses to the given alphabet (and decodes games without format header with it)
+    pub fn new(alphabet: Alphabet) -> Codec {
+        Codec {
+            compress_options: CompressOptions { alphabet, ..CompressOptions::default() },
+            decompress_options: DecompressOptions { alphabet, ..DecompressOptions::default() },
+        }
+    }
+
+    /// a codec with the given options, if it can decode every game it compresses (see [Codec::audit])
+    pub fn checked(compress_options: CompressOptions, decompress_options: DecompressOptions) -> Result<Codec, ChessError> {
+        let codec = Codec { compress_options, decompress_options };
+        codec.audit()?;
+        Ok(codec)
+    }
+
+    /// fails with [ErrorKind::IllegalConfig] if the options could make the codec misread the games it compresses.
+    ///
+    /// Promotion chars (Q, R, N, B) are only read where the game logic expects a promotion, so they never
+    /// collide with the chars of an alphabet. What can collide is the alphabet of games without format header:
+    /// games compressed to url-safe base64 may have no header, so they have to be decompressed with that alphabet.
+    /// Scrambled games can only be read with the key they were scrambled with.
+    pub fn audit(&self) -> Result<(), ChessError> {
+        let compress_alphabet = self.compress_options.alphabet;
+        let decompress_alphabet = self.decompress_options.alphabet;
+        if compress_alphabet == Alphabet::UrlSafeBase64 && decompress_alphabet != Alphabet::UrlSafeBase64 {
+            return Err(ChessError::new(
+                ErrorKind::IllegalConfig,
+                format!("games compressed to {compress_alphabet:?} can lack a format header, so they would be misread as {decompress_alphabet:?}"),
+            ));
+        }
+        if let Some(scramble_key) = &self.compress_options.scramble_key {
+            if self.decompress_options.scramble_key.as_ref() != Some(scramble_key) {
+                return Err(ChessError::new(ErrorKind::IllegalConfig, "games are scrambled with a key that decompression doesn't use".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn compress(&self, moves: Vec<Move>) -> Result<String, ChessError> {
+        self.compress_with_warnings(moves).map(|game| game.encoded)
+    }
+
+    pub fn compress_with_warnings(&self, moves: Vec<Move>) -> Result<CompressedGame, ChessError> {
+        compress_with_warnings(moves, &self.compress_options)
+    }
+
+    pub fn decompress(&self, encoded_game: &str) -> Result<DecompressedGame, ChessError> {
+        decompress_with_options(encoded_game, &self.decompress_options)
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+    use crate::base::util::tests::parse_to_vec;
+
+    #[test]
+    fn test_codecs_with_different_configurations() {
+        let moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3", ",").unwrap();
+        let qr_codec = Codec::new(Alphabet::QrAlphanumeric);
+        let scrambling_codec = Codec {
+            compress_options: CompressOptions { scramble_key: Some("tenant a".to_string()), ..CompressOptions::default() },
+            decompress_options: DecompressOptions { scramble_key: Some("tenant a".to_string()), ..DecompressOptions::default() },
+        };
+
+        let qr_game = qr_codec.compress(moves.clone()).unwrap();
+        let scrambled_game = scrambling_codec.compress(moves.clone()).unwrap();
+        assert_eq!(qr_game, ".Q.E90PA");
+        assert!(scrambled_game.starts_with(".S."));
+
+        assert_eq!(qr_codec.decompress(&qr_game).unwrap().moves.len(), 3);
+        assert_eq!(scrambling_codec.decompress(&scrambled_game).unwrap().moves.len(), 3);
+        assert!(qr_codec.decompress(&scrambled_game).is_err());
+    }
+
+    #[rstest(
+        compress_options, decompress_options, is_safe,
+        case(CompressOptions::default(), DecompressOptions::default(), true),
+        case(CompressOptions { alphabet: Alphabet::Base32, ..CompressOptions::default() }, DecompressOptions::default(), true),
+        case(CompressOptions::default(), DecompressOptions { alphabet: Alphabet::Base32, ..DecompressOptions::default() }, false),
+        case(CompressOptions { scramble_key: Some("key".to_string()), ..CompressOptions::default() }, DecompressOptions::default(), false),
+        case(CompressOptions { scramble_key: Some("key".to_string()), ..CompressOptions::default() }, DecompressOptions { scramble_key: Some("other key".to_string()), ..DecompressOptions::default() }, false),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_checked_codec(compress_options: CompressOptions, decompress_options: DecompressOptions, is_safe: bool) {
+        match Codec::checked(compress_options, decompress_options) {
+            Ok(_) => assert!(is_safe),
+            Err(error) => {
+                assert!(!is_safe);
+                assert!(matches!(error.kind, ErrorKind::IllegalConfig));
+            }
+        }
+    }
+}
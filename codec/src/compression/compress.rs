@@ -0,0 +1,395 @@
+use std::fmt;
+use std::sync::Arc;
+use crate::base::a_move::{Move, PromotionType};
+use crate::compression::alphabet::{Alphabet, to_alphabet};
+use crate::compression::base64::encode_base64;
+use crate::compression::checksum::append_checksum;
+use crate::compression::extension::{encode_extensions, Extensions};
+use crate::compression::error_correction::append_parity;
+use crate::compression::header::{FormatHeader, GAME_SEPARATOR, HEADER_DELIMITER};
+use crate::compression::metrics::{report, start_measurement, Metrics, MetricsReport};
+use crate::compression::scramble::scramble;
+use crate::compression::move_codec::{CompactTargetCodec, MoveCodec};
+use crate::compression::version::FormatVersion;
+use crate::figure::functions::is_reachable_by::get_positions_to_reach_target_from;
+use crate::base::color::Color;
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::position::Position;
+use crate::base::util::vec_to_str;
+use crate::game::game_state::{GameState, IllegalMoveReason};
+
+/// the options of [compress_with_options].
+/// New options are added with a default that keeps the previous behavior, so set the options you need
+/// and take the rest from `..CompressOptions::default()` to stay compatible with future versions.
+#[derive(Debug, Clone, Default)]
+pub struct CompressOptions {
+    pub alphabet: Alphabet,
+    /// appends 2 checksum chars, so that decompress fails with [ErrorKind::ChecksumMismatch] if the game was altered
+    pub checksum: bool,
+    /// appends 2 parity chars per 61 chars, so that decompress can fix a single wrong char in each of these blocks.
+    /// The parity is computed on the url-safe base64 form, so with a 5bit alphabet a single wrong char
+    /// can span two base64 chars, which is then only detected as uncorrectable (or, rarely, not at all).
+    pub error_correction: bool,
+    /// scrambles the game with this key, so it can only be decompressed by those who know the key.
+    /// This is obfuscation (good enough to keep casual observers from reading a shared game) not encryption.
+    pub scramble_key: Option<String>,
+    pub version: FormatVersion,
+    /// rich extension records (e.g. highlights and arrows) to add to the game
+    pub extensions: Extensions,
+    /// pawn moves to the last rank without promotion type are promoted to a queen
+    /// (and a [CompressWarning::QueenPromotionAssumed] is added) instead of failing with [ErrorKind::IllegalMoveInGame]
+    pub assume_queen_promotion: bool,
+    /// the callbacks every compress with these options reports to
+    pub metrics: Option<Arc<dyn Metrics>>,
+}
+
+pub struct CompressedGame {
+    pub encoded: String,
+    pub warnings: Vec<CompressWarning>,
+}
+
+/// something that didn't prevent compressing the game but should be known to the caller
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CompressWarning {
+    /// the pawn move at `half_move_index` had no promotion type, so a promotion to a queen was assumed
+    QueenPromotionAssumed { half_move_index: usize },
+    /// the start position has an en passant square, so it was reached by a pawn move
+    /// and the halfmove clock of its FEN (`halfmove_clock`) was reset to 0
+    HalfmoveClockReset { halfmove_clock: u32 },
+    /// the halfmove clock of the start position counts more half moves than were played according to its fullmove number,
+    /// so the fullmove number was raised from `fullmove_number` to `raised_to`
+    FullmoveNumberRaised { fullmove_number: u32, raised_to: u32 },
+    /// from the position at `position_index` on (the start position is 0) either player could claim a draw by the fifty-move rule
+    FiftyMoveRuleReached { position_index: usize },
+    /// the game was drawn by the seventy-five-move rule before the half move at `half_move_index`,
+    /// so the moves from there on continue a finished game
+    PlayedAfterSeventyFiveMoveRule { half_move_index: usize },
+}
+
+pub fn compress_with_options(moves: Vec<Move>, options: &CompressOptions) -> Result<String, ChessError> {
+    compress_from_with_options(GameState::classic(), moves, options).map(|game| game.encoded)
+}
+
+/// like [compress_with_options], but also returns the warnings
+pub fn compress_with_warnings(moves: Vec<Move>, options: &CompressOptions) -> Result<CompressedGame, ChessError> {
+    compress_from_with_options(GameState::classic(), moves, options)
+}
+
+/// like [compress_with_warnings], but the game starts from the given position, which is written to the format header.
+/// Inconsistent counters of the start position (the halfmove clock and the fullmove number of its FEN) are corrected
+/// (and a warning is added), so the FENs of the decompressed game are valid.
+pub fn compress_from_with_warnings(start: GameState, moves: Vec<Move>, options: &CompressOptions) -> Result<CompressedGame, ChessError> {
+    compress_from_with_options(start, moves, options)
+}
+
+/// compresses only the half moves `start_ply..end_ply` of the game. The encoded game starts with
+/// the FEN of the position reached after `start_ply` half moves, so decompress can continue from there.
+pub fn compress_range(moves: Vec<Move>, start_ply: usize, end_ply: usize) -> Result<String, ChessError> {
+    if start_ply > end_ply || end_ply > moves.len() {
+        return Err(ChessError::new(
+            ErrorKind::IllegalConfig,
+            format!("the range {start_ply}..{end_ply} doesn't fit into a game of {} half moves", moves.len()),
+        ));
+    }
+    let mut game_state = GameState::classic();
+    for (half_move_index, next_move) in moves[..start_ply].iter().enumerate() {
+        // makes sure that the moves before the range are legal, too
+        encode_next_move(*next_move, &game_state, half_move_index)?;
+        game_state = game_state.do_move(*next_move)?.0;
+    }
+    compress_from_with_options(game_state, moves[start_ply..end_ply].to_vec(), &CompressOptions::default()).map(|game| game.encoded)
+}
+
+pub(crate) fn compress_from_with_options(start: GameState, moves: Vec<Move>, options: &CompressOptions) -> Result<CompressedGame, ChessError> {
+    options.extensions.check_position_indices(moves.len())?;
+    let mut warnings: Vec<CompressWarning> = Vec::new();
+    let start = correct_move_counters(start, &mut warnings);
+    let start_fen = start.get_fen();
+    let moves = if options.assume_queen_promotion {
+        assume_queen_promotions(start.clone(), moves, &mut warnings)
+    } else {
+        moves
+    };
+    check_move_rules(&start, &moves, &mut warnings);
+    let mut base64_payload = compress_payload(start, moves, options.version.codec(), options.metrics.as_deref())?;
+    if options.checksum {
+        base64_payload = append_checksum(&base64_payload);
+    }
+    if options.error_correction {
+        base64_payload = append_parity(&base64_payload)?;
+    }
+    if let Some(key) = &options.scramble_key {
+        base64_payload = scramble(&base64_payload, key)?;
+    }
+    let header = FormatHeader {
+        alphabet: options.alphabet,
+        checksum: options.checksum,
+        error_correction: options.error_correction,
+        scrambled: options.scramble_key.is_some(),
+        version: options.version,
+        start_fen: (start_fen != GameState::classic().get_fen()).then_some(start_fen),
+        rich: !options.extensions.is_empty(),
+    };
+    let mut payload = to_alphabet(&base64_payload, options.alphabet)?;
+    if header.rich {
+        payload.push(HEADER_DELIMITER);
+        payload.push_str(&to_alphabet(&encode_extensions(&options.extensions), options.alphabet)?);
+    }
+    Ok(CompressedGame {
+        encoded: format!("{header}{payload}"),
+        warnings,
+    })
+}
+
+fn assume_queen_promotions(start: GameState, moves: Vec<Move>, warnings: &mut Vec<CompressWarning>) -> Vec<Move> {
+    let mut game_state = start;
+    moves.into_iter().enumerate().map(|(half_move_index, next_move)| {
+        let next_move = if next_move.promotion_type.is_none() && game_state.looks_like_pawn_promotion_move(next_move.from_to) {
+            warnings.push(CompressWarning::QueenPromotionAssumed { half_move_index });
+            Move::new_with_promotion(next_move.from_to, PromotionType::Queen)
+        } else {
+            next_move
+        };
+        // a move that can't be played is reported when the moves are encoded
+        if let Ok((new_game_state, _)) = game_state.do_move(next_move) {
+            game_state = new_game_state;
+        }
+        next_move
+    }).collect()
+}
+
+fn correct_move_counters(start: GameState, warnings: &mut Vec<CompressWarning>) -> GameState {
+    let mut halfmove_clock = start.get_half_moves_without_progress();
+    if start.en_passant_intercept_pos.is_some() && halfmove_clock != 0 {
+        warnings.push(CompressWarning::HalfmoveClockReset { halfmove_clock });
+        halfmove_clock = 0;
+    }
+    let mut fullmove_number = start.get_fullmove_number();
+    // white has already moved in the current move if it's black's turn
+    let half_moves_before_current_move = halfmove_clock.saturating_sub(u32::from(start.turn_by == Color::Black));
+    let lowest_fullmove_number = half_moves_before_current_move.div_ceil(2) + 1;
+    if fullmove_number < lowest_fullmove_number {
+        warnings.push(CompressWarning::FullmoveNumberRaised { fullmove_number, raised_to: lowest_fullmove_number });
+        fullmove_number = lowest_fullmove_number;
+    }
+    if halfmove_clock == start.get_half_moves_without_progress() && fullmove_number == start.get_fullmove_number() {
+        start
+    } else {
+        start.with_move_counters(halfmove_clock, fullmove_number)
+    }
+}
+
+fn check_move_rules(start: &GameState, moves: &[Move], warnings: &mut Vec<CompressWarning>) {
+    let mut game_state = start.clone();
+    let mut is_fifty_move_rule_reached = false;
+    for position_index in 0..=moves.len() {
+        let half_moves_without_progress = game_state.get_half_moves_without_progress();
+        // a mate on the last move counts, the move rules don't apply then
+        if half_moves_without_progress >= 100 && !is_fifty_move_rule_reached && !game_state.is_checkmate() {
+            is_fifty_move_rule_reached = true;
+            warnings.push(CompressWarning::FiftyMoveRuleReached { position_index });
+        }
+        let Some(next_move) = moves.get(position_index) else {
+            return;
+        };
+        if half_moves_without_progress >= 150 {
+            warnings.push(CompressWarning::PlayedAfterSeventyFiveMoveRule { half_move_index: position_index });
+            return;
+        }
+        // a move that can't be played is reported when the moves are encoded
+        let Ok((new_game_state, _)) = game_state.do_move(*next_move) else {
+            return;
+        };
+        game_state = new_game_state;
+    }
+}
+
+pub fn compress(moves: Vec<Move>) -> Result<String, ChessError> {
+    compress_payload(GameState::classic(), moves, &CompactTargetCodec, None)
+}
+
+/// compresses several games (e.g. of a study or a match) into a single string,
+/// the games are separated by [GAME_SEPARATOR] (see [decompress_games](crate::decompress::decompress_games)).
+/// Since an empty game is encoded as the empty string, there has to be at least one game.
+pub fn compress_games(games: Vec<Vec<Move>>) -> Result<String, ChessError> {
+    if games.is_empty() {
+        return Err(ChessError::new(
+            ErrorKind::IllegalConfig,
+            "at least one game is needed, an empty string would decode to one empty game".to_string(),
+        ));
+    }
+    let encoded_games = games.into_iter().enumerate()
+        .map(|(game_index, moves)| compress(moves).map_err(|mut error| {
+            error.msg = format!("game {}: {}", game_index + 1, error.msg);
+            error
+        }))
+        .collect::<Result<Vec<String>, ChessError>>()?;
+    Ok(encoded_games.join(&GAME_SEPARATOR.to_string()))
+}
+
+/// compresses a game that starts from the given position (e.g. a puzzle or an adjourned game).
+/// Like with [compress] the result only contains the moves, so it has to be decompressed
+/// from the same position (see [decompress_from](crate::compression::decompress::decompress_from)).
+/// Use [compress_range] for games that should carry their start position.
+pub fn compress_from(start: GameState, moves: Vec<Move>) -> Result<String, ChessError> {
+    compress_payload(start, moves, &CompactTargetCodec, None)
+}
+
+/// like [compress], but every move is checked against the legal moves of its position first,
+/// so moves that leave the own king in check (e.g. by a pinned figure) are rejected
+/// with [ErrorKind::IllegalMoveInGame] instead of being encoded.
+pub fn compress_validated(moves: Vec<Move>) -> Result<String, ChessError> {
+    let mut game_state = GameState::classic();
+    for (half_move_index, next_move) in moves.iter().enumerate() {
+        check_legality(*next_move, &game_state, half_move_index)?;
+        game_state = game_state.do_move(*next_move)?.0;
+    }
+    compress(moves)
+}
+
+fn check_legality(next_move: Move, game_state: &GameState, half_move_index: usize) -> Result<(), ChessError> {
+    game_state.is_legal(next_move).map_err(|reason| illegal_move_error(next_move, game_state, half_move_index, reason))
+}
+
+/// the error of a move that can't be played in the given state, the message is built from the reason
+pub(crate) fn illegal_move_error(next_move: Move, game_state: &GameState, half_move_index: usize, reason: IllegalMoveReason) -> ChessError {
+    let move_nr = 1 + half_move_index / 2;
+    let msg = match game_state.turn_by {
+        Color::White => format!("move {move_nr}. {next_move} .. is illegal since {reason}"),
+        Color::Black => format!("move {move_nr}. .. {next_move} is illegal since {reason}"),
+    };
+    ChessError::new(ErrorKind::IllegalMoveInGame { half_move_index, a_move: next_move, reason }, msg)
+}
+
+pub(crate) fn compress_payload(start: GameState, moves: Vec<Move>, codec: &dyn MoveCodec, metrics: Option<&dyn Metrics>) -> Result<String, ChessError> {
+    let started = start_measurement(metrics);
+    let half_moves = moves.len();
+    let result = encode_moves(start, moves, codec);
+    report(metrics, started, |metrics, duration| metrics.on_compress(&MetricsReport {
+        half_moves,
+        encoded_len: result.as_ref().map_or(0, |encoded_moves| encoded_moves.len()),
+        duration,
+        failed: result.is_err(),
+    }));
+    result
+}
+
+fn encode_moves(start: GameState, moves: Vec<Move>, codec: &dyn MoveCodec) -> Result<String, ChessError> {
+    let mut game_state = start;
+    let mut encoded_moves = String::with_capacity(moves.len()*2);
+
+    for (half_move_index, next_move) in moves.into_iter().enumerate() {
+        let encoded_move = codec.encode_move(next_move, &game_state, half_move_index)?;
+        encoded_moves.push_str(&encoded_move);
+        game_state = game_state.do_move(next_move)?.0;
+    }
+
+    Ok(encoded_moves)
+}
+
+/// returns the chars (1 to 3) that encode the move played in the given state
+pub(crate) fn encode_next_move(next_move: Move, game_state: &GameState, half_move_index: usize) -> Result<String, ChessError> {
+    encode_next_move_with_reason(next_move, game_state, half_move_index).map(|(encoded_move, _)| encoded_move)
+}
+
+/// like [encode_next_move] but also returns why the from-position was (or wasn't) encoded
+pub(crate) fn encode_next_move_with_reason(next_move: Move, game_state: &GameState, half_move_index: usize) -> Result<(String, EncodingReason), ChessError> {
+    let active_color = game_state.turn_by;
+    let target_pos = next_move.from_to.to;
+    let reason = {
+        if game_state.looks_like_castling(next_move.from_to)? {
+            // the decoder rejects castlings that aren't allowed, since king and rook could end up anywhere otherwise
+            if let Err(reason @ IllegalMoveReason::CastlingNotAllowed) = game_state.is_legal(next_move) {
+                return Err(illegal_move_error(next_move, game_state, half_move_index, reason));
+            }
+            EncodingReason::Castling { rook: target_pos }
+        } else {
+            if game_state.looks_like_en_passant(next_move.from_to) && game_state.en_passant_intercept_pos != Some(target_pos) {
+                // otherwise the move would be reported as unreachable, which doesn't tell what's wrong with it
+                return Err(illegal_move_error(next_move, game_state, half_move_index, IllegalMoveReason::NoEnPassant));
+            }
+            let positions_with_figures_that_can_reach_target: Vec<Position> = get_positions_to_reach_target_from(target_pos, game_state)?;
+            if !positions_with_figures_that_can_reach_target.contains(&next_move.from_to.from) {
+                let move_nr = 1 + half_move_index / 2;
+                let reason = match game_state.board.get_figure(next_move.from_to.from) {
+                    None => IllegalMoveReason::NoFigureToMove,
+                    Some(figure) if figure.color != active_color => IllegalMoveReason::NotYourFigure,
+                    Some(_) => IllegalMoveReason::UnreachableTarget,
+                };
+                let err_msg = {
+                    let moving_figure_type = match &game_state.board.get_figure(next_move.from_to.from).map(|figure|figure.fig_type) {
+                        None => {"Empty".to_string()}
+                        Some(figure_type) => {format!("{figure_type:?}")}
+                    };
+                    let mut msg = match active_color {
+                        Color::White => format!("move {move_nr}. {next_move} .. "),
+                        Color::Black => format!("move {move_nr}. .. {next_move} "),
+                    };
+                    msg.push_str(format!("is illegal since you can't go there with a {moving_figure_type}. {} is only reachable from {}", next_move.from_to.from, vec_to_str(&positions_with_figures_that_can_reach_target, ", ")).as_str());
+                    msg
+                };
+                return Err(ChessError::new(ErrorKind::IllegalMoveInGame { half_move_index, a_move: next_move, reason }, err_msg));
+            };
+            let other_origins: Vec<Position> = positions_with_figures_that_can_reach_target.into_iter()
+                .filter(|origin| *origin != next_move.from_to.from)
+                .collect();
+            if other_origins.is_empty() {
+                EncodingReason::UniqueTarget { target: target_pos }
+            } else {
+                EncodingReason::SharedTarget { target: target_pos, other_origins }
+            }
+        }
+    };
+
+    let is_promotion = game_state.looks_like_pawn_promotion_move(next_move.from_to);
+    if is_promotion != next_move.promotion_type.is_some() {
+        let (msg, reason) = if is_promotion {
+            (format!("half move {half_move_index} ({next_move}) is a pawn promotion, but the promotion type is missing"), IllegalMoveReason::MissingPromotionType)
+        } else {
+            (format!("half move {half_move_index} ({next_move}) has a promotion type, but isn't a pawn promotion"), IllegalMoveReason::UnexpectedPromotionType)
+        };
+        return Err(ChessError::new(ErrorKind::IllegalMoveInGame { half_move_index, a_move: next_move, reason }, msg));
+    }
+
+    let mut encoded_move = String::with_capacity(3);
+    if matches!(reason, EncodingReason::UniqueTarget { .. }) {
+        // only to-position is required to reconstruct whole FromTo
+        encoded_move.push(encode_base64(next_move.from_to.to));
+    } else {
+        // encode from- and to-positions
+        encoded_move.push(encode_base64(next_move.from_to.from));
+        encoded_move.push(encode_base64(next_move.from_to.to));
+    };
+    if let Some(promotion_type) = next_move.promotion_type {
+        encoded_move.push(promotion_type.as_encoded());
+    };
+    Ok((encoded_move, reason))
+}
+
+/// why a move is encoded with one or two position chars (not counting the promotion type char)
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum EncodingReason {
+    /// only the moving figure can reach the target, so the to-position is enough (1 char)
+    UniqueTarget { target: Position },
+    /// other figures can reach the target too, so the from-position is needed as well (2 chars)
+    SharedTarget { target: Position, other_origins: Vec<Position> },
+    /// the king moves to its rook, which is always written with both positions (2 chars)
+    Castling { rook: Position },
+}
+
+impl fmt::Display for EncodingReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingReason::UniqueTarget { target } => write!(f, "1 char, only one figure can reach {target}"),
+            EncodingReason::SharedTarget { target, other_origins } => {
+                write!(f, "2 chars, {target} can also be reached from {}", vec_to_str(other_origins, ", "))
+            }
+            EncodingReason::Castling { rook } => write!(f, "2 chars, castling is written as the king moving to the rook on {rook}"),
+        }
+    }
+}
+
+// Tests are in compression/mod.rs
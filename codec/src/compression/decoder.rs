@@ -0,0 +1,139 @@
+use crate::base::a_move::MoveData;
+use crate::base::errors::ChessError;
+use crate::compression::decompress::{decode_next_move, DecodedMove};
+use crate::game::game_state::GameState;
+
+/// decodes a game whose encoding arrives in pieces (e.g. during a live broadcast).
+///
+/// Chars of a move that isn't complete yet are kept until the rest arrives.
+/// Only the canonical form (url-safe base64 without format header) is supported.
+/// With the serde feature the decoder can be persisted and resumed later without replaying the game.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decoder {
+    game_state: GameState,
+    pending_chars: String,
+    half_moves_decoded: usize,
+}
+
+impl Decoder {
+    pub fn new() -> Decoder {
+        Decoder {
+            game_state: GameState::classic(),
+            pending_chars: String::new(),
+            half_moves_decoded: 0,
+        }
+    }
+
+    /// adds the next chars of the encoded game and returns the moves completed by them.
+    /// After an error the decoder is unchanged, so the faulty chars stay pending.
+    pub fn push_str(&mut self, encoded_chars: &str) -> Result<Vec<MoveData>, ChessError> {
+        let mut game_state = self.game_state.clone();
+        let all_chars = format!("{}{encoded_chars}", self.pending_chars);
+        // a cursor into all_chars, only the chars of an incomplete move are copied at the end
+        let mut chars: &str = &all_chars;
+        let mut moves_decoded: Vec<MoveData> = Vec::new();
+        while !chars.is_empty() {
+            let mut remaining_chars = chars.chars();
+            let next_move = match decode_next_move(&mut remaining_chars, &game_state, self.half_moves_decoded + moves_decoded.len())? {
+                DecodedMove::Complete(next_move) => next_move,
+                DecodedMove::Incomplete(_) => break,
+            };
+            chars = remaining_chars.as_str();
+            let (new_game_state, move_data) = game_state.do_move(next_move)?;
+            game_state = new_game_state;
+            moves_decoded.push(move_data);
+        }
+        self.game_state = game_state;
+        self.pending_chars = chars.to_string();
+        self.half_moves_decoded += moves_decoded.len();
+        Ok(moves_decoded)
+    }
+
+    /// the state after the last complete move
+    pub fn get_game_state(&self) -> &GameState {
+        &self.game_state
+    }
+
+    /// the chars of a move that isn't complete yet
+    pub fn get_pending_chars(&self) -> &str {
+        &self.pending_chars
+    }
+
+    pub fn get_half_moves_decoded(&self) -> usize {
+        self.half_moves_decoded
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Decoder::new()
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::a_move::{Move, MoveType};
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::compress;
+    use super::*;
+
+    #[rstest(
+        decoded_moves,
+        case("e2e4, e7e5, g1f3"),
+        case("c2c3, d7d5, d1b3, c8h3"),
+        case("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4, g2g3, h4g3, c7d8Q"),
+        case("d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decoding_char_by_char(decoded_moves: &str) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let encoded_game = compress(given_moves.clone()).unwrap();
+        let mut decoder = Decoder::new();
+        let mut actual_moves: Vec<Move> = Vec::new();
+        for c in encoded_game.chars() {
+            let moves_data = decoder.push_str(&c.to_string()).unwrap();
+            actual_moves.extend(moves_data.iter().map(|move_data| match move_data.move_type {
+                MoveType::PawnPromotion { promoted_to } => Move::new_with_promotion(move_data.given_from_to, promoted_to),
+                _ => Move::new(move_data.given_from_to),
+            }));
+        }
+        assert_eq!(actual_moves, given_moves);
+        assert_eq!(decoder.get_pending_chars(), "");
+        assert_eq!(decoder.get_half_moves_decoded(), given_moves.len());
+    }
+
+    #[test]
+    fn test_incomplete_move_stays_pending() {
+        let mut decoder = Decoder::new();
+        // "a" is c2c4, "0" is e7 which (as position of a black figure) needs a target
+        assert_eq!(decoder.push_str("a0").unwrap().len(), 1);
+        assert_eq!(decoder.get_pending_chars(), "0");
+        assert!(decoder.push_str("%").is_err());
+        assert_eq!(decoder.get_pending_chars(), "0");
+        assert_eq!(decoder.push_str("k").unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_decoder_can_be_resumed_after_serialization() {
+        let given_moves: Vec<Move> = parse_to_vec("d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1", ",").unwrap();
+        let encoded_game = compress(given_moves.clone()).unwrap();
+        let (first_part, second_part) = encoded_game.split_at(6);
+
+        let mut decoder = Decoder::new();
+        let first_moves = decoder.push_str(first_part).unwrap();
+        let bytes = postcard::to_allocvec(&decoder).unwrap();
+        let mut resumed_decoder: Decoder = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(resumed_decoder.get_pending_chars(), decoder.get_pending_chars());
+        let second_moves = resumed_decoder.push_str(second_part).unwrap();
+
+        assert_eq!(first_moves.len() + second_moves.len(), given_moves.len());
+        assert_eq!(resumed_decoder.get_half_moves_decoded(), given_moves.len());
+        let (positions, _) = crate::compression::decompress::decompress(&encoded_game).unwrap();
+        assert_eq!(resumed_decoder.get_game_state().get_fen(), positions.last().unwrap().fen);
+    }
+}
@@ -0,0 +1,590 @@
+use std::str::Chars;
+use std::sync::Arc;
+use crate::base::a_move::{FromTo, Move, MoveData, MoveSquares, PromotionType};
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::position::Position;
+use crate::compression::alphabet::{Alphabet, AlphabetVariant, from_alphabet};
+use crate::compression::base64::{assert_is_url_safe_base64, decode_base64, encode_base64};
+use crate::compression::checksum::verify_and_strip_checksum;
+use crate::compression::compress::illegal_move_error;
+use crate::compression::error_correction::{append_parity, correct_and_strip_parity};
+use crate::compression::extension::{decode_extensions, Annotation, Comment, Extensions, Nag};
+use crate::compression::header::{FormatHeader, GAME_SEPARATOR, HEADER_DELIMITER};
+use crate::compression::metrics::{report, start_measurement, Metrics, MetricsReport};
+use crate::compression::repair::decompress_with_confusables_replaced;
+use crate::compression::scramble::unscramble;
+use crate::compression::summary::GameResult;
+use crate::compression::move_codec::MoveCodec;
+use crate::figure::functions::is_reachable_by::get_positions_to_reach_target_from;
+use crate::game::game_state::{DrawStatus, GameState, IllegalMoveReason};
+use crate::notation::san::to_san;
+
+/// the options of [decompress_with_options].
+/// New options are added with a default that keeps the previous behavior, so set the options you need
+/// and take the rest from `..DecompressOptions::default()` to stay compatible with future versions.
+#[derive(Debug, Clone, Default)]
+pub struct DecompressOptions {
+    /// the alphabet of games without format header (games with header bring their own)
+    pub alphabet: Alphabet,
+    /// the base64 variant the game was converted to (only needed for legacy data, see [AlphabetVariant::Standard])
+    pub alphabet_variant: AlphabetVariant,
+    /// the key of scrambled games (it's ignored for games that aren't scrambled)
+    pub scramble_key: Option<String>,
+    /// fails with [ErrorKind::LimitExceeded] if the game contains more half moves
+    pub max_half_moves: Option<usize>,
+    /// fails with [ErrorKind::LimitExceeded] if the encoded game (incl. format header) is longer (in bytes),
+    /// this is checked before any decoding happens
+    pub max_input_len: Option<usize>,
+    /// stops decoding once the game is drawn by the fifty-move rule or threefold repetition
+    /// (the rest of the input is ignored and a [DecodeWarning::StoppedAtDraw] is added).
+    /// This bounds the work spent on inputs that keep shuffling figures back and forth.
+    pub stop_at_draw: bool,
+    /// adds the Polyglot Zobrist key of every position to its [PositionData]
+    pub zobrist_keys: bool,
+    /// adds the move that led to each position in standard algebraic notation to its [PositionData]
+    pub san: bool,
+    /// also returns the moves after the reveal boundary of the game (see [Extensions::reveal_boundary])
+    pub allow_spoilers: bool,
+    /// if the game can't be decoded as given, smart dashes are replaced by '-' and 'O' and '0' are swapped
+    /// until it can (each replacement is reported as [DecodeWarning::ConfusableCharReplaced])
+    pub repair_confusables: bool,
+    /// the callbacks every decompress with these options reports to
+    pub metrics: Option<Arc<dyn Metrics>>,
+    /// computes the FENs of all positions in parallel once all moves are decoded
+    #[cfg(feature = "rayon")]
+    pub parallel_fen: bool,
+}
+
+pub struct DecompressedGame {
+    /// is 1 longer than `moves`, since the initial Position exist before the first move
+    pub positions: Vec<PositionData>,
+    pub moves: Vec<MoveData>,
+    pub warnings: Vec<DecodeWarning>,
+    /// the extension records of the game (they are also added to the positions they belong to)
+    pub extensions: Extensions,
+    /// the result as far as it can be told from the final position (see [GameState::get_game_result]),
+    /// a result that was stored with the game is part of the extensions
+    pub result: GameResult,
+    /// the index of the first position in which neither player has the material to mate anymore (a dead draw),
+    /// the moves after it don't change the result. This is set even if a different result was stored with the game.
+    pub dead_draw_at: Option<usize>,
+}
+
+/// something that didn't prevent decoding the game but should be known to the caller
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeWarning {
+    /// the error correction replaced a wrong char.
+    /// `index` points into the url-safe base64 form of the game (without format header)
+    CorrectedChar { index: usize, found: char, corrected_to: char },
+    /// decoding stopped after `half_moves_decoded` half moves, since the game was drawn at that point.
+    /// `ignored_chars` (of the url-safe base64 form) weren't decoded.
+    StoppedAtDraw { reason: DrawReason, half_moves_decoded: usize, ignored_chars: usize },
+    /// the last `hidden_half_moves` half moves (and their extension records) weren't returned,
+    /// since they are after the reveal boundary of the game and spoilers weren't allowed
+    SpoilersHidden { hidden_half_moves: usize },
+    /// the char at `index` (a char index into the given encoded game) was replaced by a char that looks alike,
+    /// since the game couldn't be decoded otherwise (see [DecompressOptions::repair_confusables])
+    ConfusableCharReplaced { index: usize, found: char, replaced_by: char },
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DrawReason {
+    /// 50 moves by each player without a capture or pawn move
+    FiftyMoveRule,
+    /// the same position occurred for the third time
+    ThreefoldRepetition,
+}
+
+/// the length of Vec<PositionData> is 1 higher than the length of Vec<MoveData>, since the initial Position exist before the first move.
+/// Malformed input results in a ChessError, decompressing never panics (this is fuzzed by the target in the fuzz directory).
+pub fn decompress(base64_encoded_match: &str) -> Result<(Vec<PositionData>, Vec<MoveData>), ChessError> {
+    let game = decompress_with_options(base64_encoded_match, &DecompressOptions::default())?;
+    Ok((game.positions, game.moves))
+}
+
+/// where and why [decompress_lossy] stopped decoding a game
+#[derive(Debug)]
+pub struct DecodeFailure {
+    /// the index of the first half move that couldn't be decoded (which is the number of moves that could)
+    pub half_move_index: usize,
+    /// the index of the first char of that move in the url-safe base64 form of the game (without format header)
+    pub char_index: usize,
+    pub error: ChessError,
+}
+
+/// decompresses as much of a damaged (e.g. truncated or mistyped) game as possible, so that viewers
+/// can still show the playable beginning of it. Decoding stops at the first move that can't be decoded,
+/// the returned failure tells where and why (it's `None` if the whole game could be decoded).
+///
+/// This still fails if not a single move can be decoded with certainty, e.g. if the format header is invalid
+/// or the checksum doesn't match.
+pub fn decompress_lossy(encoded_match: &str) -> Result<(DecompressedGame, Option<DecodeFailure>), ChessError> {
+    let options = DecompressOptions::default();
+    let CanonicalForm { header, moves: base64_encoded_match, extensions, warnings } = to_canonical_form(encoded_match, &options)?;
+    decode_game_until_failure(header.start_game_state()?, &base64_encoded_match, header.version.codec(), extensions, warnings, &options)
+}
+
+/// decompresses the games packed into one string by [compress_games](crate::compression::compress::compress_games).
+/// Every game may have its own format header.
+pub fn decompress_games(encoded_games: &str) -> Result<Vec<DecompressedGame>, ChessError> {
+    let options = DecompressOptions::default();
+    encoded_games.split(GAME_SEPARATOR).enumerate()
+        .map(|(game_index, encoded_game)| decompress_with_options(encoded_game, &options).map_err(|mut error| {
+            error.msg = format!("game {}: {}", game_index + 1, error.msg);
+            error
+        }))
+        .collect()
+}
+
+/// decompresses a game that starts from the given position (see [compress_from](crate::compression::compress::compress_from)).
+/// Games that carry their own start position can only be decompressed from that one.
+pub fn decompress_from(start: GameState, encoded_match: &str) -> Result<(Vec<PositionData>, Vec<MoveData>), ChessError> {
+    let options = DecompressOptions::default();
+    let CanonicalForm { header, moves: base64_encoded_match, extensions, warnings } = to_canonical_form(encoded_match, &options)?;
+    if let Some(start_fen) = &header.start_fen {
+        if *start_fen != start.get_fen() {
+            return Err(ChessError::new(ErrorKind::IllegalConfig, format!("{encoded_match} starts from {start_fen}, not from {}", start.get_fen())));
+        }
+    }
+    let game = decode_game(start, &base64_encoded_match, header.version.codec(), extensions, warnings, &options)?;
+    Ok((game.positions, game.moves))
+}
+
+pub fn decompress_with_options(encoded_match: &str, options: &DecompressOptions) -> Result<DecompressedGame, ChessError> {
+    if let Some(max_input_len) = options.max_input_len {
+        if encoded_match.len() > max_input_len {
+            return Err(ChessError::new(
+                ErrorKind::LimitExceeded,
+                format!("encoded game is {} bytes long, but only {max_input_len} are allowed", encoded_match.len()),
+            ));
+        }
+    }
+    match decompress_as_given(encoded_match, options) {
+        Err(error) if options.repair_confusables => decompress_with_confusables_replaced(encoded_match, options).ok_or(error),
+        result => result,
+    }
+}
+
+fn decompress_as_given(encoded_match: &str, options: &DecompressOptions) -> Result<DecompressedGame, ChessError> {
+    let CanonicalForm { header, moves: base64_encoded_match, extensions, warnings } = to_canonical_form(encoded_match, options)?;
+    decode_game(header.start_game_state()?, &base64_encoded_match, header.version.codec(), extensions, warnings, options)
+}
+
+/// returns the FEN of the final position, e.g. for previews of shared games.
+///
+/// This is faster than [decompress] since neither the moves nor the FENs of the positions in between are kept.
+/// Moves behind the reveal boundary aren't played (see [DecompressOptions::allow_spoilers]).
+pub fn final_fen(encoded_match: &str) -> Result<String, ChessError> {
+    play_moves(encoded_match, |_, _| {}).map(|final_state| final_state.get_fen())
+}
+
+/// plays the moves of a game without keeping the positions in between and returns the final state.
+/// `on_move` is called with every move and the state it's played in.
+/// Moves behind the reveal boundary aren't played (see [DecompressOptions::allow_spoilers]).
+pub(crate) fn play_moves(encoded_match: &str, mut on_move: impl FnMut(Move, &GameState)) -> Result<GameState, ChessError> {
+    let CanonicalForm { header, moves: base64_encoded_match, extensions, .. } = to_canonical_form(encoded_match, &DecompressOptions::default())?;
+    let codec = header.version.codec();
+    let mut encoded_chars: Chars = base64_encoded_match.chars();
+    let mut game_state = header.start_game_state()?;
+    // the hidden moves are still decoded, so that invalid games are rejected just like by decompress
+    let mut revealed_game_state: Option<GameState> = None;
+    let mut half_move_index = 0;
+    while !encoded_chars.as_str().is_empty() {
+        if extensions.reveal_boundary == Some(half_move_index) {
+            revealed_game_state = Some(game_state.clone());
+        }
+        let next_move = match codec.decode_move(&mut encoded_chars, &game_state, half_move_index)? {
+            DecodedMove::Complete(next_move) => next_move,
+            DecodedMove::Incomplete(error) => return Err(error),
+        };
+        if revealed_game_state.is_none() {
+            on_move(next_move, &game_state);
+        }
+        game_state = game_state.do_move(next_move)?.0;
+        half_move_index += 1;
+    }
+    extensions.check_position_indices(half_move_index)?;
+    Ok(revealed_game_state.unwrap_or(game_state))
+}
+
+/// decodes the moves of a game that was already stripped of everything else (see [to_canonical_form])
+pub(crate) fn decode_game(
+    start: GameState,
+    base64_encoded_match: &str,
+    codec: &dyn MoveCodec,
+    extensions: Extensions,
+    warnings: Vec<DecodeWarning>,
+    options: &DecompressOptions,
+) -> Result<DecompressedGame, ChessError> {
+    match decode_game_until_failure(start, base64_encoded_match, codec, extensions, warnings, options)? {
+        (game, None) => Ok(game),
+        (_, Some(failure)) => Err(failure.error),
+    }
+}
+
+/// like [decode_game], but a move that can't be decoded only ends the game instead of failing it.
+/// Limits set in the options are still enforced.
+fn decode_game_until_failure(
+    start: GameState,
+    base64_encoded_match: &str,
+    codec: &dyn MoveCodec,
+    extensions: Extensions,
+    warnings: Vec<DecodeWarning>,
+    options: &DecompressOptions,
+) -> Result<(DecompressedGame, Option<DecodeFailure>), ChessError> {
+    let metrics = options.metrics.as_deref();
+    let started = start_measurement(metrics);
+    let result = decode_moves_until_failure(start, base64_encoded_match, codec, extensions, warnings, options);
+    report(metrics, started, |metrics, duration| metrics.on_decompress(&MetricsReport {
+        half_moves: match &result {
+            Ok((game, _)) => game.moves.len(),
+            Err(_) => 0,
+        },
+        encoded_len: base64_encoded_match.len(),
+        duration,
+        failed: !matches!(result, Ok((_, None))),
+    }));
+    result
+}
+
+fn decode_moves_until_failure(
+    start: GameState,
+    base64_encoded_match: &str,
+    codec: &dyn MoveCodec,
+    mut extensions: Extensions,
+    mut warnings: Vec<DecodeWarning>,
+    options: &DecompressOptions,
+) -> Result<(DecompressedGame, Option<DecodeFailure>), ChessError> {
+    let mut encoded_chars: Chars = base64_encoded_match.chars();
+    // counted per move, recounting the remaining chars every time would take quadratic time
+    let mut char_index: usize = 0;
+    let mut failure: Option<DecodeFailure> = None;
+    let mut game_state = start;
+    let mut moves_played: Vec<MoveData> = Vec::new();
+    // FENs are computed at the end, so that it can happen in parallel
+    let mut game_states_reached: Vec<GameState> = vec![game_state.clone()];
+    // material never increases, so once insufficient it stays insufficient
+    let mut dead_draw_at: Option<usize> = (!game_state.board.contains_sufficient_material_to_continue()).then_some(0);
+
+    let mut half_move_index = 0;
+    while !encoded_chars.as_str().is_empty() {
+        if let Some(max_half_moves) = options.max_half_moves {
+            if half_move_index >= max_half_moves {
+                return Err(ChessError::new(
+                    ErrorKind::LimitExceeded,
+                    format!("encoded game contains more than the allowed {max_half_moves} half moves"),
+                ));
+            }
+        }
+        let chars_before_move = encoded_chars.as_str();
+        let next_move = match codec.decode_move(&mut encoded_chars, &game_state, half_move_index) {
+            Ok(DecodedMove::Complete(next_move)) => next_move,
+            Ok(DecodedMove::Incomplete(error)) | Err(error) => {
+                failure = Some(DecodeFailure { half_move_index, char_index, error });
+                break;
+            }
+        };
+
+        let (new_game_state, latest_move_data) = match game_state.do_move(next_move) {
+            Ok(game_state_and_move_data) => game_state_and_move_data,
+            Err(error) => {
+                failure = Some(DecodeFailure { half_move_index, char_index, error });
+                break;
+            }
+        };
+        game_state = new_game_state;
+        game_states_reached.push(game_state.clone());
+        moves_played.push(latest_move_data);
+        half_move_index += 1;
+        if dead_draw_at.is_none() && !game_state.board.contains_sufficient_material_to_continue() {
+            dead_draw_at = Some(half_move_index);
+        }
+        char_index += chars_before_move[..chars_before_move.len() - encoded_chars.as_str().len()].chars().count();
+
+        if options.stop_at_draw {
+            let draw_reason = game_state.draw_status().map(|draw_status| match draw_status {
+                DrawStatus::ThreefoldAvailable | DrawStatus::FivefoldForced => DrawReason::ThreefoldRepetition,
+                _ => DrawReason::FiftyMoveRule,
+            });
+            if let (Some(reason), false) = (draw_reason, encoded_chars.as_str().is_empty()) {
+                let ignored_chars = encoded_chars.as_str().chars().count();
+                warnings.push(DecodeWarning::StoppedAtDraw { reason, half_moves_decoded: half_move_index, ignored_chars });
+                break;
+            }
+        }
+    }
+
+    if failure.is_some() {
+        // the records of the moves that couldn't be decoded are lost with them
+        extensions.truncate(moves_played.len());
+    } else if encoded_chars.as_str().is_empty() {
+        extensions.check_position_indices(moves_played.len())?;
+    }
+    if let (Some(reveal_boundary), false) = (extensions.reveal_boundary, options.allow_spoilers) {
+        if moves_played.len() > reveal_boundary {
+            warnings.push(DecodeWarning::SpoilersHidden { hidden_half_moves: moves_played.len() - reveal_boundary });
+            moves_played.truncate(reveal_boundary);
+            game_states_reached.truncate(reveal_boundary + 1);
+            extensions.truncate(reveal_boundary);
+            // the result would spoil the hidden moves, too
+            extensions.metadata.result = None;
+            dead_draw_at = dead_draw_at.filter(|position_index| *position_index <= reveal_boundary);
+        }
+    }
+    let final_state = game_states_reached.last().expect("there is always a start position");
+    let result = final_state.get_game_result();
+    let mut positions = to_position_data(&game_states_reached, options);
+    for (position, move_data) in positions.iter_mut().skip(1).zip(&moves_played) {
+        position.last_move = Some(move_data.squares());
+    }
+    if options.san {
+        for ((position, move_data), game_state_before) in positions.iter_mut().skip(1).zip(&moves_played).zip(&game_states_reached) {
+            position.san = Some(to_san(move_data.given_move(), game_state_before));
+        }
+    }
+    for (position_index, annotations) in &extensions.annotations {
+        if let Some(position) = positions.get_mut(*position_index) {
+            position.annotations = annotations.clone();
+        }
+    }
+    for (position_index, comments) in &extensions.comments {
+        if let Some(position) = positions.get_mut(*position_index) {
+            position.comments = comments.clone();
+        }
+    }
+    for (position_index, nags) in &extensions.nags {
+        if let Some(position) = positions.get_mut(*position_index) {
+            position.nags = nags.clone();
+        }
+    }
+
+    let game = DecompressedGame {
+        positions,
+        moves: moves_played,
+        warnings,
+        extensions,
+        result,
+        dead_draw_at,
+    };
+    Ok((game, failure))
+}
+
+pub(crate) struct CanonicalForm {
+    pub header: FormatHeader,
+    /// the encoded moves in url-safe base64
+    pub moves: String,
+    pub extensions: Extensions,
+    pub warnings: Vec<DecodeWarning>,
+}
+
+/// strips format header, alphabet, scrambling, error correction and checksum, so that only the encoded moves
+/// (in the format version of the returned header) remain
+pub(crate) fn to_canonical_form(encoded_match: &str, options: &DecompressOptions) -> Result<CanonicalForm, ChessError> {
+    let encoded_match = &options.alphabet_variant.to_url_safe(encoded_match);
+    let (header, mut payload) = FormatHeader::split_from(encoded_match)?;
+    let alphabet = if header.is_default() {
+        options.alphabet
+    } else {
+        header.alphabet
+    };
+    let mut extensions = Extensions::default();
+    if header.rich {
+        let Some((moves_payload, extensions_payload)) = payload.split_once(HEADER_DELIMITER) else {
+            return Err(ChessError::new(
+                ErrorKind::IllegalFormat,
+                format!("{encoded_match} should contain extension records after a '{HEADER_DELIMITER}'"),
+            ));
+        };
+        extensions = decode_extensions(&from_alphabet(extensions_payload, alphabet)?)?;
+        payload = moves_payload;
+    }
+    let mut base64_encoded_match = from_alphabet(payload, alphabet)?;
+    assert_is_url_safe_base64(&base64_encoded_match)?;
+    if header.scrambled {
+        let Some(key) = &options.scramble_key else {
+            return Err(ChessError::new(ErrorKind::IllegalConfig, format!("{encoded_match} is scrambled, it can't be decompressed without key")));
+        };
+        base64_encoded_match = unscramble(&base64_encoded_match, key)?;
+    }
+    let mut warnings: Vec<DecodeWarning> = Vec::new();
+    if header.error_correction {
+        let (corrected_match, corrected_indices) = correct_and_strip_parity(&base64_encoded_match)?;
+        if !corrected_indices.is_empty() {
+            // recomputing the parity also yields the correct value of corrected parity chars
+            let found_chars: Vec<char> = base64_encoded_match.chars().collect();
+            let corrected_chars: Vec<char> = append_parity(&corrected_match)?.chars().collect();
+            warnings.extend(corrected_indices.into_iter().map(|index| DecodeWarning::CorrectedChar {
+                index,
+                found: found_chars[index],
+                corrected_to: corrected_chars[index],
+            }));
+        }
+        base64_encoded_match = corrected_match;
+    }
+    if header.checksum {
+        base64_encoded_match = verify_and_strip_checksum(&base64_encoded_match)?.to_string();
+    }
+    Ok(CanonicalForm { header, moves: base64_encoded_match, extensions, warnings })
+}
+
+pub enum DecodedMove {
+    Complete(Move),
+    /// the chars ended before the move was complete, the error describes what's missing
+    Incomplete(ChessError),
+}
+
+/// decodes the move starting at the next char, only consumes the chars of that move.
+pub(crate) fn decode_next_move(
+    encoded_chars: &mut Chars,
+    game_state: &GameState,
+    half_move_index: usize,
+) -> Result<DecodedMove, ChessError> {
+    let move_index = half_move_index / 2;
+    let active_color = game_state.turn_by;
+    let Some(first_char) = encoded_chars.next() else {
+        return Ok(DecodedMove::Incomplete(ChessError::new(ErrorKind::IllegalFormat, format!("{move_index} move for {active_color} is missing"))));
+    };
+    let first_pos: Position = decode_base64(first_char)?;
+
+    let names_origin = game_state.board.contains_color(first_pos, active_color);
+    let from_to = if names_origin {
+        let to_pos: Position = match encoded_chars.next() {
+            None => {
+                return Ok(DecodedMove::Incomplete(ChessError::new(
+                    ErrorKind::IllegalFormat,
+                    format!("second position missing for {move_index} move for {active_color} after start position was {first_pos}"),
+                )));
+            }
+            Some(base64_char) => { decode_base64(base64_char)? }
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(half_move_index, path = "two-char", from = %first_pos, to = %to_pos, "decoded from- and to-position");
+        FromTo::new(first_pos, to_pos)
+    } else {
+        let positions_with_figures_that_can_reach_target: Vec<Position> = get_positions_to_reach_target_from(first_pos, game_state)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            half_move_index,
+            path = "one-char",
+            to = %first_pos,
+            origins = ?positions_with_figures_that_can_reach_target,
+            is_ambiguous = positions_with_figures_that_can_reach_target.len() > 1,
+            "decoded to-position, looked up the origin",
+        );
+        let from_to: FromTo = match positions_with_figures_that_can_reach_target.len() {
+            0 => {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalFormat,
+                    format!("no position found that could reach {first_pos} in move {move_index} for {active_color}"),
+                ));
+            }
+            1 => { FromTo::new(positions_with_figures_that_can_reach_target[0], first_pos) }
+            _ => {
+                // only the (hand-edited) one-char form is ambiguous, the two-char form names the origin
+                let suggested_encodings: Vec<String> = positions_with_figures_that_can_reach_target.iter()
+                    .map(|origin| format!("{}{}", encode_base64(*origin), encode_base64(first_pos)))
+                    .collect();
+                let msg = format!("many positions found that could reach {first_pos} in move {move_index} for {active_color}: {positions_with_figures_that_can_reach_target:?}, use one of {suggested_encodings:?} instead");
+                return Err(ChessError::new(
+                    ErrorKind::AmbiguousOrigin {
+                        target: first_pos,
+                        origins: positions_with_figures_that_can_reach_target,
+                        suggested_encodings,
+                    },
+                    msg,
+                ));
+            }
+        };
+        from_to
+    };
+
+    let next_move = if game_state.looks_like_pawn_promotion_move(from_to) {
+        let promotion_type: PromotionType = match encoded_chars.next() {
+            None => {
+                return Ok(DecodedMove::Incomplete(ChessError::new(
+                    ErrorKind::IllegalFormat,
+                    format!("missing pawn promotion type at last decoded move {from_to}, one of 'Q', 'R', 'N' or 'B' was expected next depending on what figure the pawn should promoted to"),
+                )));
+            }
+            Some(promotion_type_char) => {
+                match promotion_type_char.to_string().parse::<PromotionType>()  {
+                    Ok(promotion_type) => {promotion_type}
+                    Err(_) => {
+                        return Err(ChessError::new(
+                            ErrorKind::IllegalFormat,
+                            format!("missing pawn promotion at decoded move {move_index}. {from_to}, one of 'Q', 'R', 'N' or 'B' was expected next depending on what figure the pawn should promoted to"),
+                        ));
+                    }
+                }
+            }
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(half_move_index, %from_to, promotion_type = %promotion_type, "decoded pawn promotion");
+        Move::new_with_promotion(from_to, promotion_type)
+    } else {
+        Move::new(from_to)
+    };
+
+    // the two-char form can name any origin, so moves the figure can't make (e.g. h2g4) are rejected like by the encoder,
+    // they would lead to positions that can't occur. Pins are ignored, just like by the one-char form.
+    if names_origin {
+        match game_state.is_legal(next_move) {
+            Ok(()) | Err(IllegalMoveReason::LeavesKingInCheck) => {}
+            Err(reason) => return Err(illegal_move_error(next_move, game_state, half_move_index, reason)),
+        }
+    }
+    Ok(DecodedMove::Complete(next_move))
+}
+
+fn to_position_data(game_states: &[GameState], options: &DecompressOptions) -> Vec<PositionData> {
+    let to_position_data = |game_state: &GameState| PositionData {
+        fen: game_state.get_fen(),
+        zobrist_key: options.zobrist_keys.then(|| game_state.position_hash()),
+        san: None,
+        last_move: None,
+        annotations: Vec::new(),
+        comments: Vec::new(),
+        nags: Vec::new(),
+    };
+    #[cfg(feature = "rayon")]
+    if options.parallel_fen {
+        use rayon::prelude::*;
+        return game_states.par_iter().map(to_position_data).collect();
+    }
+    game_states.iter().map(to_position_data).collect()
+}
+
+pub struct PositionData {
+    pub fen: String,
+    /// the Zobrist key of the position as defined by the Polyglot book format
+    /// (only present if [DecompressOptions::zobrist_keys] is set)
+    pub zobrist_key: Option<u64>,
+    /// the move that led to this position in standard algebraic notation (e.g. "Nbd2", "exd6" or "O-O-O#"),
+    /// None for the start position (only present if [DecompressOptions::san] is set)
+    pub san: Option<String>,
+    /// the squares of the move that led to this position (e.g. to highlight them), None for the start position
+    pub last_move: Option<MoveSquares>,
+    /// the highlights and arrows of the position (see [Extensions])
+    pub annotations: Vec<Annotation>,
+    /// the comments on the move that led to this position (see [Extensions])
+    pub comments: Vec<Comment>,
+    /// the annotation glyphs of the move that led to this position (see [Extensions])
+    pub nags: Vec<Nag>,
+}
+
+impl PositionData {
+    pub fn new(fen: String) -> PositionData {
+        PositionData {
+            fen,
+            zobrist_key: None,
+            san: None,
+            last_move: None,
+            annotations: Vec::new(),
+            comments: Vec::new(),
+            nags: Vec::new(),
+        }
+    }
+}
+
+// Tests are in compression/mod.rs
@@ -0,0 +1,94 @@
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::position::Position;
+use crate::compression::base64::{decode_base64, encode_base64};
+use crate::figure::figure::Figure;
+use crate::game::board::Board;
+
+/// the content char of a field that was cleared
+const EMPTY_FIELD: char = '-';
+
+/// encodes the fields that differ between two boards as a url-safe patch, e.g. for board editors that sync
+/// edits (which don't have to be legal moves).
+///
+/// Every changed field takes 2 chars: the field (as in the move encoding) followed by its new content,
+/// which is the FEN char of the figure or '-' if the field was cleared.
+pub fn encode_position_delta(from: &Board, to: &Board) -> String {
+    let mut delta = String::new();
+    for index in 0..64 {
+        let position = Position::from_index_unchecked(index);
+        let new_content = to.get_figure(position);
+        if from.get_figure(position) != new_content {
+            delta.push(encode_base64(position));
+            delta.push(new_content.map_or(EMPTY_FIELD, |figure| figure.get_fen_char()));
+        }
+    }
+    delta
+}
+
+/// applies a patch created by [encode_position_delta] to a board
+pub fn apply_position_delta(board: &Board, delta: &str) -> Result<Board, ChessError> {
+    let mut patched_board = board.clone();
+    let mut chars = delta.chars();
+    while let Some(position_char) = chars.next() {
+        let position = decode_base64(position_char)?;
+        match chars.next() {
+            Some(EMPTY_FIELD) => {
+                if !patched_board.is_empty(position) {
+                    patched_board.clear_field(position);
+                }
+            }
+            Some(content_char) => {
+                let Some(figure) = Figure::from_fen_char(content_char) else {
+                    return Err(ChessError::new(
+                        ErrorKind::IllegalFormat,
+                        format!("illegal content '{content_char}' of field {position} in position delta {delta}"),
+                    ));
+                };
+                patched_board.set_figure(position, figure);
+            }
+            None => {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalFormat,
+                    format!("the content of field {position} is missing at the end of position delta {delta}"),
+                ));
+            }
+        }
+    }
+    Ok(patched_board)
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+    use crate::game::game_state::GameState;
+
+    #[rstest(
+        from_fen, to_fen, expected_delta,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", ""),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", "M-cP"),
+        case("8/8/4k3/8/8/4K3/4P3/8 w - - 0 1", "8/8/4k3/8/8/4K3/8/4q3 w - - 0 1", "EqM-"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_position_delta(from_fen: &str, to_fen: &str, expected_delta: &str) {
+        let from = GameState::from_fen(from_fen).unwrap().board;
+        let to = GameState::from_fen(to_fen).unwrap().board;
+        let delta = encode_position_delta(&from, &to);
+        assert_eq!(delta, expected_delta);
+        assert_eq!(apply_position_delta(&from, &delta).unwrap().get_fen_part1(), to.get_fen_part1());
+    }
+
+    #[rstest(
+        delta,
+        case("M"),
+        case("Mx"),
+        case("%P"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_apply_position_delta_fails(delta: &str) {
+        let error = apply_position_delta(&Board::classic(), delta).unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::IllegalFormat));
+    }
+}
@@ -0,0 +1,294 @@
+use std::fmt;
+use std::str;
+use crate::base::a_move::{Move, MoveData};
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::compression::base64::encode_base64_index;
+use crate::compression::compress::{compress, compress_from_with_options, CompressOptions};
+use crate::compression::decompress::{decompress, decompress_with_options, DecompressOptions, DecompressedGame, PositionData};
+use crate::game::game_state::GameState;
+
+/// a game in its canonical compressed form.
+///
+/// The same game can be written down in more than one way (e.g. "Ka" and "a" both decode to c2c4,
+/// but only the latter is what [compress] produces), so parsing an EncodedGame re-compresses the
+/// decoded moves. Games that don't start from the classic position keep their start position in the format header.
+/// Two EncodedGames are equal if and only if they describe the same sequence of moves from the same start position.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct EncodedGame {
+    encoded: String,
+}
+
+impl EncodedGame {
+    pub fn compress(moves: Vec<Move>) -> Result<EncodedGame, ChessError> {
+        Ok(EncodedGame {
+            encoded: compress(moves)?,
+        })
+    }
+
+    /// like [EncodedGame::compress], but the game starts from the given position, which is written to the format header
+    pub fn compress_from(start: GameState, moves: Vec<Move>) -> Result<EncodedGame, ChessError> {
+        Ok(EncodedGame {
+            encoded: compress_from_with_options(start, moves, &CompressOptions::default())?.encoded,
+        })
+    }
+
+    pub fn decompress(&self) -> Result<(Vec<PositionData>, Vec<MoveData>), ChessError> {
+        decompress(&self.encoded)
+    }
+
+    /// parses a game that was taken from a url, either as a (possibly percent-encoded) url component
+    /// (e.g. "ckGV5qh" or "ckGV90Fa-tHG%5F-") or as the full url.
+    /// The game is looked for in the path segments (starting with the last one), the query values and the fragment.
+    pub fn from_url_component(component: &str) -> Result<EncodedGame, ChessError> {
+        let Some((_, after_scheme)) = component.split_once("://") else {
+            return percent_decode(component)?.parse();
+        };
+        let (before_fragment, fragment) = after_scheme.split_once('#').unwrap_or((after_scheme, ""));
+        let (path, query) = before_fragment.split_once('?').unwrap_or((before_fragment, ""));
+        // the first segment is the host
+        let path_segments: Vec<&str> = path.split('/').skip(1).collect();
+        let query_values = query.split('&').map(|param| param.split_once('=').map_or(param, |(_, value)| value));
+        path_segments.into_iter().rev().chain(query_values).chain([fragment])
+            .filter(|part| !part.is_empty())
+            .find_map(|part| percent_decode(part).ok()?.parse().ok())
+            .ok_or_else(|| ChessError::new(ErrorKind::IllegalFormat, format!("no encoded game found in url {component}")))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.encoded
+    }
+
+    /// returns a url-safe hash of the canonical encoding consisting of `len` characters.
+    ///
+    /// The result is deterministic (across runs, platforms and crate versions), so it can be used
+    /// to derive short redirect slugs from games. Each character carries 6 bits, so with `n` games
+    /// stored the chance of any two slugs colliding is about `n² / 2^(6*len+1)`
+    /// (e.g. ~0.4% for a million games with `len` 10).
+    /// Since a collision can never be ruled out, services should store the slug together with the
+    /// full encoding, compare the encodings on insertion and fall back to a longer slug
+    /// (e.g. `short_hash(len+2)`) for the newcomer if the slug is already taken by a different game.
+    pub fn short_hash(&self, len: usize) -> String {
+        let mut hash = String::with_capacity(len);
+        let mut block_index: u64 = 0;
+        while hash.len() < len {
+            // every 64bit block provides 10 characters (60bits), longer hashes chain further blocks
+            let mut block = fnv1a_64(block_index, self.encoded.as_bytes());
+            for _ in 0..10 {
+                if hash.len() == len {
+                    break;
+                }
+                hash.push(encode_base64_index((block & 63) as usize));
+                block >>= 6;
+            }
+            block_index += 1;
+        }
+        hash
+    }
+}
+
+impl str::FromStr for EncodedGame {
+    type Err = ChessError;
+
+    fn from_str(encoded: &str) -> Result<Self, Self::Err> {
+        let game = decompress_with_options(encoded, &DecompressOptions::default())?;
+        let moves: Vec<Move> = game.moves.iter().map(MoveData::given_move).collect();
+        EncodedGame::compress_from(GameState::from_fen(&game.positions[0].fen)?, moves)
+    }
+}
+
+impl fmt::Display for EncodedGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encoded)
+    }
+}
+
+fn percent_decode(component: &str) -> Result<String, ChessError> {
+    let illegal_format = || ChessError::new(ErrorKind::IllegalFormat, format!("{component} isn't a valid percent-encoded url component"));
+    let mut bytes: Vec<u8> = Vec::with_capacity(component.len());
+    let mut remaining = component.as_bytes();
+    while let Some((&byte, rest)) = remaining.split_first() {
+        if byte == b'%' {
+            let hex = rest.get(..2).and_then(|hex| str::from_utf8(hex).ok()).ok_or_else(illegal_format)?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|error| illegal_format().with_source(error))?);
+            remaining = &rest[2..];
+        } else {
+            bytes.push(byte);
+            remaining = rest;
+        }
+    }
+    String::from_utf8(bytes).map_err(|error| illegal_format().with_source(error))
+}
+
+impl DecompressedGame {
+    /// the game as it would be if `new_moves` had been played after the first `ply` half moves
+    /// (e.g. for trying a different move in a viewer). The first `ply` moves keep their encoding,
+    /// since the encoding of a move only depends on the moves before it.
+    pub fn branch_at(&self, ply: usize, new_moves: Vec<Move>) -> Result<EncodedGame, ChessError> {
+        if ply > self.moves.len() {
+            return Err(ChessError::new(
+                ErrorKind::IllegalConfig,
+                format!("can't branch at half move {ply}, the game only has {} half moves", self.moves.len()),
+            ));
+        }
+        let moves: Vec<Move> = self.moves[..ply].iter().map(MoveData::given_move).chain(new_moves).collect();
+        EncodedGame::compress_from(GameState::from_fen(&self.positions[0].fen)?, moves)
+    }
+}
+
+// FNV-1a is used (instead of std's DefaultHasher) because its output is guaranteed to stay the same
+pub(crate) fn fnv1a_64(seed: u64, bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in seed.to_le_bytes().iter().chain(bytes) {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::base64::assert_is_url_safe_base64;
+    use crate::compression::decompress::{decompress_with_options, DecompressOptions};
+    use super::*;
+
+    #[rstest(
+        encoded, len,
+        case("", 0),
+        case("", 6),
+        case("ckGV5qh", 1),
+        case("ckGV5qh", 8),
+        case("ckGV5qh", 10),
+        case("ckGV5qh", 11),
+        case("ckGV5qh", 32),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_short_hash_has_requested_length_and_is_url_safe(
+        encoded: EncodedGame,
+        len: usize,
+    ) {
+        let hash = encoded.short_hash(len);
+        assert_eq!(hash.len(), len);
+        assert_is_url_safe_base64(&hash).unwrap();
+    }
+
+    #[test]
+    fn test_short_hash_is_stable() {
+        let encoded = "ckGV5qh".parse::<EncodedGame>().unwrap();
+        // changing this value would invalidate every slug ever derived from a game
+        assert_eq!(encoded.short_hash(12), "AIczvB8O3EDl");
+        // longer hashes extend shorter ones, so slugs can be lengthened on collision
+        assert!(encoded.short_hash(24).starts_with(&encoded.short_hash(12)));
+    }
+
+    #[rstest(
+        non_canonical, canonical,
+        case("Ka", "a"),
+        case("Kazj", "aj"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parsing_normalizes_to_canonical_encoding(
+        non_canonical: EncodedGame,
+        canonical: EncodedGame,
+    ) {
+        assert_eq!(non_canonical.as_str(), canonical.as_str());
+        assert_eq!(non_canonical.short_hash(8), canonical.short_hash(8));
+    }
+
+    #[rstest(
+        encoded, expected_start_fen,
+        case(".P.N.c", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1"),
+        case(".F.4k3-8-8-8-8-8-4P3-4K3_w_-_-_0_1.c", "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parsing_keeps_the_start_position(
+        encoded: EncodedGame,
+        expected_start_fen: &str,
+    ) {
+        assert_ne!(encoded.as_str(), "c");
+        let (positions, moves) = encoded.decompress().unwrap();
+        assert_eq!(positions[0].fen, expected_start_fen);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(encoded.as_str().parse::<EncodedGame>().unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_different_games_have_different_short_hashes() {
+        let game1 = "a".parse::<EncodedGame>().unwrap();
+        let game2 = "c".parse::<EncodedGame>().unwrap();
+        assert_ne!(game1.short_hash(8), game2.short_hash(8));
+    }
+
+    #[rstest(
+        component, expected_game,
+        case("ckGV5qh", "ckGV5qh"),
+        case("ckGV90Fa-tHG%5F-", "ckGV90Fa-tHG_-"),
+        case("%2EC%2EckGVE4", ".C.ckGVE4"),
+        case("https://example.org/game/ckGV5qh", "ckGV5qh"),
+        case("https://example.org/game/ckGV90Fa-tHG%5f-/", "ckGV90Fa-tHG_-"),
+        case("https://example.org/view?game=ckGV5qh&ref=chat", "ckGV5qh"),
+        case("https://example.org/#ckGV5qh", "ckGV5qh"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_url_component(component: &str, expected_game: EncodedGame) {
+        assert_eq!(EncodedGame::from_url_component(component).unwrap(), expected_game);
+    }
+
+    #[rstest(
+        encoded, ply, new_moves, expected_branch,
+        case("ckGV5qh", 0, "d2d4", "b"),
+        case("ckGV5qh", 3, "g8f6", "ckGV-t"),
+        case("ckGV5qh", 5, "", "ckGV5qh"),
+        case("ckGV5qh", 5, "a7a6, b5a4", "ckGV5qhohY"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_branch_at(encoded: &str, ply: usize, new_moves: &str, expected_branch: &str) {
+        let game = decompress_with_options(encoded, &DecompressOptions::default()).unwrap();
+        let new_moves: Vec<Move> = parse_to_vec(new_moves, ",").unwrap();
+        let branch = game.branch_at(ply, new_moves).unwrap();
+        assert_eq!(branch.as_str(), expected_branch);
+        assert!(branch.as_str().starts_with(&compress(game.moves[..ply].iter().map(MoveData::given_move).collect()).unwrap()));
+    }
+
+    #[test]
+    fn test_branch_at_rejects_illegal_branches() {
+        let game = decompress_with_options("ckGV5qh", &DecompressOptions::default()).unwrap();
+        assert!(matches!(game.branch_at(6, vec![]).unwrap_err().kind, ErrorKind::IllegalConfig));
+        assert!(matches!(game.branch_at(3, parse_to_vec("b8b6", ",").unwrap()).unwrap_err().kind, ErrorKind::IllegalMoveInGame { .. }));
+    }
+
+    #[rstest(
+        encoded, ply, new_moves, expected_branch,
+        case(".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.T", 0, "e3f3", ".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.V"),
+        case(".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.T", 1, "", ".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.T"),
+        case(".P.N.c", 1, "d7d5", ".P.N.cj"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_branch_at_keeps_the_start_position(encoded: &str, ply: usize, new_moves: &str, expected_branch: &str) {
+        let game = decompress_with_options(encoded, &DecompressOptions::default()).unwrap();
+        let new_moves: Vec<Move> = parse_to_vec(new_moves, ",").unwrap();
+        let branch = game.branch_at(ply, new_moves).unwrap();
+        assert_eq!(branch.as_str(), expected_branch);
+        let (positions, _) = branch.decompress().unwrap();
+        assert_eq!(positions[0].fen, game.positions[0].fen);
+    }
+
+    #[rstest(
+        component,
+        case("ckGV%5"),
+        case("ckGV%zz"),
+        case("%FF"),
+        case("https://example.org/"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_url_component_fails(component: &str) {
+        let error = EncodedGame::from_url_component(component).unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::IllegalFormat));
+    }
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+use crate::base::a_move::{Move, MoveData};
+use crate::base::errors::ChessError;
+use crate::compression::compress::encode_next_move;
+use crate::game::game_state::GameState;
+
+/// compresses a game move by move (e.g. while it's played), the counterpart of [Decoder](crate::decoder::Decoder).
+///
+/// The encoding of a move only depends on the position it's played in, so moves can be added
+/// and taken back without re-encoding the rest of the game.
+#[derive(Debug, Clone)]
+pub struct Encoder {
+    encoded: String,
+    /// the moves played so far are shared with the encoders forked from this one
+    last_move: Option<Arc<EncodedMove>>,
+    game_state: GameState,
+}
+
+/// the name under which live game servers know the [Encoder]: moves are appended with
+/// [push_move](Encoder::push_move) as they arrive and the game is closed with [finish](Encoder::finish).
+pub type Compressor = Encoder;
+
+#[derive(Debug)]
+struct EncodedMove {
+    previous_move: Option<Arc<EncodedMove>>,
+    a_move: Move,
+    char_count: usize,
+    /// the state the move was played in
+    game_state_before: GameState,
+    half_move_index: usize,
+}
+
+impl Encoder {
+    pub fn new() -> Encoder {
+        Encoder {
+            encoded: String::new(),
+            last_move: None,
+            game_state: GameState::classic(),
+        }
+    }
+
+    /// encodes the next move and returns the chars appended to the encoding
+    pub fn push_move(&mut self, next_move: Move) -> Result<&str, ChessError> {
+        let half_move_index = self.get_half_moves();
+        let encoded_move = encode_next_move(next_move, &self.game_state, half_move_index)?;
+        let (new_game_state, _) = self.game_state.do_move(next_move)?;
+        let game_state_before = std::mem::replace(&mut self.game_state, new_game_state);
+        self.last_move = Some(Arc::new(EncodedMove {
+            previous_move: self.last_move.take(),
+            a_move: next_move,
+            char_count: encoded_move.len(),
+            game_state_before,
+            half_move_index,
+        }));
+        let start_of_move = self.encoded.len();
+        self.encoded.push_str(&encoded_move);
+        Ok(&self.encoded[start_of_move..])
+    }
+
+    /// takes back the last move (including all of its chars), returns None if no moves were played
+    pub fn pop_move(&mut self) -> Option<Move> {
+        let last_move = self.last_move.take()?;
+        self.encoded.truncate(self.encoded.len() - last_move.char_count);
+        self.game_state = last_move.game_state_before.clone();
+        self.last_move = last_move.previous_move.clone();
+        Some(last_move.a_move)
+    }
+
+    /// returns an independent encoder that continues from the current position (e.g. to explore a variation).
+    /// The moves played so far are shared and not copied, so forking is cheap even for long games.
+    pub fn fork(&self) -> Encoder {
+        self.clone()
+    }
+
+    /// ends the game and returns its encoding, the same as [compress](crate::compress::compress) returns for the moves pushed
+    pub fn finish(self) -> String {
+        self.encoded
+    }
+
+    /// the canonical encoding of the moves pushed so far
+    pub fn as_str(&self) -> &str {
+        &self.encoded
+    }
+
+    /// the state after the last move
+    pub fn get_game_state(&self) -> &GameState {
+        &self.game_state
+    }
+
+    pub fn get_half_moves(&self) -> usize {
+        self.last_move.as_ref().map(|last_move| last_move.half_move_index + 1).unwrap_or(0)
+    }
+
+    pub fn get_moves(&self) -> Vec<Move> {
+        let mut moves: Vec<Move> = Vec::with_capacity(self.get_half_moves());
+        let mut encoded_move = self.last_move.as_deref();
+        while let Some(EncodedMove { previous_move, a_move, .. }) = encoded_move {
+            moves.push(*a_move);
+            encoded_move = previous_move.as_deref();
+        }
+        moves.reverse();
+        moves
+    }
+
+    /// the data of the last move (e.g. which figure got captured) or None if no moves were played
+    pub fn get_last_move_data(&self) -> Option<MoveData> {
+        let last_move = self.last_move.as_ref()?;
+        last_move.game_state_before.do_move(last_move.a_move).ok().map(|(_, move_data)| move_data)
+    }
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Encoder::new()
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::compress;
+    use super::*;
+
+    #[rstest(
+        decoded_moves,
+        case("e2e4, e7e5, g1f3"),
+        case("c2c3, d7d5, d1b3, c8h3"),
+        case("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4, g2g3, h4g3, c7d8Q"),
+        case("d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_encoder_matches_compress_while_pushing_and_popping(decoded_moves: &str) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let mut encoder = Encoder::new();
+        for (i, given_move) in given_moves.iter().enumerate() {
+            encoder.push_move(*given_move).unwrap();
+            assert_eq!(encoder.as_str(), compress(given_moves[..=i].to_vec()).unwrap());
+        }
+        for i in (0..given_moves.len()).rev() {
+            assert_eq!(encoder.pop_move(), Some(given_moves[i]));
+            assert_eq!(encoder.as_str(), compress(given_moves[..i].to_vec()).unwrap());
+            assert_eq!(encoder.get_moves(), given_moves[..i].to_vec());
+        }
+        assert_eq!(encoder.pop_move(), None);
+        assert_eq!(encoder.get_game_state().get_fen(), GameState::classic().get_fen());
+    }
+
+    #[test]
+    fn test_compressor_appends_the_chars_of_each_move() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3, b8c6", ",").unwrap();
+        let mut compressor = Compressor::new();
+        let appended_chars: Vec<String> = given_moves.iter()
+            .map(|given_move| compressor.push_move(*given_move).unwrap().to_string())
+            .collect();
+        assert_eq!(appended_chars, vec!["c", "k", "GV", "5q"]);
+        assert_eq!(compressor.finish(), compress(given_moves).unwrap());
+    }
+
+    #[test]
+    fn test_takeback_of_promotion() {
+        let mut encoder = Encoder::new();
+        for a_move in parse_to_vec::<Move>("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4", ",").unwrap() {
+            encoder.push_move(a_move).unwrap();
+        }
+        let encoded_before_promotion = encoder.as_str().to_string();
+        assert_eq!(encoder.push_move("c7d8Q".parse().unwrap()).unwrap(), "7Q");
+        assert_eq!(encoder.pop_move(), Some("c7d8Q".parse().unwrap()));
+        assert_eq!(encoder.as_str(), encoded_before_promotion);
+        // after the takeback a different promotion is possible
+        assert_eq!(encoder.push_move("c7b8N".parse().unwrap()).unwrap(), "5N");
+    }
+
+    #[test]
+    fn test_illegal_move_leaves_encoder_unchanged() {
+        let mut encoder = Encoder::new();
+        encoder.push_move("e2e4".parse().unwrap()).unwrap();
+        assert!(encoder.push_move("e7e4".parse().unwrap()).is_err());
+        assert_eq!(encoder.as_str(), "c");
+        assert_eq!(encoder.get_moves().len(), 1);
+    }
+
+    #[test]
+    fn test_forked_encoders_are_independent() {
+        let mut encoder = Encoder::new();
+        for a_move in parse_to_vec::<Move>("e2e4, e7e5, g1f3", ",").unwrap() {
+            encoder.push_move(a_move).unwrap();
+        }
+        let mut petrov = encoder.fork();
+        petrov.push_move("g8f6".parse().unwrap()).unwrap();
+        encoder.push_move("b8c6".parse().unwrap()).unwrap();
+        let mut italian = encoder.fork();
+        italian.push_move("f1c4".parse().unwrap()).unwrap();
+        encoder.pop_move();
+        encoder.pop_move();
+
+        assert_eq!(encoder.as_str(), compress(parse_to_vec("e2e4, e7e5", ",").unwrap()).unwrap());
+        assert_eq!(petrov.as_str(), compress(parse_to_vec("e2e4, e7e5, g1f3, g8f6", ",").unwrap()).unwrap());
+        assert_eq!(italian.as_str(), compress(parse_to_vec("e2e4, e7e5, g1f3, b8c6, f1c4", ",").unwrap()).unwrap());
+        assert_eq!(italian.get_half_moves(), 5);
+        assert_eq!(italian.pop_move(), Some("f1c4".parse().unwrap()));
+        assert_eq!(italian.pop_move(), Some("b8c6".parse().unwrap()));
+        assert_eq!(italian.as_str(), &petrov.as_str()[..italian.as_str().len()]);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::compression::base64::{decode_base64_index, encode_base64_index};
+
+// Reed-Solomon code over GF(64), so that every symbol is exactly one url-safe base64 char.
+// Two parity symbols per block allow to correct one wrong char per block.
+// A block can't be longer than the number of non-zero field elements (63), so longer values
+// are split into blocks of up to 61 data chars. The data chars are kept in front (so they look
+// like a normal encoded game) and the parity chars of all blocks are appended in block order.
+
+pub const PARITY_CHARS_PER_BLOCK: usize = 2;
+const MAX_BLOCK_LENGTH: usize = 63;
+pub const MAX_DATA_CHARS_PER_BLOCK: usize = MAX_BLOCK_LENGTH - PARITY_CHARS_PER_BLOCK;
+
+// x^6 + x + 1
+const PRIMITIVE_POLYNOMIAL: u8 = 0b100_0011;
+
+struct GaloisField {
+    exp: [u8; 2 * MAX_BLOCK_LENGTH],
+    log: [u8; 64],
+}
+
+static GF64: GaloisField = {
+    let mut exp = [0_u8; 2 * MAX_BLOCK_LENGTH];
+    let mut log = [0_u8; 64];
+    let mut value: u8 = 1;
+    let mut power = 0;
+    while power < MAX_BLOCK_LENGTH {
+        exp[power] = value;
+        exp[power + MAX_BLOCK_LENGTH] = value;
+        log[value as usize] = power as u8;
+        value <<= 1;
+        if value & 0b100_0000 != 0 {
+            value ^= PRIMITIVE_POLYNOMIAL;
+        }
+        power += 1;
+    }
+    GaloisField { exp, log }
+};
+
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    GF64.exp[GF64.log[a as usize] as usize + GF64.log[b as usize] as usize]
+}
+
+// generator polynomial g(x) = (x - α^0)(x - α^1) = x^2 + G1*x + G0
+const G1: u8 = 0b11; // α^0 + α^1
+const G0: u8 = 0b10; // α^0 * α^1
+
+/// appends the parity chars to a url-safe base64 value
+pub fn append_parity(base64_value: &str) -> Result<String, ChessError> {
+    let values = to_values(base64_value)?;
+    let mut with_parity = base64_value.to_string();
+    for block in values.chunks(MAX_DATA_CHARS_PER_BLOCK) {
+        // remainder of block(x)*x^2 divided by g(x)
+        let (mut r1, mut r0) = (0_u8, 0_u8);
+        for value in block {
+            let feedback = value ^ r1;
+            r1 = r0 ^ gf_mul(feedback, G1);
+            r0 = gf_mul(feedback, G0);
+        }
+        with_parity.push(encode_base64_index(r1 as usize));
+        with_parity.push(encode_base64_index(r0 as usize));
+    }
+    Ok(with_parity)
+}
+
+/// removes the parity chars from a value created by [append_parity] after fixing
+/// up to one wrong char per block. Returns the corrected value and the indices of the chars
+/// that were corrected (parity chars aren't part of the returned value but their indices are reported).
+pub fn correct_and_strip_parity(with_parity: &str) -> Result<(String, Vec<usize>), ChessError> {
+    let mut values = to_values(with_parity)?;
+    // the last block has to contain at least one data char
+    if matches!(values.len() % MAX_BLOCK_LENGTH, 1..=PARITY_CHARS_PER_BLOCK) {
+        return Err(ChessError::new(ErrorKind::IllegalFormat, format!("value {with_parity} is too short to contain the error correction chars")));
+    }
+    let number_of_blocks = values.len().div_ceil(MAX_BLOCK_LENGTH);
+    let data_length = values.len() - number_of_blocks * PARITY_CHARS_PER_BLOCK;
+
+    let mut corrected_indices: Vec<usize> = Vec::new();
+    for block_index in 0..number_of_blocks {
+        // indices into values, data followed by the 2 parity chars
+        let block_indices: Vec<usize> = {
+            let data_start = block_index * MAX_DATA_CHARS_PER_BLOCK;
+            let data_end = (data_start + MAX_DATA_CHARS_PER_BLOCK).min(data_length);
+            let parity_start = data_length + block_index * PARITY_CHARS_PER_BLOCK;
+            (data_start..data_end).chain(parity_start..parity_start + PARITY_CHARS_PER_BLOCK).collect()
+        };
+
+        // syndromes: the codeword evaluated at α^0 and α^1
+        let (mut s0, mut s1) = (0_u8, 0_u8);
+        for index in block_indices.iter() {
+            s0 ^= values[*index];
+            s1 = gf_mul(s1, GF64.exp[1]) ^ values[*index];
+        }
+        if s0 == 0 && s1 == 0 {
+            continue;
+        }
+
+        let uncorrectable_error = ChessError::new(
+            ErrorKind::IllegalFormat,
+            format!("value {with_parity} contains more than one wrong char in block {block_index}, so it can't be corrected"),
+        );
+        if s0 == 0 || s1 == 0 {
+            return Err(uncorrectable_error);
+        }
+        // a single error with magnitude s0 at degree d results in s1 = s0*α^d
+        let degree = (GF64.log[s1 as usize] as usize + MAX_BLOCK_LENGTH - GF64.log[s0 as usize] as usize) % MAX_BLOCK_LENGTH;
+        if degree >= block_indices.len() {
+            return Err(uncorrectable_error);
+        }
+        let wrong_index = block_indices[block_indices.len() - 1 - degree];
+        values[wrong_index] ^= s0;
+        corrected_indices.push(wrong_index);
+    }
+
+    let corrected: String = values[..data_length].iter().map(|value| encode_base64_index(*value as usize)).collect();
+    Ok((corrected, corrected_indices))
+}
+
+fn to_values(base64_value: &str) -> Result<Vec<u8>, ChessError> {
+    base64_value.chars().map(|c| decode_base64_index(c).map(|index| index as u8)).collect()
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::compression::base64::encode_base64_index;
+    use super::*;
+
+    fn long_value(length: usize) -> String {
+        (0..length).map(|i| encode_base64_index((i * 7 + 3) % 64)).collect()
+    }
+
+    fn replace_char(value: &str, index: usize) -> String {
+        value.chars().enumerate().map(|(i, c)| {
+            if i == index {
+                if c == 'A' { 'B' } else { 'A' }
+            } else {
+                c
+            }
+        }).collect()
+    }
+
+    #[rstest(
+        value_length, expected_parity_chars,
+        case(0, 0),
+        case(1, 2),
+        case(61, 2),
+        case(62, 4),
+        case(122, 4),
+        case(123, 6),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_round_trip_without_errors(value_length: usize, expected_parity_chars: usize) {
+        let value = long_value(value_length);
+        let with_parity = append_parity(&value).unwrap();
+        assert_eq!(with_parity.len(), value_length + expected_parity_chars);
+        assert!(with_parity.starts_with(&value));
+        let (corrected, corrected_indices) = correct_and_strip_parity(&with_parity).unwrap();
+        assert_eq!(corrected, value);
+        assert!(corrected_indices.is_empty());
+    }
+
+    #[rstest(
+        value_length,
+        case(1),
+        case(7),
+        case(61),
+        case(100),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_every_single_wrong_char_is_corrected(value_length: usize) {
+        let value = long_value(value_length);
+        let with_parity = append_parity(&value).unwrap();
+        for wrong_index in 0..with_parity.len() {
+            let damaged = replace_char(&with_parity, wrong_index);
+            let (corrected, corrected_indices) = correct_and_strip_parity(&damaged).unwrap();
+            assert_eq!(corrected, value, "wrong char at {wrong_index}");
+            assert_eq!(corrected_indices, vec![wrong_index]);
+        }
+    }
+
+    #[test]
+    fn test_one_wrong_char_per_block_is_corrected() {
+        let value = long_value(100);
+        let damaged = replace_char(&replace_char(&append_parity(&value).unwrap(), 3), 70);
+        let (corrected, corrected_indices) = correct_and_strip_parity(&damaged).unwrap();
+        assert_eq!(corrected, value);
+        assert_eq!(corrected_indices, vec![3, 70]);
+    }
+
+    #[test]
+    fn test_two_wrong_chars_in_one_block_are_detected() {
+        let value = long_value(20);
+        let damaged = replace_char(&replace_char(&append_parity(&value).unwrap(), 3), 4);
+        // two errors can look like a different single error, but never like no error
+        if let Ok((corrected, _)) = correct_and_strip_parity(&damaged) {
+            assert_ne!(corrected, value);
+        }
+    }
+
+    #[rstest(
+        with_parity,
+        case("A"),
+        case("AB"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_too_short_values_are_rejected(with_parity: &str) {
+        assert!(correct_and_strip_parity(with_parity).is_err());
+    }
+}
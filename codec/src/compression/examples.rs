@@ -0,0 +1,96 @@
+use std::fmt;
+use crate::base::a_move::Move;
+use crate::base::errors::ChessError;
+use crate::compression::compress::{encode_next_move_with_reason, EncodingReason};
+use crate::game::game_state::GameState;
+use crate::notation::san::to_san;
+
+/// one move of an [annotated_walkthrough]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WalkthroughRow {
+    pub half_move_index: usize,
+    /// the move as it was given (e.g. "g1f3")
+    pub a_move: String,
+    pub san: String,
+    /// the chars the move is encoded with (url-safe base64)
+    pub chars: String,
+    pub reason: EncodingReason,
+}
+
+impl fmt::Display for WalkthroughRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}. {} ({}) -> \"{}\": {}", self.half_move_index + 1, self.san, self.a_move, self.chars, self.reason)
+    }
+}
+
+/// compresses a (short) game move by move and records why each move got the chars it got,
+/// e.g. for doc sites and playgrounds that explain the format.
+/// Concatenating the chars of all rows yields the result of [compress](crate::compress::compress).
+pub fn annotated_walkthrough(moves: Vec<Move>) -> Result<Vec<WalkthroughRow>, ChessError> {
+    let mut game_state = GameState::classic();
+    let mut rows: Vec<WalkthroughRow> = Vec::with_capacity(moves.len());
+    for (half_move_index, a_move) in moves.into_iter().enumerate() {
+        let (chars, reason) = encode_next_move_with_reason(a_move, &game_state, half_move_index)?;
+        rows.push(WalkthroughRow {
+            half_move_index,
+            a_move: a_move.to_string(),
+            san: to_san(a_move, &game_state),
+            chars,
+            reason,
+        });
+        game_state = game_state.do_move(a_move)?.0;
+    }
+    Ok(rows)
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::compress;
+    use super::*;
+
+    #[rstest(
+        decoded_moves, expected_rows,
+        case("", vec![]),
+        case("e2e4, e7e5, g1f3", vec![
+            "1. e4 (e2e4) -> \"c\": 1 char, only one figure can reach e4",
+            "2. e5 (e7e5) -> \"k\": 1 char, only one figure can reach e5",
+            "3. Nf3 (g1f3) -> \"GV\": 2 chars, f3 can also be reached from [d1, f2]",
+        ]),
+        case("e2e4, e7e5, g1f3, b8c6, f1c4, g8f6, e1h1", vec![
+            "1. e4 (e2e4) -> \"c\": 1 char, only one figure can reach e4",
+            "2. e5 (e7e5) -> \"k\": 1 char, only one figure can reach e5",
+            "3. Nf3 (g1f3) -> \"GV\": 2 chars, f3 can also be reached from [d1, f2]",
+            "4. Nc6 (b8c6) -> \"5q\": 2 chars, c6 can also be reached from [c7]",
+            "5. Bc4 (f1c4) -> \"Fa\": 2 chars, c4 can also be reached from [c2]",
+            "6. Nf6 (g8f6) -> \"-t\": 2 chars, f6 can also be reached from [f7, d8]",
+            "7. O-O (e1h1) -> \"EH\": 2 chars, castling is written as the king moving to the rook on h1",
+        ]),
+        case("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4, g2g3, h4g3, c7d8Q", vec![
+            "1. a4 (a2a4) -> \"Y\": 1 char, only one figure can reach a4",
+            "2. h6 (h7h6) -> \"3v\": 2 chars, h6 can also be reached from [g8]",
+            "3. a5 (a4a5) -> \"g\": 1 char, only one figure can reach a5",
+            "4. b5 (b7b5) -> \"h\": 1 char, only one figure can reach b5",
+            "5. axb6 (a5b6) -> \"p\": 1 char, only one figure can reach b6",
+            "6. h5 (h6h5) -> \"n\": 1 char, only one figure can reach h5",
+            "7. bxc7 (b6c7) -> \"y\": 1 char, only one figure can reach c7",
+            "8. h4 (h5h4) -> \"f\": 1 char, only one figure can reach h4",
+            "9. g3 (g2g3) -> \"W\": 1 char, only one figure can reach g3",
+            "10. hxg3 (h4g3) -> \"W\": 1 char, only one figure can reach g3",
+            "11. cxd8=Q+ (c7d8Q) -> \"7Q\": 1 char, only one figure can reach d8",
+        ]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_annotated_walkthrough(decoded_moves: &str, expected_rows: Vec<&str>) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let rows = annotated_walkthrough(given_moves.clone()).unwrap();
+        let actual_rows: Vec<String> = rows.iter().map(|row| row.to_string()).collect();
+        assert_eq!(actual_rows, expected_rows);
+        let chars: String = rows.into_iter().map(|row| row.chars).collect();
+        assert_eq!(chars, compress(given_moves).unwrap());
+    }
+}
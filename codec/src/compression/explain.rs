@@ -0,0 +1,172 @@
+use std::fmt;
+use std::str::Chars;
+use crate::base::a_move::Move;
+use crate::base::errors::ChessError;
+use crate::compression::base64::decode_base64_index;
+use crate::compression::decompress::{decode_next_move, to_canonical_form, CanonicalForm, DecodedMove, DecompressOptions};
+use crate::compression::version::{decode_next_move_v2, FormatVersion};
+use crate::notation::san::to_san;
+
+/// how a part of an encoded game was read
+#[derive(Debug)]
+pub struct DecodeStep {
+    pub half_move_index: usize,
+    /// the chars (of the url-safe base64 form) read in this step
+    pub chars: String,
+    pub interpretation: Interpretation,
+}
+
+#[derive(Debug)]
+pub enum Interpretation {
+    /// a single char encodes the target, since only one figure can reach it
+    UniqueTarget { a_move: Move, san: String },
+    /// two chars encode origin and target (also used for castling, where the king targets its rook)
+    FromTo { a_move: Move, san: String },
+    /// the chars encode the index of the move in [chess_compress_urlsafe_core::GameState::ordered_legal_moves] (v2 format)
+    LegalMoveIndex { a_move: Move, san: String, index: usize, legal_move_count: usize },
+    /// the chars can't be decoded, all further chars are part of this step
+    Invalid { error: ChessError },
+}
+
+impl fmt::Display for DecodeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}. \"{}\": ", self.half_move_index + 1, self.chars)?;
+        let a_move = match &self.interpretation {
+            Interpretation::UniqueTarget { a_move, san } => {
+                write!(f, "{san}, only one figure can reach {}", a_move.from_to.to)?;
+                a_move
+            }
+            Interpretation::FromTo { a_move, san } => {
+                write!(f, "{san}, from {} to {}", a_move.from_to.from, a_move.from_to.to)?;
+                a_move
+            }
+            Interpretation::LegalMoveIndex { a_move, san, index, legal_move_count } => {
+                write!(f, "{san}, legal move {index} of {legal_move_count}")?;
+                a_move
+            }
+            Interpretation::Invalid { error } => return write!(f, "invalid, {:?}: {}", error.kind, error.msg),
+        };
+        if let Some(promotion_type) = a_move.promotion_type {
+            write!(f, ", promotes to {promotion_type}")?;
+        }
+        Ok(())
+    }
+}
+
+/// decodes a game step by step, to show how each char was interpreted (e.g. for documentation or bug reports).
+/// Decoding stops at the first invalid step.
+pub fn explain(encoded_match: &str) -> Vec<DecodeStep> {
+    let (header, base64_encoded_match) = match to_canonical_form(encoded_match, &DecompressOptions::default()) {
+        Ok(CanonicalForm { header, moves, .. }) => (header, moves),
+        Err(error) => return vec![DecodeStep {
+            half_move_index: 0,
+            chars: encoded_match.to_string(),
+            interpretation: Interpretation::Invalid { error },
+        }],
+    };
+
+    let mut steps: Vec<DecodeStep> = Vec::new();
+    let mut encoded_chars: Chars = base64_encoded_match.chars();
+    let mut game_state = match header.start_game_state() {
+        Ok(game_state) => game_state,
+        Err(error) => return vec![DecodeStep {
+            half_move_index: 0,
+            chars: encoded_match.to_string(),
+            interpretation: Interpretation::Invalid { error },
+        }],
+    };
+    while !encoded_chars.as_str().is_empty() {
+        let half_move_index = steps.len();
+        let chars_before = encoded_chars.as_str();
+        let decoded_move = match header.version {
+            FormatVersion::V1 => decode_next_move(&mut encoded_chars, &game_state, half_move_index),
+            FormatVersion::V2 => decode_next_move_v2(&mut encoded_chars, &game_state, half_move_index),
+        };
+        let chars = chars_before[..chars_before.len() - encoded_chars.as_str().len()].to_string();
+        let a_move = match decoded_move {
+            Ok(DecodedMove::Complete(a_move)) => a_move,
+            Ok(DecodedMove::Incomplete(error)) | Err(error) => {
+                steps.push(DecodeStep {
+                    half_move_index,
+                    chars: chars_before.to_string(),
+                    interpretation: Interpretation::Invalid { error },
+                });
+                break;
+            }
+        };
+        let san = to_san(a_move, &game_state);
+        let position_chars = chars.chars().count() - usize::from(a_move.promotion_type.is_some());
+        let interpretation = if header.version == FormatVersion::V2 {
+            let index = chars.chars().try_fold(0, |index, c| decode_base64_index(c).map(|value| index * 64 + value))
+                .expect("the chars were just decoded successfully");
+            Interpretation::LegalMoveIndex { a_move, san, index, legal_move_count: game_state.legal_move_count() }
+        } else if position_chars == 1 {
+            Interpretation::UniqueTarget { a_move, san }
+        } else {
+            Interpretation::FromTo { a_move, san }
+        };
+        match game_state.do_move(a_move) {
+            Ok((new_game_state, _)) => {
+                steps.push(DecodeStep { half_move_index, chars, interpretation });
+                game_state = new_game_state;
+            }
+            Err(error) => {
+                steps.push(DecodeStep { half_move_index, chars, interpretation: Interpretation::Invalid { error } });
+                break;
+            }
+        }
+    }
+    steps
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        encoded_match, expected_steps,
+        case("", vec![]),
+        case("ckGV", vec![
+            "1. \"c\": e4, only one figure can reach e4",
+            "2. \"k\": e5, only one figure can reach e5",
+            "3. \"GV\": Nf3, from g1 to f3",
+        ]),
+        case("ak0", vec![
+            "1. \"a\": c4, only one figure can reach c4",
+            "2. \"k\": e5, only one figure can reach e5",
+            "3. \"0\": invalid, IllegalFormat: no position found that could reach e7 in move 1 for white",
+        ]),
+        case("Y3vghpnyfWW7Q", vec![
+            "1. \"Y\": a4, only one figure can reach a4",
+            "2. \"3v\": h6, from h7 to h6",
+            "3. \"g\": a5, only one figure can reach a5",
+            "4. \"h\": b5, only one figure can reach b5",
+            "5. \"p\": axb6, only one figure can reach b6",
+            "6. \"n\": h5, only one figure can reach h5",
+            "7. \"y\": bxc7, only one figure can reach c7",
+            "8. \"f\": h4, only one figure can reach h4",
+            "9. \"W\": g3, only one figure can reach g3",
+            "10. \"W\": hxg3, only one figure can reach g3",
+            "11. \"7Q\": cxd8=Q+, only one figure can reach d8, promotes to Q",
+        ]),
+        case(".2.NIN", vec![
+            "1. \"N\": e4, legal move 13 of 20",
+            "2. \"I\": e5, legal move 8 of 20",
+            "3. \"N\": Nf3, legal move 13 of 29",
+        ]),
+        case(".C.ckGV", vec![
+            "1. \".C.ckGV\": invalid, ChecksumMismatch: checksum of ckGV should be Ih but is GV",
+        ]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_explain(
+        encoded_match: &str,
+        expected_steps: Vec<&str>,
+    ) {
+        let actual_steps: Vec<String> = explain(encoded_match).iter().map(|step| step.to_string()).collect();
+        assert_eq!(actual_steps, expected_steps);
+    }
+}
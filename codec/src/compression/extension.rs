@@ -0,0 +1,509 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::Chars;
+use std::time::Duration;
+use crate::base::a_move::FromTo;
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::position::Position;
+use crate::compression::alphabet::regroup_bits;
+use crate::compression::base64::{decode_base64, decode_base64_index, encode_base64, encode_base64_index};
+use crate::compression::summary::GameResult;
+
+/// the rich extension records of a game, which are written (in the alphabet of the game) after its moves.
+///
+/// Every record belongs to a position, which is identified by its index
+/// (0 is the start position, 1 the position after the first half move, ..).
+/// The records aren't covered by checksum, error correction or scrambling.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Extensions {
+    /// visual markup (e.g. of study tools) by index of the position it's shown on
+    pub annotations: BTreeMap<usize, Vec<Annotation>>,
+    /// comments by index of the position reached by the commented move
+    pub comments: BTreeMap<usize, Vec<Comment>>,
+    /// annotation glyphs (e.g. "!?") by index of the position reached by the annotated move
+    pub nags: BTreeMap<usize, Vec<Nag>>,
+    /// the time left on the clock of the player who made the move leading to the position (by index of that position).
+    /// It's stored in tenths of a second, finer durations are rounded down.
+    pub clocks: BTreeMap<usize, Duration>,
+    /// the number of half moves decompress reveals, the rest of the game is only returned
+    /// if [DecompressOptions::allow_spoilers](crate::decompress::DecompressOptions::allow_spoilers) is set
+    /// (e.g. for guess-the-move trainers)
+    pub reveal_boundary: Option<usize>,
+    pub metadata: Metadata,
+}
+
+/// facts about the game that aren't part of its moves (they're written as records of the start position)
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Metadata {
+    pub white_rating: Option<u16>,
+    pub black_rating: Option<u16>,
+    /// the result of the game, which (unlike a checkmate) can't always be told from the moves alone
+    pub result: Option<GameResult>,
+    pub termination: Option<Termination>,
+    /// the white player berserked (gave up half of their time for an extra point in an arena tournament)
+    pub white_berserk: bool,
+    /// the black player berserked (gave up half of their time for an extra point in an arena tournament)
+    pub black_berserk: bool,
+}
+
+/// how the game ended.
+///
+/// Terminations unknown to this version of the crate are kept as their code,
+/// so they survive decompressing and compressing the game again.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Termination {
+    /// checkmate, stalemate, draw by rule or agreement
+    Normal,
+    Resignation,
+    TimeForfeit,
+    /// the game was aborted before it really started (and doesn't count)
+    Aborted,
+    /// the result was changed since a player was flagged for cheating
+    CheatFlagged,
+    /// a termination added by a later version (the code is never the one of a variant above)
+    Unknown(u32),
+}
+
+impl Termination {
+    pub fn code(&self) -> u32 {
+        match self {
+            Termination::Normal => 0,
+            Termination::Resignation => 1,
+            Termination::TimeForfeit => 2,
+            Termination::Aborted => 3,
+            Termination::CheatFlagged => 4,
+            Termination::Unknown(code) => *code,
+        }
+    }
+
+    pub fn from_code(code: u32) -> Termination {
+        match code {
+            0 => Termination::Normal,
+            1 => Termination::Resignation,
+            2 => Termination::TimeForfeit,
+            3 => Termination::Aborted,
+            4 => Termination::CheatFlagged,
+            _ => Termination::Unknown(code),
+        }
+    }
+}
+
+/// a comment and (in studies with several annotators) the id of its author
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Comment {
+    pub author: Option<u8>,
+    pub text: String,
+}
+
+/// a numeric annotation glyph as defined by the PGN standard (e.g. 1 is "!" and 6 is "?!")
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Nag(pub u8);
+
+const NAG_SYMBOLS: [(&str, Nag); 6] = [("!", Nag(1)), ("?", Nag(2)), ("!!", Nag(3)), ("??", Nag(4)), ("!?", Nag(5)), ("?!", Nag(6))];
+
+impl Nag {
+    /// the NAG of a move suffix like "!?", None for symbols that aren't move annotations
+    pub fn from_symbol(symbol: &str) -> Option<Nag> {
+        NAG_SYMBOLS.iter().find(|(it, _)| *it == symbol).map(|(_, nag)| *nag)
+    }
+
+    /// the move suffix of NAGs 1 to 6, None for the others
+    pub fn symbol(&self) -> Option<&'static str> {
+        NAG_SYMBOLS.iter().find(|(_, nag)| nag == self).map(|(symbol, _)| *symbol)
+    }
+}
+
+/// prints the move suffix of the NAG (e.g. "!?") if it has one, else its PGN form (e.g. "$14")
+impl fmt::Display for Nag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.symbol() {
+            Some(symbol) => write!(f, "{symbol}"),
+            None => write!(f, "${}", self.0),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Annotation {
+    Highlight { square: Position, color: MarkupColor },
+    Arrow { from_to: FromTo, color: MarkupColor },
+}
+
+/// the colors study tools commonly offer for highlights and arrows
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MarkupColor {
+    Green,
+    Red,
+    Yellow,
+    Blue,
+}
+
+const MARKUP_COLORS: [MarkupColor; 4] = [MarkupColor::Green, MarkupColor::Red, MarkupColor::Yellow, MarkupColor::Blue];
+
+// a record is the varint index of its position, followed by its kind and the data of that kind
+const HIGHLIGHT_KIND_OFFSET: usize = 0;
+const ARROW_KIND_OFFSET: usize = 4;
+const COMMENT_KIND: usize = 8;
+const AUTHORED_COMMENT_KIND: usize = 9;
+const REVEAL_BOUNDARY_KIND: usize = 10;
+const CLOCK_KIND: usize = 11;
+const WHITE_RATING_KIND: usize = 12;
+const BLACK_RATING_KIND: usize = 13;
+const RESULT_KIND: usize = 14;
+const TERMINATION_KIND: usize = 15;
+const WHITE_BERSERK_KIND: usize = 16;
+const BLACK_BERSERK_KIND: usize = 17;
+const NAG_KIND: usize = 18;
+const GAME_RESULTS: [GameResult; 4] = [GameResult::WhiteWins, GameResult::BlackWins, GameResult::Draw, GameResult::Undecided];
+
+impl Extensions {
+    pub fn is_empty(&self) -> bool {
+        self.annotations.values().all(Vec::is_empty)
+            && self.comments.values().all(Vec::is_empty)
+            && self.nags.values().all(Vec::is_empty)
+            && self.clocks.is_empty()
+            && self.reveal_boundary.is_none()
+            && self.metadata == Metadata::default()
+    }
+
+    /// removes the records of the positions after the given one
+    pub(crate) fn truncate(&mut self, last_position_index: usize) {
+        self.annotations.retain(|position_index, _| *position_index <= last_position_index);
+        self.comments.retain(|position_index, _| *position_index <= last_position_index);
+        self.nags.retain(|position_index, _| *position_index <= last_position_index);
+        self.clocks.retain(|position_index, _| *position_index <= last_position_index);
+    }
+
+    /// fails if a record belongs to a position after `last_position_index`
+    pub(crate) fn check_position_indices(&self, last_position_index: usize) -> Result<(), ChessError> {
+        let max_position_index = self.annotations.keys().next_back()
+            .max(self.comments.keys().next_back())
+            .max(self.nags.keys().next_back())
+            .max(self.clocks.keys().next_back())
+            .max(self.reveal_boundary.as_ref());
+        match max_position_index {
+            Some(position_index) if *position_index > last_position_index => Err(ChessError::new(
+                ErrorKind::IllegalConfig,
+                format!("there are extension records for position {position_index}, but the game only has positions up to {last_position_index}"),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MarkupColor {
+    fn index(&self) -> usize {
+        MARKUP_COLORS.iter().position(|color| color == self).expect("all colors are listed")
+    }
+}
+
+/// writes the records in url-safe base64
+pub(crate) fn encode_extensions(extensions: &Extensions) -> String {
+    let mut encoded = String::new();
+    let metadata = &extensions.metadata;
+    for (rating_kind, rating) in [(WHITE_RATING_KIND, metadata.white_rating), (BLACK_RATING_KIND, metadata.black_rating)] {
+        if let Some(rating) = rating {
+            push_varint(&mut encoded, 0);
+            encoded.push(encode_base64_index(rating_kind));
+            push_varint(&mut encoded, rating as usize);
+        }
+    }
+    if let Some(result) = metadata.result {
+        push_varint(&mut encoded, 0);
+        encoded.push(encode_base64_index(RESULT_KIND));
+        encoded.push(encode_base64_index(GAME_RESULTS.iter().position(|it| *it == result).expect("all results are listed")));
+    }
+    if let Some(termination) = metadata.termination {
+        push_varint(&mut encoded, 0);
+        encoded.push(encode_base64_index(TERMINATION_KIND));
+        push_varint(&mut encoded, termination.code() as usize);
+    }
+    for (berserk_kind, has_berserked) in [(WHITE_BERSERK_KIND, metadata.white_berserk), (BLACK_BERSERK_KIND, metadata.black_berserk)] {
+        if has_berserked {
+            push_varint(&mut encoded, 0);
+            encoded.push(encode_base64_index(berserk_kind));
+        }
+    }
+    for (position_index, annotations) in &extensions.annotations {
+        for annotation in annotations {
+            push_varint(&mut encoded, *position_index);
+            match annotation {
+                Annotation::Highlight { square, color } => {
+                    encoded.push(encode_base64_index(HIGHLIGHT_KIND_OFFSET + color.index()));
+                    encoded.push(encode_base64(*square));
+                }
+                Annotation::Arrow { from_to, color } => {
+                    encoded.push(encode_base64_index(ARROW_KIND_OFFSET + color.index()));
+                    encoded.push(encode_base64(from_to.from));
+                    encoded.push(encode_base64(from_to.to));
+                }
+            }
+        }
+    }
+    for (position_index, comments) in &extensions.comments {
+        for comment in comments {
+            push_varint(&mut encoded, *position_index);
+            match comment.author {
+                None => encoded.push(encode_base64_index(COMMENT_KIND)),
+                Some(author) => {
+                    encoded.push(encode_base64_index(AUTHORED_COMMENT_KIND));
+                    push_bytes(&mut encoded, &[author]);
+                }
+            }
+            push_varint(&mut encoded, comment.text.len());
+            push_bytes(&mut encoded, comment.text.as_bytes());
+        }
+    }
+    for (position_index, nags) in &extensions.nags {
+        for nag in nags {
+            push_varint(&mut encoded, *position_index);
+            encoded.push(encode_base64_index(NAG_KIND));
+            push_varint(&mut encoded, nag.0 as usize);
+        }
+    }
+    for (position_index, time_left) in &extensions.clocks {
+        push_varint(&mut encoded, *position_index);
+        encoded.push(encode_base64_index(CLOCK_KIND));
+        push_varint(&mut encoded, (time_left.as_millis() / 100) as usize);
+    }
+    if let Some(reveal_boundary) = extensions.reveal_boundary {
+        push_varint(&mut encoded, reveal_boundary);
+        encoded.push(encode_base64_index(REVEAL_BOUNDARY_KIND));
+    }
+    encoded
+}
+
+/// reads the records written by [encode_extensions]
+pub(crate) fn decode_extensions(encoded: &str) -> Result<Extensions, ChessError> {
+    let mut extensions = Extensions::default();
+    let mut encoded_chars = encoded.chars();
+    while !encoded_chars.as_str().is_empty() {
+        let position_index = read_varint(&mut encoded_chars)?;
+        let kind = decode_base64_index(next_char(&mut encoded_chars)?)?;
+        if kind == COMMENT_KIND || kind == AUTHORED_COMMENT_KIND {
+            let author = if kind == AUTHORED_COMMENT_KIND {
+                Some(read_bytes(&mut encoded_chars, 1)?[0])
+            } else {
+                None
+            };
+            let text_len = read_varint(&mut encoded_chars)?;
+            let Ok(text) = String::from_utf8(read_bytes(&mut encoded_chars, text_len)?) else {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("comment for position {position_index} isn't valid utf-8")));
+            };
+            extensions.comments.entry(position_index).or_default().push(Comment { author, text });
+            continue;
+        }
+        if kind == REVEAL_BOUNDARY_KIND {
+            extensions.reveal_boundary = Some(position_index);
+            continue;
+        }
+        if kind == WHITE_RATING_KIND || kind == BLACK_RATING_KIND {
+            let Ok(rating) = u16::try_from(read_varint(&mut encoded_chars)?) else {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("rating in {encoded} is too big")));
+            };
+            if kind == WHITE_RATING_KIND {
+                extensions.metadata.white_rating = Some(rating);
+            } else {
+                extensions.metadata.black_rating = Some(rating);
+            }
+            continue;
+        }
+        if kind == RESULT_KIND {
+            let result_index = decode_base64_index(next_char(&mut encoded_chars)?)?;
+            let Some(result) = GAME_RESULTS.get(result_index) else {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("unknown result {result_index} in {encoded}")));
+            };
+            extensions.metadata.result = Some(*result);
+            continue;
+        }
+        if kind == TERMINATION_KIND {
+            let Ok(code) = u32::try_from(read_varint(&mut encoded_chars)?) else {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("termination code in {encoded} is too big")));
+            };
+            extensions.metadata.termination = Some(Termination::from_code(code));
+            continue;
+        }
+        if kind == WHITE_BERSERK_KIND {
+            extensions.metadata.white_berserk = true;
+            continue;
+        }
+        if kind == BLACK_BERSERK_KIND {
+            extensions.metadata.black_berserk = true;
+            continue;
+        }
+        if kind == NAG_KIND {
+            let Ok(nag) = u8::try_from(read_varint(&mut encoded_chars)?) else {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("NAG for position {position_index} is too big")));
+            };
+            extensions.nags.entry(position_index).or_default().push(Nag(nag));
+            continue;
+        }
+        if kind == CLOCK_KIND {
+            let Some(millis) = (read_varint(&mut encoded_chars)? as u64).checked_mul(100) else {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("clock for position {position_index} is too big")));
+            };
+            extensions.clocks.insert(position_index, Duration::from_millis(millis));
+            continue;
+        }
+        let annotation = match kind {
+            HIGHLIGHT_KIND_OFFSET..ARROW_KIND_OFFSET => Annotation::Highlight {
+                square: decode_base64(next_char(&mut encoded_chars)?)?,
+                color: MARKUP_COLORS[kind - HIGHLIGHT_KIND_OFFSET],
+            },
+            ARROW_KIND_OFFSET..8 => Annotation::Arrow {
+                from_to: FromTo::new(
+                    decode_base64(next_char(&mut encoded_chars)?)?,
+                    decode_base64(next_char(&mut encoded_chars)?)?,
+                ),
+                color: MARKUP_COLORS[kind - ARROW_KIND_OFFSET],
+            },
+            _ => {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("unknown extension record kind {kind} in {encoded}")));
+            }
+        };
+        extensions.annotations.entry(position_index).or_default().push(annotation);
+    }
+    Ok(extensions)
+}
+
+fn next_char(encoded_chars: &mut Chars) -> Result<char, ChessError> {
+    encoded_chars.next().ok_or_else(|| ChessError::new(ErrorKind::IllegalFormat, "the last extension record is incomplete".to_string()))
+}
+
+// bytes are regrouped into 6bit chars, the last char is padded with zero bits
+fn push_bytes(encoded: &mut String, bytes: &[u8]) {
+    encoded.extend(regroup_bits(bytes, 8, 6).into_iter().map(|value| encode_base64_index(value as usize)));
+}
+
+fn read_bytes(encoded_chars: &mut Chars, nr_of_bytes: usize) -> Result<Vec<u8>, ChessError> {
+    let Some(nr_of_bits) = nr_of_bytes.checked_mul(8) else {
+        return Err(ChessError::new(ErrorKind::IllegalFormat, format!("an extension record claims to contain {nr_of_bytes} bytes")));
+    };
+    let values = (0..nr_of_bits.div_ceil(6))
+        .map(|_| next_char(encoded_chars).and_then(decode_base64_index).map(|value| value as u8))
+        .collect::<Result<Vec<u8>, ChessError>>()?;
+    Ok(regroup_bits(&values, 6, 8))
+}
+
+// 5 bits of the value per char, the 6th bit signals that more chars follow
+fn push_varint(encoded: &mut String, mut value: usize) {
+    while value >= 32 {
+        encoded.push(encode_base64_index(32 | (value & 31)));
+        value >>= 5;
+    }
+    encoded.push(encode_base64_index(value));
+}
+
+fn read_varint(encoded_chars: &mut Chars) -> Result<usize, ChessError> {
+    let mut value: usize = 0;
+    let mut shift: u32 = 0;
+    loop {
+        let chunk = decode_base64_index(next_char(encoded_chars)?)?;
+        if shift > usize::BITS - 5 {
+            return Err(ChessError::new(ErrorKind::IllegalFormat, "position index of an extension record is too big".to_string()));
+        }
+        value |= (chunk & 31) << shift;
+        if chunk < 32 {
+            return Ok(value);
+        }
+        shift += 5;
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    fn annotations(annotations: Vec<(usize, Annotation)>) -> Extensions {
+        let mut extensions = Extensions::default();
+        for (position_index, annotation) in annotations {
+            extensions.annotations.entry(position_index).or_default().push(annotation);
+        }
+        extensions
+    }
+
+    fn comments(comments: Vec<(usize, Comment)>) -> Extensions {
+        let mut extensions = Extensions::default();
+        for (position_index, comment) in comments {
+            extensions.comments.entry(position_index).or_default().push(comment);
+        }
+        extensions
+    }
+
+    #[rstest(
+        extensions, expected_encoded,
+        case(Extensions::default(), ""),
+        case(annotations(vec![(0, Annotation::Highlight { square: "e4".parse().unwrap(), color: MarkupColor::Green })]), "AAc"),
+        case(annotations(vec![
+            (1, Annotation::Arrow { from_to: "g1f3".parse().unwrap(), color: MarkupColor::Red }),
+            (1, Annotation::Highlight { square: "a8".parse().unwrap(), color: MarkupColor::Blue }),
+            (40, Annotation::Highlight { square: "h1".parse().unwrap(), color: MarkupColor::Yellow }),
+        ]), "BFGVBD4oBCH"),
+        case(comments(vec![
+            (1, Comment { author: None, text: "best by test".to_string() }),
+            (3, Comment { author: Some(7), text: "ü".to_string() }),
+        ]), "BIMYmVzdCBieSB0ZXN0DJBwCw7w"),
+        case(Extensions { clocks: BTreeMap::from([(1, Duration::from_secs(180)), (2, Duration::from_millis(179_900))]), ..Extensions::default() }, "BLo4BCLn4B"),
+        case(Extensions { nags: BTreeMap::from([(1, vec![Nag(5)]), (2, vec![Nag(4), Nag(146)])]), ..Extensions::default() }, "BSFCSECSyE"),
+        case(Extensions { metadata: Metadata { white_rating: Some(1500), black_rating: Some(2850), result: Some(GameResult::Draw), ..Metadata::default() }, ..Extensions::default() }, "AM8uBANi5CAOC"),
+        case(Extensions { metadata: Metadata { termination: Some(Termination::CheatFlagged), black_berserk: true, ..Metadata::default() }, ..Extensions::default() }, "APEAR"),
+        case(Extensions { metadata: Metadata { termination: Some(Termination::Unknown(42)), white_berserk: true, ..Metadata::default() }, ..Extensions::default() }, "APqBAQ"),
+        case(Extensions { reveal_boundary: Some(5), ..annotations(vec![(2, Annotation::Highlight { square: "a1".parse().unwrap(), color: MarkupColor::Red })]) }, "CBAFK"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_encode_extensions(extensions: Extensions, expected_encoded: &str) {
+        let encoded = encode_extensions(&extensions);
+        assert_eq!(encoded, expected_encoded);
+        assert_eq!(decode_extensions(&encoded).unwrap(), extensions);
+    }
+
+    #[rstest(
+        symbol, expected_nag,
+        case("!", Some(Nag(1))),
+        case("??", Some(Nag(4))),
+        case("?!", Some(Nag(6))),
+        case("+-", None),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_nag_symbols(symbol: &str, expected_nag: Option<Nag>) {
+        assert_eq!(Nag::from_symbol(symbol), expected_nag);
+        if let Some(nag) = expected_nag {
+            assert_eq!(nag.to_string(), symbol);
+        }
+        assert_eq!(Nag(14).to_string(), "$14");
+    }
+
+    #[rstest(
+        code,
+        case(0),
+        case(4),
+        case(5),
+        case(u32::MAX),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_termination_code_round_trip(code: u32) {
+        assert_eq!(Termination::from_code(code).code(), code);
+    }
+
+    #[rstest(
+        encoded,
+        case("A"),
+        case("AA"),
+        case("AI"),
+        case("AEc"),
+        case("-"),
+        case("AIC"),
+        case("AIB_A"),
+        case("AM____B"),
+        case("AOE"),
+        case("BSoI"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decode_extensions_rejects_illegal_records(encoded: &str) {
+        assert!(decode_extensions(encoded).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::game::game_state::GameState;
+use crate::compression::alphabet::Alphabet;
+use crate::compression::start_position::{find_start_position, find_start_position_by_fen};
+use crate::compression::version::FormatVersion;
+
+/// The format header describes how the rest of an encoded game has to be read.
+///
+/// Games in the default format (url-safe base64) don't have a header, so all encodings
+/// created before the header was introduced stay valid. Every other format starts with
+/// '.', followed by one tag char per non-default setting, followed by another '.'
+/// (e.g. ".Q." for the QR alphanumeric alphabet). '.' isn't part of any alphabet, so a header
+/// can always be told apart from an encoded move.
+/// Tags are letters (or the digit of the format version) and matched case-insensitive,
+/// so the header survives the same channels as the alphabet it announces.
+/// Games that don't start from the classic position have the tag 'F' and the FEN of their
+/// start position (written url-safe and case-sensitive) followed by another '.' after the tags
+/// (e.g. ".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.k").
+/// Registered start positions (see [start_positions](crate::start_position::start_positions)) are written
+/// shorter: with the tag 'P' and the id of the position followed by another '.' (e.g. ".P.N.k" for knight odds).
+/// Games with the tag 'R' have rich extension records, which follow the moves after another '.'.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct FormatHeader {
+    pub alphabet: Alphabet,
+    /// a checksum is appended, so altered games are detected
+    pub checksum: bool,
+    /// parity chars are appended, so single wrong chars can be corrected
+    pub error_correction: bool,
+    /// the game was scrambled with a key, which is needed to decompress it
+    pub scrambled: bool,
+    pub version: FormatVersion,
+    /// the FEN of the start position of games that don't start from the classic position
+    pub start_fen: Option<String>,
+    /// extension records (see [Extensions](crate::extension::Extensions)) follow the moves
+    pub rich: bool,
+}
+
+pub const HEADER_DELIMITER: char = '.';
+/// separates the games packed into one string by [compress_games](crate::compress::compress_games).
+/// It's url-safe but not part of any alphabet, header or FEN, so it can't occur within a game.
+pub const GAME_SEPARATOR: char = '~';
+
+pub(crate) const QR_ALPHANUMERIC_TAG: char = 'Q';
+pub(crate) const BASE32_TAG: char = 'B';
+pub(crate) const CHECKSUM_TAG: char = 'C';
+pub(crate) const ERROR_CORRECTION_TAG: char = 'E';
+pub(crate) const SCRAMBLED_TAG: char = 'S';
+pub(crate) const V2_TAG: char = '2';
+pub(crate) const START_FEN_TAG: char = 'F';
+pub(crate) const START_POSITION_TAG: char = 'P';
+pub(crate) const RICH_TAG: char = 'R';
+
+impl FormatHeader {
+    pub fn is_default(&self) -> bool {
+        *self == FormatHeader::default()
+    }
+
+    /// the position the game starts from
+    pub fn start_game_state(&self) -> Result<GameState, ChessError> {
+        match &self.start_fen {
+            None => Ok(GameState::classic()),
+            Some(start_fen) => GameState::from_fen(start_fen),
+        }
+    }
+
+    /// splits an encoded game into its header and the rest. Values without header get the default header.
+    pub fn split_from(encoded: &str) -> Result<(FormatHeader, &str), ChessError> {
+        let Some(after_start_delimiter) = encoded.strip_prefix(HEADER_DELIMITER) else {
+            return Ok((FormatHeader::default(), encoded));
+        };
+        let Some((tags, mut payload)) = after_start_delimiter.split_once(HEADER_DELIMITER) else {
+            return Err(ChessError::new(ErrorKind::IllegalFormat, format!("format header of {encoded} isn't terminated by '{HEADER_DELIMITER}'")));
+        };
+
+        let mut header = FormatHeader::default();
+        for tag in tags.chars() {
+            let alphabet = match tag.to_ascii_uppercase() {
+                QR_ALPHANUMERIC_TAG => Alphabet::QrAlphanumeric,
+                BASE32_TAG => Alphabet::Base32,
+                CHECKSUM_TAG => {
+                    header.checksum = true;
+                    continue;
+                }
+                ERROR_CORRECTION_TAG => {
+                    header.error_correction = true;
+                    continue;
+                }
+                SCRAMBLED_TAG => {
+                    header.scrambled = true;
+                    continue;
+                }
+                V2_TAG => {
+                    header.version = FormatVersion::V2;
+                    continue;
+                }
+                RICH_TAG => {
+                    header.rich = true;
+                    continue;
+                }
+                START_FEN_TAG | START_POSITION_TAG => {
+                    if header.start_fen.is_some() {
+                        return Err(ChessError::new(
+                            ErrorKind::IllegalFormat,
+                            format!("format header of {encoded} specifies more than one start position"),
+                        ));
+                    }
+                    let Some((start_position, rest)) = payload.split_once(HEADER_DELIMITER) else {
+                        return Err(ChessError::new(
+                            ErrorKind::IllegalFormat,
+                            format!("start position of {encoded} isn't terminated by '{HEADER_DELIMITER}'"),
+                        ));
+                    };
+                    header.start_fen = if tag.eq_ignore_ascii_case(&START_FEN_TAG) {
+                        Some(from_url_safe_fen(start_position))
+                    } else {
+                        let Some(registered_start_position) = find_start_position(start_position) else {
+                            return Err(ChessError::new(
+                                ErrorKind::IllegalFormat,
+                                format!("'{start_position}' in format header of {encoded} isn't the id of a registered start position"),
+                            ));
+                        };
+                        Some(registered_start_position.fen.to_string())
+                    };
+                    payload = rest;
+                    continue;
+                }
+                _ => {
+                    return Err(ChessError::new(ErrorKind::IllegalFormat, format!("unknown tag '{tag}' in format header of {encoded}")));
+                }
+            };
+            if header.alphabet != Alphabet::UrlSafeBase64 {
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("format header of {encoded} specifies more than one alphabet")));
+            }
+            header.alphabet = alphabet;
+        }
+        Ok((header, payload))
+    }
+}
+
+/// writes nothing for the default header
+impl fmt::Display for FormatHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_default() {
+            return Ok(());
+        }
+        write!(f, "{HEADER_DELIMITER}")?;
+        match self.alphabet {
+            Alphabet::UrlSafeBase64 => {}
+            Alphabet::QrAlphanumeric => write!(f, "{QR_ALPHANUMERIC_TAG}")?,
+            Alphabet::Base32 => write!(f, "{BASE32_TAG}")?,
+        };
+        if self.checksum {
+            write!(f, "{CHECKSUM_TAG}")?;
+        }
+        if self.error_correction {
+            write!(f, "{ERROR_CORRECTION_TAG}")?;
+        }
+        if self.scrambled {
+            write!(f, "{SCRAMBLED_TAG}")?;
+        }
+        if self.version == FormatVersion::V2 {
+            write!(f, "{V2_TAG}")?;
+        }
+        if self.rich {
+            write!(f, "{RICH_TAG}")?;
+        }
+        let registered_start_position = self.start_fen.as_deref().and_then(find_start_position_by_fen);
+        if registered_start_position.is_some() {
+            write!(f, "{START_POSITION_TAG}")?;
+        } else if self.start_fen.is_some() {
+            write!(f, "{START_FEN_TAG}")?;
+        }
+        write!(f, "{HEADER_DELIMITER}")?;
+        if let Some(start_position) = registered_start_position {
+            write!(f, "{}{HEADER_DELIMITER}", start_position.id)?;
+        } else if let Some(start_fen) = &self.start_fen {
+            write!(f, "{}{HEADER_DELIMITER}", to_url_safe_fen(start_fen))?;
+        }
+        Ok(())
+    }
+}
+
+// '-' only appears in the FEN as a field of its own, so it can stand in for the '/' between the rows
+fn to_url_safe_fen(fen: &str) -> String {
+    fen.split_whitespace().map(|field| field.replace('/', "-")).collect::<Vec<String>>().join("_")
+}
+
+fn from_url_safe_fen(url_safe_fen: &str) -> String {
+    let mut fields = url_safe_fen.split('_');
+    let board_field = fields.next().unwrap_or_default().replace('-', "/");
+    let mut fen = board_field;
+    for field in fields {
+        fen.push(' ');
+        fen.push_str(field);
+    }
+    fen
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        encoded, expected_alphabet, expected_payload,
+        case("", Alphabet::UrlSafeBase64, ""),
+        case("ckGV5qh", Alphabet::UrlSafeBase64, "ckGV5qh"),
+        case("..ckGV5qh", Alphabet::UrlSafeBase64, "ckGV5qh"),
+        case(".Q.E90PBPL88", Alphabet::QrAlphanumeric, "E90PBPL88"),
+        case(".b.ojazlzvii", Alphabet::Base32, "ojazlzvii"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_split_from(encoded: &str, expected_alphabet: Alphabet, expected_payload: &str) {
+        let (header, payload) = FormatHeader::split_from(encoded).unwrap();
+        assert_eq!(header.alphabet, expected_alphabet);
+        assert_eq!(payload, expected_payload);
+    }
+
+    #[rstest(
+        encoded, expected_header,
+        case(".E.ckGV5qhAB", FormatHeader { alphabet: Alphabet::UrlSafeBase64, checksum: false, error_correction: true, scrambled: false, version: FormatVersion::V1, start_fen: None, rich: false }),
+        case(".qe.E90PBPL88AB", FormatHeader { alphabet: Alphabet::QrAlphanumeric, checksum: false, error_correction: true, scrambled: false, version: FormatVersion::V1, start_fen: None, rich: false }),
+        case(".C.ckGV5qhAB", FormatHeader { alphabet: Alphabet::UrlSafeBase64, checksum: true, error_correction: false, scrambled: false, version: FormatVersion::V1, start_fen: None, rich: false }),
+        case(".ecb.OJAZLZVIIABCD", FormatHeader { alphabet: Alphabet::Base32, checksum: true, error_correction: true, scrambled: false, version: FormatVersion::V1, start_fen: None, rich: false }),
+        case(".S.ckGV5qh", FormatHeader { alphabet: Alphabet::UrlSafeBase64, checksum: false, error_correction: false, scrambled: true, version: FormatVersion::V1, start_fen: None, rich: false }),
+        case(".2.NIN", FormatHeader { alphabet: Alphabet::UrlSafeBase64, checksum: false, error_correction: false, scrambled: false, version: FormatVersion::V2, start_fen: None, rich: false }),
+        case(".R.c.AAc", FormatHeader { rich: true, ..FormatHeader::default() }),
+        case(".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.k", FormatHeader { start_fen: Some("8/8/4k3/8/8/4K3/4P3/8 w - - 0 1".to_string()), ..FormatHeader::default() }),
+        case(".P.N.k", FormatHeader { start_fen: Some("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1".to_string()), ..FormatHeader::default() }),
+        case(".qp.pm.k", FormatHeader { alphabet: Alphabet::QrAlphanumeric, start_fen: Some("rnbqkbnr/pppppppp/8/8/8/8/PPPPP1PP/RNBQKBNR b KQkq - 0 1".to_string()), ..FormatHeader::default() }),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_split_from_with_flag_tags(encoded: &str, expected_header: FormatHeader) {
+        let (header, _) = FormatHeader::split_from(encoded).unwrap();
+        assert_eq!(header, expected_header);
+        assert_eq!(FormatHeader::split_from(&format!("{header}")).unwrap().0, header);
+    }
+
+    #[rstest(
+        encoded,
+        case(".Q"),
+        case(".X.abc"),
+        case(".QB.abc"),
+        case(".F.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1"),
+        case(".P.X.k"),
+        case(".P.N"),
+        case(".PF.N.8-8-4k3-8-8-4K3-4P3-8_w_-_-_0_1.k"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_split_from_rejects_illegal_headers(encoded: &str) {
+        assert!(FormatHeader::split_from(encoded).is_err());
+    }
+
+    #[rstest(
+        alphabet, expected_header,
+        case(Alphabet::UrlSafeBase64, ""),
+        case(Alphabet::QrAlphanumeric, ".Q."),
+        case(Alphabet::Base32, ".B."),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_display(alphabet: Alphabet, expected_header: &str) {
+        let header = FormatHeader { alphabet, ..FormatHeader::default() };
+        assert_eq!(format!("{header}"), expected_header);
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+use crate::base::errors::ChessError;
+use crate::compression::compress::encode_next_move;
+use crate::compression::decompress::{decompress_with_options, DecompressOptions};
+use crate::compression::explain::{explain, Interpretation};
+use crate::compression::header::FormatHeader;
+use crate::compression::summary::GameResult;
+use crate::compression::version::{convert, FormatVersion};
+
+/// something about a (e.g. hand-edited or templated) encoded game that decodes fine but should be written differently
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LintFinding {
+    /// the format header is still read but not written like this anymore (e.g. lowercase tags,
+    /// tags in another order or an empty header "..")
+    DeprecatedHeader { found: String, canonical: String },
+    /// the moves decode, but [compress](crate::compress::compress) would write them differently
+    NonCanonical { canonical: String },
+    /// a move was written as from- and to-position, although only one figure can reach the target.
+    /// The chars are those of the url-safe base64 form (without format header).
+    RedundantTwoCharMove { half_move_index: usize, chars: String, shorter: String },
+    /// the game is over (mate, stalemate or insufficient material) but its result isn't stored with it
+    MissingResult { result: GameResult },
+    /// the game can't be decoded, so nothing else was checked
+    Invalid { error: ChessError },
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintFinding::DeprecatedHeader { found, canonical } => write!(f, "header \"{found}\" should be written as \"{canonical}\""),
+            LintFinding::NonCanonical { canonical } => write!(f, "the canonical encoding is \"{canonical}\""),
+            LintFinding::RedundantTwoCharMove { half_move_index, chars, shorter } => {
+                write!(f, "move {} \"{chars}\" can be written as \"{shorter}\"", half_move_index + 1)
+            }
+            LintFinding::MissingResult { result } => write!(f, "the game is over ({result:?}) but has no result record"),
+            LintFinding::Invalid { error } => write!(f, "invalid, {:?}: {}", error.kind, error.msg),
+        }
+    }
+}
+
+/// checks an encoded game for forms that decode, but aren't what this crate would write.
+/// An empty result means the game is in its canonical form.
+pub fn lint(encoded: &str) -> Vec<LintFinding> {
+    let lint_decodable = || -> Result<Vec<LintFinding>, ChessError> {
+        let (header, payload) = FormatHeader::split_from(encoded)?;
+        let game = decompress_with_options(encoded, &DecompressOptions {
+            allow_spoilers: true,
+            ..DecompressOptions::default()
+        })?;
+        let canonical = convert(encoded, header.version)?;
+        let (_, canonical_payload) = FormatHeader::split_from(&canonical)?;
+
+        let mut findings: Vec<LintFinding> = Vec::new();
+        let found_header = &encoded[..encoded.len() - payload.len()];
+        let canonical_header = &canonical[..canonical.len() - canonical_payload.len()];
+        if found_header != canonical_header {
+            findings.push(LintFinding::DeprecatedHeader {
+                found: found_header.to_string(),
+                canonical: canonical_header.to_string(),
+            });
+        }
+        if payload != canonical_payload {
+            findings.push(LintFinding::NonCanonical { canonical: canonical.clone() });
+        }
+        if header.version == FormatVersion::V1 {
+            let mut game_state = header.start_game_state()?;
+            for step in explain(encoded) {
+                let a_move = match step.interpretation {
+                    Interpretation::UniqueTarget { a_move, .. } | Interpretation::LegalMoveIndex { a_move, .. } => a_move,
+                    Interpretation::FromTo { a_move, .. } => {
+                        let shorter = encode_next_move(a_move, &game_state, step.half_move_index)?;
+                        if shorter.len() < step.chars.len() {
+                            findings.push(LintFinding::RedundantTwoCharMove {
+                                half_move_index: step.half_move_index,
+                                chars: step.chars,
+                                shorter,
+                            });
+                        }
+                        a_move
+                    }
+                    Interpretation::Invalid { error } => return Err(error),
+                };
+                game_state = game_state.do_move(a_move)?.0;
+            }
+        }
+        if game.extensions.metadata.result.is_none() && game.result != GameResult::Undecided {
+            findings.push(LintFinding::MissingResult { result: game.result });
+        }
+        Ok(findings)
+    };
+    lint_decodable().unwrap_or_else(|error| vec![LintFinding::Invalid { error }])
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        encoded, expected_findings,
+        case("", vec![]),
+        case("ckGV", vec![]),
+        case(".C.ckGVE4", vec![]),
+        case("Ka", vec![
+            "the canonical encoding is \"a\"",
+            "move 1 \"Ka\" can be written as \"a\"",
+        ]),
+        case("c0kGV", vec![
+            "the canonical encoding is \"ckGV\"",
+            "move 2 \"0k\" can be written as \"k\"",
+        ]),
+        case(".c.ckGVE4", vec![
+            "header \".c.\" should be written as \".C.\"",
+        ]),
+        case("..ckGV", vec![
+            "header \"..\" should be written as \"\"",
+        ]),
+        case("NVkef", vec![
+            "the game is over (BlackWins) but has no result record",
+        ]),
+        case("ckG", vec![
+            "invalid, IllegalFormat: second position missing for 1 move for white after start position was g1",
+        ]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_lint(encoded: &str, expected_findings: Vec<&str>) {
+        let actual_findings: Vec<String> = lint(encoded).iter().map(|finding| finding.to_string()).collect();
+        assert_eq!(actual_findings, expected_findings);
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// callbacks for the metrics of a (long-running) service, e.g. Prometheus counters and histograms.
+/// Pass them with [CompressOptions::metrics](crate::compress::CompressOptions::metrics) and
+/// [DecompressOptions::metrics](crate::decompress::DecompressOptions::metrics) (e.g. via a [Codec](crate::codec::Codec)),
+/// afterwards every compress and decompress with these options reports to them.
+///
+/// The callbacks are invoked on the thread that compressed/decompressed the game, so keep them cheap.
+pub trait Metrics: Send + Sync {
+    fn on_compress(&self, _report: &MetricsReport) {}
+    /// is also invoked for every attempt of [DecompressOptions::repair_confusables](crate::decompress::DecompressOptions::repair_confusables)
+    fn on_decompress(&self, _report: &MetricsReport) {}
+}
+
+// so that the options carrying the metrics can still derive Debug
+impl fmt::Debug for dyn Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Metrics")
+    }
+}
+
+/// what a single compress or decompress did
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MetricsReport {
+    /// the half moves encoded or decoded (up to an error)
+    pub half_moves: usize,
+    /// the length of the encoded moves (url-safe base64 without format header and extensions)
+    pub encoded_len: usize,
+    pub duration: Duration,
+    pub failed: bool,
+}
+
+/// returns when a measurement started, but only if there are metrics to report to
+pub(crate) fn start_measurement(metrics: Option<&dyn Metrics>) -> Option<Instant> {
+    metrics.map(|_| Instant::now())
+}
+
+pub(crate) fn report(metrics: Option<&dyn Metrics>, started: Option<Instant>, on_report: impl FnOnce(&dyn Metrics, Duration)) {
+    if let (Some(metrics), Some(started)) = (metrics, started) {
+        on_report(metrics, started.elapsed());
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use crate::base::a_move::Move;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::{compress, compress_with_options, CompressOptions};
+    use crate::compression::decompress::{decompress_with_options, DecompressOptions};
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingMetrics {
+        reports: Mutex<Vec<(&'static str, MetricsReport)>>,
+    }
+
+    impl Metrics for RecordingMetrics {
+        fn on_compress(&self, report: &MetricsReport) {
+            self.reports.lock().unwrap().push(("compress", *report));
+        }
+
+        fn on_decompress(&self, report: &MetricsReport) {
+            self.reports.lock().unwrap().push(("decompress", *report));
+        }
+    }
+
+    #[test]
+    fn test_metrics_are_reported() {
+        let metrics = Arc::new(RecordingMetrics::default());
+        let compress_options = CompressOptions { metrics: Some(metrics.clone()), ..CompressOptions::default() };
+        let decompress_options = DecompressOptions { metrics: Some(metrics.clone()), ..DecompressOptions::default() };
+
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3", ",").unwrap();
+        compress_with_options(given_moves.clone(), &compress_options).unwrap();
+        decompress_with_options("ckGV", &decompress_options).unwrap();
+        assert!(decompress_with_options("ckG", &decompress_options).is_err());
+        // without metrics in the options nothing is reported
+        compress(given_moves).unwrap();
+        decompress_with_options("ckGV", &DecompressOptions::default()).unwrap();
+
+        let reports: Vec<(&str, usize, usize, bool)> = metrics.reports.lock().unwrap().iter()
+            .map(|(operation, report)| (*operation, report.half_moves, report.encoded_len, report.failed))
+            .collect();
+        assert_eq!(reports, vec![
+            ("compress", 3, 4, false),
+            ("decompress", 3, 4, false),
+            ("decompress", 2, 3, true),
+        ]);
+    }
+}
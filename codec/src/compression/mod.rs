@@ -0,0 +1,773 @@
+pub mod alphabet;
+pub mod archive;
+pub mod clock;
+pub mod codec;
+pub mod compress;
+pub mod decoder;
+pub mod encoder;
+pub mod decompress;
+pub mod delta;
+pub mod encoded_game;
+pub mod examples;
+pub mod explain;
+pub mod extension;
+pub mod header;
+pub mod lint;
+pub mod metrics;
+pub mod move_codec;
+pub mod players;
+pub mod repair;
+pub mod self_test;
+pub mod spec;
+pub mod start_position;
+pub mod summary;
+pub mod unfurl;
+pub mod version;
+mod base64;
+mod checksum;
+mod error_correction;
+mod scramble;
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+    use crate::base::a_move::{Move, MoveData, MoveSquares};
+    use crate::base::errors::ErrorKind;
+    use crate::base::position::Position;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::base::util::vec_to_str;
+    use crate::compression::alphabet::{Alphabet, AlphabetVariant};
+    use crate::compression::compress::{compress, compress_from, compress_games, compress_range, compress_validated, compress_with_options, compress_with_warnings, compress_from_with_options, compress_from_with_warnings, CompressOptions, CompressWarning};
+    use crate::compression::decompress::{decompress, decompress_from, decompress_games, decompress_lossy, decompress_with_options, final_fen, DecodeWarning, DecompressOptions, DrawReason, PositionData};
+    use crate::compression::extension::{Annotation, Comment, Extensions, MarkupColor, Metadata, Nag};
+    use crate::compression::summary::{self, GameResult};
+    use crate::game::game_state::{GameState, IllegalMoveReason};
+
+    fn remove_space(s: &str) -> String {
+        s.replace(' ', "")
+    }
+
+    #[template]
+    #[rstest]
+    #[case("", "")]       //    | "no moves -> empty encoded String
+    #[case("c2c3", "KS")] // KS | destination not unique target -> encoding needs two chars
+    #[case("c2c4", "a")]  // Ka | destination is unique target -> encoding needs one char
+    #[case("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4, g2g3, h4g3, c7d8Q", "Y 3v g h p n y f W W 7Q")] // IY 3v Yg xh gp vn py nf OW fW y7Q | tests all pawn moves single-step, double-step, diagonal-capture, en-passant & promotion
+    #[case("d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1", "T u CU 2 BS -t DL 8_ EA")]              // LT 2u CU 92 BS -t DL 8_ EA        | tests king- & queen-side castling
+    fn compress_decompress_cases(#[case] decoded_moves: &str, #[case] encoded_moves_seperated_by_space: &str) {}
+
+    #[apply(compress_decompress_cases)]
+    fn test_compress(decoded_moves: &str, encoded_moves_seperated_by_space: &str) {
+        let actual_encoded_game: String = {
+            let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+            compress(given_moves).unwrap()
+        };
+        let expected_encoded_game: String = remove_space(encoded_moves_seperated_by_space);
+        assert_eq!(actual_encoded_game, expected_encoded_game);
+    }
+
+    #[apply(compress_decompress_cases)]
+    fn test_decompress(decoded_moves: &str, encoded_moves_seperated_by_space: &str) {
+        let actual_decoded_moves = {
+            let given_encoded_game = remove_space(encoded_moves_seperated_by_space);
+            let (positions_data, moves_data): (Vec<PositionData>, Vec<MoveData>) = decompress(given_encoded_game.as_str()).unwrap();
+            assert_eq!(positions_data.len(), moves_data.len()+1, "each game should consist of 1 more position than moves made, but got {} positions and {} moves", positions_data.len(), moves_data.len());
+            let given_moves: Vec<Move> = moves_data.into_iter().map(Move::from).collect();
+            vec_to_str(&given_moves, ",")
+        };
+        let expected_decoded_moves = format!("[{}]", remove_space(decoded_moves));
+        assert_eq!(expected_decoded_moves, actual_decoded_moves);
+    }
+
+    #[apply(compress_decompress_cases)]
+    fn test_final_fen(_decoded_moves: &str, encoded_moves_seperated_by_space: &str) {
+        let encoded_game = remove_space(encoded_moves_seperated_by_space);
+        let (positions, _) = decompress(&encoded_game).unwrap();
+        assert_eq!(final_fen(&encoded_game).unwrap(), positions.last().unwrap().fen);
+    }
+
+    #[apply(compress_decompress_cases)]
+    fn test_compress_decompress_with_other_alphabets(decoded_moves: &str, _encoded_moves_seperated_by_space: &str) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        for alphabet in [Alphabet::QrAlphanumeric, Alphabet::Base32] {
+            let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+                alphabet,
+                ..CompressOptions::default()
+            }).unwrap();
+            // the alphabet is detected by the format header
+            let (_, moves_data) = decompress(&encoded_game).unwrap();
+            assert_eq!(moves_data.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves, "alphabet {alphabet:?}");
+        }
+    }
+
+    #[rstest(
+        encoded_game, expected_origins, expected_suggestions,
+        case("S", "b1, c2", vec!["BS", "KS"]),
+        case("cq", "c7, b8", vec!["yq", "5q"]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_ambiguous_origin_suggests_unambiguous_encodings(encoded_game: &str, expected_origins: &str, expected_suggestions: Vec<&str>) {
+        let Err(error) = decompress(encoded_game) else {
+            panic!("{encoded_game} should be ambiguous");
+        };
+        let ErrorKind::AmbiguousOrigin { origins, suggested_encodings, .. } = error.kind else {
+            panic!("unexpected error: {error}");
+        };
+        assert_eq!(origins, parse_to_vec::<Position>(expected_origins, ",").unwrap());
+        assert_eq!(suggested_encodings, expected_suggestions);
+        for suggested_encoding in suggested_encodings {
+            let fixed_game = format!("{}{suggested_encoding}", &encoded_game[..encoded_game.len() - 1]);
+            assert!(decompress(&fixed_game).is_ok(), "{fixed_game}");
+        }
+    }
+
+    #[test]
+    fn test_decompress_uses_alphabet_from_options_for_games_without_header() {
+        let decompressed_game = decompress_with_options("E90PBPL88", &DecompressOptions {
+            alphabet: Alphabet::QrAlphanumeric,
+            ..DecompressOptions::default()
+        }).unwrap();
+        assert_eq!(decompressed_game.moves.len(), 5);
+    }
+
+    #[test]
+    fn test_decompress_legacy_standard_base64() {
+        let legacy_game = ".C.ckGV90Fa+tHG/+T2==";
+        assert!(decompress(legacy_game).is_err());
+        let decompressed_game = decompress_with_options(legacy_game, &DecompressOptions {
+            alphabet_variant: AlphabetVariant::Standard,
+            ..DecompressOptions::default()
+        }).unwrap();
+        let (_, expected_moves) = decompress(".C.ckGV90Fa-tHG_-T2").unwrap();
+        assert_eq!(decompressed_game.moves.into_iter().map(Move::from).collect::<Vec<Move>>(), expected_moves.into_iter().map(Move::from).collect::<Vec<Move>>());
+    }
+
+    #[test]
+    fn test_base32_is_case_insensitive() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3", ",").unwrap();
+        let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+            alphabet: Alphabet::Base32,
+            ..CompressOptions::default()
+        }).unwrap();
+        let (_, moves_data) = decompress(&encoded_game.to_lowercase()).unwrap();
+        assert_eq!(moves_data.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves);
+    }
+
+    #[rstest(
+        alphabet, wrong_index,
+        case(Alphabet::UrlSafeBase64, 0),
+        case(Alphabet::UrlSafeBase64, 12),
+        case(Alphabet::QrAlphanumeric, 5),
+        case(Alphabet::Base32, 6),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_error_correction_fixes_a_wrong_char(alphabet: Alphabet, wrong_index: usize) {
+        let given_moves: Vec<Move> = parse_to_vec("d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1", ",").unwrap();
+        let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+            alphabet,
+            error_correction: true,
+            ..CompressOptions::default()
+        }).unwrap();
+        let (header, payload) = encoded_game.split_at(encoded_game.find("E.").unwrap() + 2);
+        let damaged_payload: String = payload.chars().enumerate().map(|(i, c)| {
+            if i != wrong_index { c } else if c == '2' { '3' } else { '2' }
+        }).collect();
+
+        let decompressed_game = decompress_with_options(&format!("{header}{damaged_payload}"), &DecompressOptions::default()).unwrap();
+        assert_eq!(decompressed_game.moves.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves);
+        assert_eq!(decompressed_game.warnings.len(), 1);
+        assert!(matches!(decompressed_game.warnings[0], DecodeWarning::CorrectedChar { .. }));
+    }
+
+    #[test]
+    fn test_error_correction_keeps_undamaged_games_warning_free() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3", ",").unwrap();
+        let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+            error_correction: true,
+            ..CompressOptions::default()
+        }).unwrap();
+        assert!(encoded_game.starts_with(".E."));
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        assert_eq!(decompressed_game.moves.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves);
+        assert!(decompressed_game.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_checksum_distinguishes_altered_games_from_illegal_games() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3", ",").unwrap();
+        let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+            checksum: true,
+            ..CompressOptions::default()
+        }).unwrap();
+        let (_, moves_data) = decompress(&encoded_game).unwrap();
+        assert_eq!(moves_data.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves);
+
+        let payload_start = encoded_game.len() - 5;
+        for replacement in ["A", "_", "h"] {
+            let mut altered_game = encoded_game.clone();
+            altered_game.replace_range(payload_start..payload_start + 1, replacement);
+            if altered_game == encoded_game {
+                continue;
+            }
+            let Err(error) = decompress(&altered_game) else {
+                panic!("{altered_game} should have been rejected");
+            };
+            assert!(matches!(error.kind, ErrorKind::ChecksumMismatch), "{altered_game}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_scrambled_games_need_the_key() {
+        let given_moves: Vec<Move> = parse_to_vec("d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1", ",").unwrap();
+        let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+            checksum: true,
+            scramble_key: Some("secret".to_string()),
+            ..CompressOptions::default()
+        }).unwrap();
+        assert!(!encoded_game.contains("Tu"));
+
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions {
+            scramble_key: Some("secret".to_string()),
+            ..DecompressOptions::default()
+        }).unwrap();
+        assert_eq!(decompressed_game.moves.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves);
+
+        let Err(error) = decompress(&encoded_game) else {
+            panic!("decompress should fail without key");
+        };
+        assert!(matches!(error.kind, ErrorKind::IllegalConfig));
+        let Err(error) = decompress_with_options(&encoded_game, &DecompressOptions {
+            scramble_key: Some("wrong key".to_string()),
+            ..DecompressOptions::default()
+        }) else {
+            panic!("decompress should fail with the wrong key");
+        };
+        assert!(matches!(error.kind, ErrorKind::ChecksumMismatch));
+    }
+
+    #[test]
+    fn test_assume_queen_promotion() {
+        let moves_without_promotion_type: Vec<Move> = parse_to_vec("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4, g2g3, h4g3, c7d8", ",").unwrap();
+        let Err(error) = compress(moves_without_promotion_type.clone()) else {
+            panic!("compress should fail without promotion type");
+        };
+        assert!(matches!(error.kind, ErrorKind::IllegalMoveInGame { half_move_index: 10, reason: IllegalMoveReason::MissingPromotionType, .. }));
+
+        let compressed_game = compress_with_warnings(moves_without_promotion_type, &CompressOptions {
+            assume_queen_promotion: true,
+            ..CompressOptions::default()
+        }).unwrap();
+        let moves_with_queen_promotion: Vec<Move> = parse_to_vec("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4, g2g3, h4g3, c7d8Q", ",").unwrap();
+        assert_eq!(compressed_game.encoded, compress(moves_with_queen_promotion).unwrap());
+        assert_eq!(compressed_game.warnings, vec![CompressWarning::QueenPromotionAssumed { half_move_index: 10 }]);
+    }
+
+    #[rstest(
+        decoded_moves, expected_error,
+        case("d2d4, e7e6, e2e4, f8b4, c2c3, b4c3, d4d5", "move 4. d4d5 .. is illegal since it leaves the own king in check"),
+        case("e2e4, d7d6, f1b5, e8d7", "move 2. .. e8d7 is illegal since it leaves the own king in check"),
+        case("e2e4, e7e5, e1e2, d8h4, e2e3, h4h3, e3f3", "move 4. e3f3 .. is illegal since it leaves the own king in check"),
+        case("e2e4, e7e5, g1g3", "move 2. g1g3 .. is illegal since no figure of the player to move can go there"),
+        case("e2e4, e7e5, g1f3, d8h4, f1c4, h4f2, e1h1", "move 4. e1h1 .. is illegal since castling isn't allowed (the castling right is lost, a figure is in the way or the king is in or passes through check)"),
+        case("e2e4, e7e5, d1h5, f7f6", "move 2. .. f7f6 is illegal since it leaves the own king in check"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_compress_validated_rejects_illegal_moves(decoded_moves: &str, expected_error: &str) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let Err(error) = compress_validated(given_moves.clone()) else {
+            panic!("{decoded_moves} should have been rejected");
+        };
+        let ErrorKind::IllegalMoveInGame { half_move_index, a_move, reason } = error.kind else {
+            panic!("{error} should point to the illegal move");
+        };
+        assert_eq!((half_move_index, a_move), (given_moves.len() - 1, *given_moves.last().unwrap()));
+        assert!(expected_error.ends_with(&format!("is illegal since {reason}")));
+        assert_eq!(error.msg, expected_error);
+    }
+
+    #[rstest(
+        decoded_moves, expected_error,
+        case("e2e4, a7a6, e4e5, h7h6, e5d6", "move 3. e5d6 .. is illegal since a pawn can only move diagonally onto an empty field when capturing en passant"),
+        case("e2e4, d7d5, e4e5, h7h6, e5d6", "move 3. e5d6 .. is illegal since a pawn can only move diagonally onto an empty field when capturing en passant"),
+        case("a2a3, e7e5, a3a4, e5e4, d2d3, e4f3", "move 3. .. e4f3 is illegal since a pawn can only move diagonally onto an empty field when capturing en passant"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_compress_rejects_diagonal_pawn_moves_that_arent_en_passant(decoded_moves: &str, expected_error: &str) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let Err(error) = compress(given_moves.clone()) else {
+            panic!("{decoded_moves} should have been rejected");
+        };
+        assert!(matches!(error.kind, ErrorKind::IllegalMoveInGame { reason: IllegalMoveReason::NoEnPassant, .. }));
+        assert_eq!(error.msg, expected_error);
+        let Err(error) = compress_validated(given_moves) else {
+            panic!("{decoded_moves} should have been rejected by compress_validated");
+        };
+        assert_eq!(error.msg, expected_error);
+        // the en-passant capture right after the double step is fine
+        assert!(compress(parse_to_vec("e2e4, a7a6, e4e5, d7d5, e5d6", ",").unwrap()).is_ok());
+    }
+
+    #[apply(compress_decompress_cases)]
+    fn test_compress_validated(decoded_moves: &str, encoded_moves_seperated_by_space: &str) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        assert_eq!(compress_validated(given_moves).unwrap(), remove_space(encoded_moves_seperated_by_space));
+    }
+
+    #[rstest(
+        decoded_moves, expected_result,
+        case("f2f3, e7e5, g2g4", GameResult::Undecided),
+        case("f2f3, e7e5, g2g4, d8h4", GameResult::BlackWins),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decompress_flags_the_result(decoded_moves: &str, expected_result: GameResult) {
+        let encoded_game = compress(parse_to_vec(decoded_moves, ",").unwrap()).unwrap();
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        assert_eq!(decompressed_game.result, expected_result);
+    }
+
+    #[rstest(
+        start_fen, decoded_moves, expected_dead_draw_at,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "e2e4, e7e5", None),
+        case("4k3/8/8/8/8/8/3r4/4K3 w - - 0 1", "e1d2, e8e7, d2d3", Some(1)),
+        case("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", "e1e2", Some(0)),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decompress_finds_the_dead_draw(start_fen: &str, decoded_moves: &str, expected_dead_draw_at: Option<usize>) {
+        // the stored result comes first in infer_result, the dead draw has to be found nonetheless
+        let extensions = Extensions { metadata: Metadata { result: Some(GameResult::WhiteWins), ..Metadata::default() }, ..Extensions::default() };
+        let start = GameState::from_fen(start_fen).unwrap();
+        let encoded_game = compress_from_with_options(start, parse_to_vec(decoded_moves, ",").unwrap(), &CompressOptions { extensions, ..CompressOptions::default() }).unwrap().encoded;
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        assert_eq!(decompressed_game.dead_draw_at, expected_dead_draw_at);
+    }
+
+    #[rstest(
+        start_fen, decoded_moves, expected_start_fen, expected_warnings,
+        case("4k3/8/8/8/8/8/8/4K2R w K - 0 1", "h1h2", "4k3/8/8/8/8/8/8/4K2R w K - 0 1", vec![]),
+        case("4k3/8/8/8/4P3/8/8/4K3 b - e3 7 12", "e8e7", "4k3/8/8/8/4P3/8/8/4K3 b - e3 0 12", vec![CompressWarning::HalfmoveClockReset { halfmove_clock: 7 }]),
+        case("4k3/8/8/8/8/8/8/4K2R w K - 80 1", "h1h2", "4k3/8/8/8/8/8/8/4K2R w K - 80 41", vec![CompressWarning::FullmoveNumberRaised { fullmove_number: 1, raised_to: 41 }]),
+        case("4k3/8/8/8/8/8/8/4K2R b K - 81 3", "e8e7", "4k3/8/8/8/8/8/8/4K2R b K - 81 41", vec![CompressWarning::FullmoveNumberRaised { fullmove_number: 3, raised_to: 41 }]),
+        case("4k3/8/8/8/8/8/8/4K2R w K - 99 60", "h1h2, e8e7, h2h3", "4k3/8/8/8/8/8/8/4K2R w K - 99 60", vec![CompressWarning::FiftyMoveRuleReached { position_index: 1 }]),
+        case("4k3/8/8/8/8/8/8/4K2R w K - 150 80", "h1h2, e8e7", "4k3/8/8/8/8/8/8/4K2R w K - 150 80", vec![
+            CompressWarning::FiftyMoveRuleReached { position_index: 0 },
+            CompressWarning::PlayedAfterSeventyFiveMoveRule { half_move_index: 0 },
+        ]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_compress_from_fen_corrects_the_move_counters(start_fen: &str, decoded_moves: &str, expected_start_fen: &str, expected_warnings: Vec<CompressWarning>) {
+        let start = GameState::from_fen(start_fen).unwrap();
+        let compressed_game = compress_from_with_warnings(start, parse_to_vec(decoded_moves, ",").unwrap(), &CompressOptions::default()).unwrap();
+        assert_eq!(compressed_game.warnings, expected_warnings);
+        let (positions, _) = decompress(&compressed_game.encoded).unwrap();
+        assert_eq!(positions[0].fen, expected_start_fen);
+    }
+
+    #[rstest(
+        max_half_moves, max_input_len, is_within_limits,
+        case(None, None, true),
+        case(Some(11), Some(13), true),
+        case(Some(10), None, false),
+        case(None, Some(12), false),
+        case(Some(0), None, false),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decompress_limits(max_half_moves: Option<usize>, max_input_len: Option<usize>, is_within_limits: bool) {
+        // 11 half moves encoded in 13 chars
+        let encoded_game = "Y3vghpnyfWW7Q";
+        let result = decompress_with_options(encoded_game, &DecompressOptions {
+            max_half_moves,
+            max_input_len,
+            ..DecompressOptions::default()
+        });
+        match result {
+            Ok(decompressed_game) => {
+                assert!(is_within_limits);
+                assert_eq!(decompressed_game.moves.len(), 11);
+            }
+            Err(error) => {
+                assert!(!is_within_limits, "unexpected error: {error}");
+                assert!(matches!(error.kind, ErrorKind::LimitExceeded));
+            }
+        }
+    }
+
+    #[rstest(
+        decoded_moves, expected_half_moves, expected_warning,
+        case("g1f3, g8f6, f3g1, f6g8, g1f3, g8f6, f3g1, f6g8", 8, None),
+        case("g1f3, g8f6, f3g1, f6g8, g1f3, g8f6, f3g1, f6g8, e2e4", 8, Some(DrawReason::ThreefoldRepetition)),
+        case("g1f3, g8f6, f3g1, f6g8, e2e4, e7e5", 6, None),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_stop_at_draw(decoded_moves: &str, expected_half_moves: usize, expected_warning: Option<DrawReason>) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let encoded_game = compress(given_moves).unwrap();
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions {
+            stop_at_draw: true,
+            ..DecompressOptions::default()
+        }).unwrap();
+        assert_eq!(decompressed_game.moves.len(), expected_half_moves);
+        assert_eq!(decompressed_game.positions.len(), expected_half_moves + 1);
+        let actual_warning = decompressed_game.warnings.iter().find_map(|warning| match warning {
+            DecodeWarning::StoppedAtDraw { reason, half_moves_decoded, .. } => {
+                assert_eq!(*half_moves_decoded, expected_half_moves);
+                Some(*reason)
+            }
+            _ => None,
+        });
+        assert_eq!(actual_warning, expected_warning);
+    }
+
+    #[test]
+    fn test_stop_at_draw_by_fifty_move_rule() {
+        // 101 knight moves, no position occurs three times
+        let encoded_game = "BS5qjq5p5qpaq5k5quq5uf5qlq5v5qveq5eU5qGXq5l5qvq5ve5qkq5u5qfq5fV5qVmq5s5qsdq5n5qnWq5Wc5qiq5o5q5bqbs5dqdu5fqfl5lvqe5etqtn5Wqc5cmq-v5msqd5duqf5flqlr5cqct5teq";
+        assert_eq!(decompress(encoded_game).unwrap().1.len(), 101);
+        let decompressed_game = decompress_with_options(encoded_game, &DecompressOptions {
+            stop_at_draw: true,
+            ..DecompressOptions::default()
+        }).unwrap();
+        assert_eq!(decompressed_game.moves.len(), 100);
+        assert_eq!(decompressed_game.warnings, vec![DecodeWarning::StoppedAtDraw {
+            reason: DrawReason::FiftyMoveRule,
+            half_moves_decoded: 100,
+            ignored_chars: 1,
+        }]);
+    }
+
+    #[test]
+    fn test_zobrist_keys() {
+        // 1.e4 d5 2.e5 f5 (Polyglot reference values)
+        let encoded_game = compress(parse_to_vec("e2e4, d7d5, e4e5, f7f5", ",").unwrap()).unwrap();
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions {
+            zobrist_keys: true,
+            ..DecompressOptions::default()
+        }).unwrap();
+        let zobrist_keys: Vec<Option<u64>> = decompressed_game.positions.iter().map(|position| position.zobrist_key).collect();
+        assert_eq!(zobrist_keys, vec![
+            Some(0x463b96181691fc9c),
+            Some(0x823c9b50fd114196),
+            Some(0x0756b94461c50fb0),
+            Some(0x662fafb965db29d4),
+            Some(0x22a48b5a8e47ff78),
+        ]);
+        let (positions, _) = decompress(&encoded_game).unwrap();
+        assert!(positions.iter().all(|position| position.zobrist_key.is_none()));
+    }
+
+    #[rstest(
+        decoded_moves, expected_sans,
+        case("", vec![]),
+        case("e2e4, e7e5, g1f3, b8c6, f1c4, g8f6, e1h1", vec!["e4", "e5", "Nf3", "Nc6", "Bc4", "Nf6", "O-O"]),
+        case("e2e4, a7a6, e4e5, d7d5, e5d6, c7d6, d2d4, a6a5, g1f3, a5a4, b1d2", vec!["e4", "a6", "e5", "d5", "exd6", "cxd6", "d4", "a5", "Nf3", "a4", "Nbd2"]),
+        case("f2f3, e7e5, g2g4, d8h4", vec!["f3", "e5", "g4", "Qh4#"]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_san_of_positions(decoded_moves: &str, expected_sans: Vec<&str>) {
+        let encoded_game = compress(parse_to_vec(decoded_moves, ",").unwrap()).unwrap();
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions {
+            san: true,
+            ..DecompressOptions::default()
+        }).unwrap();
+        assert_eq!(decompressed_game.positions[0].san, None);
+        let sans: Vec<String> = decompressed_game.positions.into_iter().skip(1).map(|position| position.san.unwrap()).collect();
+        assert_eq!(sans, expected_sans);
+        let (positions, _) = decompress(&encoded_game).unwrap();
+        assert!(positions.iter().all(|position| position.san.is_none()));
+    }
+
+    #[rstest(
+        decoded_moves, expected_last_move,
+        case("", None),
+        case("e2e4, e7e5, g1f3", Some(MoveSquares { from: "g1".parse().unwrap(), to: "f3".parse().unwrap(), rook_move: None, captured_pawn_pos: None })),
+        case("e2e4, a7a6, e4e5, d7d5, e5d6", Some(MoveSquares { from: "e5".parse().unwrap(), to: "d6".parse().unwrap(), rook_move: None, captured_pawn_pos: Some("d5".parse().unwrap()) })),
+        case("g1f3, g8f6, g2g3, g7g6, f1g2, f8g7, e1h1", Some(MoveSquares { from: "e1".parse().unwrap(), to: "g1".parse().unwrap(), rook_move: Some("h1f1".parse().unwrap()), captured_pawn_pos: None })),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_last_move_of_positions(decoded_moves: &str, expected_last_move: Option<MoveSquares>) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let (positions, moves) = decompress(&compress(given_moves).unwrap()).unwrap();
+        assert_eq!(positions[0].last_move, None);
+        assert_eq!(positions.last().unwrap().last_move, expected_last_move);
+        for (position, move_data) in positions.iter().skip(1).zip(&moves) {
+            assert_eq!(position.last_move.map(|squares| squares.to), Some(move_data.squares().to));
+        }
+    }
+
+    #[rstest(
+        games, expected_encoded_games,
+        case(vec![""], ""),
+        case(vec!["e2e4, e7e5, g1f3", ""], "ckGV~"),
+        case(vec!["e2e4, e7e5, g1f3", "c2c4", "d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1"], "ckGV~a~Tu CU2BS-tDL8_EA"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_compress_games(games: Vec<&str>, expected_encoded_games: &str) {
+        let given_games: Vec<Vec<Move>> = games.iter().map(|moves| parse_to_vec(moves, ",").unwrap()).collect();
+        let encoded_games = compress_games(given_games.clone()).unwrap();
+        assert_eq!(encoded_games, remove_space(expected_encoded_games));
+        let decoded_games: Vec<Vec<Move>> = decompress_games(&encoded_games).unwrap().into_iter()
+            .map(|game| game.moves.into_iter().map(Move::from).collect::<Vec<Move>>())
+            .collect();
+        assert_eq!(decoded_games, given_games);
+    }
+
+    #[test]
+    fn test_decompress_games_with_headers_and_errors() {
+        let decoded_games = decompress_games(".C.ckGVE4~.Q.E90PBPL88").unwrap();
+        assert_eq!(decoded_games.iter().map(|game| game.moves.len()).collect::<Vec<usize>>(), vec![3, 5]);
+        let Err(error) = decompress_games("ckGV~ckG") else {
+            panic!("the second game is incomplete");
+        };
+        assert!(error.msg.starts_with("game 2: "), "{error}");
+        assert!(matches!(compress_games(vec![]).unwrap_err().kind, ErrorKind::IllegalConfig));
+    }
+
+    // found by fuzzing (see the fuzz directory), these used to panic
+    #[rstest(
+        encoded_game,
+        case(".R.ckGV.78p3PGdJtghu_bL65ui1ts58_veE"),
+        case(".F.8-8-7q-8-4K3-8-k7-8_b_-_-_65535_4294967295.Tg6tKljFsrjGfFo"),
+        case(".2.F.4K3-8-8-8-8-8-8-k7_w_-_-_0_4294967295.A"),
+        case(".F.99999999999999999-8-8-8-8-8-8-8_w_-_-_0_1."),
+        case(".F.9999999999999999999999999999k92-8-8-8-8-8-8-4K3_w_-_-_0_1.ckGV"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decompress_rejects_malformed_games(encoded_game: &str) {
+        assert!(decompress(encoded_game).is_err());
+        assert!(decompress_lossy(encoded_game).is_err());
+    }
+
+    // the two-char form names the origin, these name figures that can't make the move
+    #[rstest(
+        encoded_game, expected_half_move_index, expected_reason,
+        case("Pe", 0, IllegalMoveReason::NoEnPassant), // h2g4
+        case("IZ", 0, IllegalMoveReason::NoEnPassant), // a2b4
+        case("ckGV5qFh_g", 5, IllegalMoveReason::UnreachableTarget), // h8a5
+        case("EH", 0, IllegalMoveReason::CastlingNotAllowed), // e1h1 with bishop and knight in between
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decompress_rejects_moves_the_named_figure_can_not_make(encoded_game: &str, expected_half_move_index: usize, expected_reason: IllegalMoveReason) {
+        let Err(error) = decompress(encoded_game) else {
+            panic!("{encoded_game} should have been rejected");
+        };
+        let ErrorKind::IllegalMoveInGame { half_move_index, reason, .. } = error.kind else {
+            panic!("{error} should point to the illegal move");
+        };
+        assert_eq!((half_move_index, reason), (expected_half_move_index, expected_reason));
+        assert!(summary::preview(encoded_game).is_err());
+        assert!(summary::summarize(encoded_game).is_err());
+    }
+
+    #[test]
+    fn test_compress_rejects_castlings_that_are_not_allowed() {
+        // the king castles out of check
+        let Err(error) = compress(parse_to_vec("e2e4, e7e5, g1f3, d8h4, f1c4, h4f2, e1h1", ",").unwrap()) else {
+            panic!("the castling should have been rejected");
+        };
+        assert!(matches!(error.kind, ErrorKind::IllegalMoveInGame { half_move_index: 6, reason: IllegalMoveReason::CastlingNotAllowed, .. }));
+    }
+
+    #[rstest(
+        encoded_game, expected_half_moves, expected_failure_at,
+        case("ckGV", 3, None),
+        case("ckG", 2, Some(2)),
+        case("ckGVx", 3, Some(4)),
+        case("ckGV5qA", 4, Some(6)),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_decompress_lossy(encoded_game: &str, expected_half_moves: usize, expected_failure_at: Option<usize>) {
+        let (game, failure) = decompress_lossy(encoded_game).unwrap();
+        assert_eq!(game.moves.len(), expected_half_moves);
+        assert_eq!(game.positions.len(), expected_half_moves + 1);
+        assert_eq!(failure.as_ref().map(|failure| failure.char_index), expected_failure_at);
+        if let Some(failure) = failure {
+            assert_eq!(failure.half_move_index, expected_half_moves);
+            assert!(decompress(encoded_game).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decompress_lossy_fails_if_nothing_can_be_decoded() {
+        assert!(decompress_lossy("ck!").is_err());
+        let Err(error) = decompress_lossy(".C.ckGV5qE4") else {
+            panic!("the checksum of the altered game shouldn't match");
+        };
+        assert!(matches!(error.kind, ErrorKind::ChecksumMismatch));
+    }
+
+    #[rstest(
+        start_ply, end_ply, expected_encoded_game,
+        case(0, 4, "ckGV5q"),
+        case(2, 4, ".F.rnbqkbnr-pppp1ppp-8-4p3-4P3-8-PPPP1PPP-RNBQKBNR_w_KQkq_e6_0_2.GV5q"),
+        case(4, 4, ".F.r1bqkbnr-pppp1ppp-2n5-4p3-4P3-5N2-PPPP1PPP-RNBQKB1R_w_KQkq_-_2_3."),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_compress_range(start_ply: usize, end_ply: usize, expected_encoded_game: &str) {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3, b8c6", ",").unwrap();
+        let encoded_game = compress_range(given_moves.clone(), start_ply, end_ply).unwrap();
+        assert_eq!(encoded_game, expected_encoded_game);
+
+        let (positions, moves) = decompress(&encoded_game).unwrap();
+        assert_eq!(moves.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves[start_ply..end_ply]);
+        let (all_positions, _) = decompress(&compress(given_moves).unwrap()).unwrap();
+        let to_fens = |positions: &[PositionData]| positions.iter().map(|position| position.fen.clone()).collect::<Vec<String>>();
+        assert_eq!(to_fens(&positions), to_fens(&all_positions[start_ply..=end_ply]));
+    }
+
+    #[test]
+    fn test_compress_from_custom_position() {
+        let start = GameState::from_fen("8/8/4k3/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e6d6, e1d2", ",").unwrap();
+        let encoded_game = compress_from(start.clone(), given_moves.clone()).unwrap();
+        let (positions, moves) = decompress_from(start, &encoded_game).unwrap();
+        assert_eq!(moves.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves);
+        assert_eq!(positions.last().unwrap().fen, "8/8/3k4/8/4P3/8/3K4/8 b - - 2 2");
+        // the moves don't fit the classic start position
+        assert!(decompress(&encoded_game).is_err());
+    }
+
+    #[test]
+    fn test_decompress_from_rejects_games_with_other_start_position() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3, b8c6", ",").unwrap();
+        let encoded_game = compress_range(given_moves, 2, 4).unwrap();
+        let start_of_range = GameState::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2").unwrap();
+        assert_eq!(decompress_from(start_of_range, &encoded_game).unwrap().1.len(), 2);
+        let Err(error) = decompress_from(GameState::classic(), &encoded_game) else {
+            panic!("decompress_from should fail for another start position");
+        };
+        assert!(matches!(error.kind, ErrorKind::IllegalConfig));
+    }
+
+    #[rstest(
+        start_ply, end_ply,
+        case(3, 2),
+        case(0, 5),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_compress_range_rejects_illegal_ranges(start_ply: usize, end_ply: usize) {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3, b8c6", ",").unwrap();
+        let error = compress_range(given_moves, start_ply, end_ply).unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::IllegalConfig));
+    }
+
+    #[rstest(
+        alphabet, expected_encoded_game,
+        case(Alphabet::UrlSafeBase64, ".R.ckGV.AAcCEGV"),
+        case(Alphabet::Base32, ".BR.OJAZK.AADQEEDFI"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_annotations_survive_the_round_trip(alphabet: Alphabet, expected_encoded_game: &str) {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3", ",").unwrap();
+        let highlight = Annotation::Highlight { square: "e4".parse().unwrap(), color: MarkupColor::Green };
+        let arrow = Annotation::Arrow { from_to: "g1f3".parse().unwrap(), color: MarkupColor::Green };
+        let mut extensions = Extensions::default();
+        extensions.annotations.insert(0, vec![highlight]);
+        extensions.annotations.insert(2, vec![arrow]);
+        let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+            alphabet,
+            extensions: extensions.clone(),
+            ..CompressOptions::default()
+        }).unwrap();
+        assert_eq!(encoded_game, expected_encoded_game);
+
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        assert_eq!(decompressed_game.moves.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves);
+        assert_eq!(decompressed_game.extensions, extensions);
+        let annotations: Vec<Vec<Annotation>> = decompressed_game.positions.into_iter().map(|position| position.annotations).collect();
+        assert_eq!(annotations, vec![vec![highlight], vec![], vec![arrow], vec![]]);
+    }
+
+    #[test]
+    fn test_comments_keep_their_author() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5", ",").unwrap();
+        let comments = vec![
+            Comment { author: Some(1), text: "the most popular move".to_string() },
+            Comment { author: Some(2), text: "I prefer d4".to_string() },
+            Comment { author: None, text: "both are fine".to_string() },
+        ];
+        let mut extensions = Extensions::default();
+        extensions.comments.insert(1, comments.clone());
+        let encoded_game = compress_with_options(given_moves, &CompressOptions {
+            alphabet: Alphabet::QrAlphanumeric,
+            extensions,
+            ..CompressOptions::default()
+        }).unwrap();
+
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        let position_comments: Vec<Vec<Comment>> = decompressed_game.positions.into_iter().map(|position| position.comments).collect();
+        assert_eq!(position_comments, vec![vec![], comments, vec![]]);
+    }
+
+    #[test]
+    fn test_nags_survive_the_round_trip() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, f2f4", ",").unwrap();
+        let mut extensions = Extensions::default();
+        extensions.nags.insert(3, vec![Nag::from_symbol("!?").unwrap()]);
+        extensions.comments.insert(3, vec![Comment { author: None, text: "the King's Gambit".to_string() }]);
+        let encoded_game = compress_with_options(given_moves, &CompressOptions {
+            extensions: extensions.clone(),
+            ..CompressOptions::default()
+        }).unwrap();
+
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        assert_eq!(decompressed_game.extensions, extensions);
+        let position_nags: Vec<String> = decompressed_game.positions.iter()
+            .map(|position| position.nags.iter().map(|nag| nag.to_string()).collect())
+            .collect();
+        assert_eq!(position_nags, vec!["", "", "", "!?"]);
+    }
+
+    #[rstest(
+        allow_spoilers, expected_half_moves, expected_warnings,
+        case(false, 2, vec![DecodeWarning::SpoilersHidden { hidden_half_moves: 2 }]),
+        case(true, 4, vec![]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_reveal_boundary(allow_spoilers: bool, expected_half_moves: usize, expected_warnings: Vec<DecodeWarning>) {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3, b8c6", ",").unwrap();
+        let mut extensions = Extensions { reveal_boundary: Some(2), ..Extensions::default() };
+        extensions.comments.insert(3, vec![Comment { author: None, text: "the solution".to_string() }]);
+        let encoded_game = compress_with_options(given_moves.clone(), &CompressOptions {
+            extensions,
+            ..CompressOptions::default()
+        }).unwrap();
+
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions {
+            allow_spoilers,
+            ..DecompressOptions::default()
+        }).unwrap();
+        assert_eq!(decompressed_game.moves.into_iter().map(Move::from).collect::<Vec<Move>>(), given_moves[..expected_half_moves]);
+        assert_eq!(decompressed_game.positions.len(), expected_half_moves + 1);
+        assert_eq!(decompressed_game.extensions.comments.is_empty(), !allow_spoilers);
+        assert_eq!(decompressed_game.warnings, expected_warnings);
+        if !allow_spoilers {
+            assert_eq!(final_fen(&encoded_game).unwrap(), decompressed_game.positions.last().unwrap().fen);
+        }
+    }
+
+    #[test]
+    fn test_annotations_have_to_belong_to_a_position_of_the_game() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5", ",").unwrap();
+        let mut extensions = Extensions::default();
+        extensions.annotations.insert(3, vec![Annotation::Highlight { square: "e4".parse().unwrap(), color: MarkupColor::Red }]);
+        let error = compress_with_options(given_moves, &CompressOptions {
+            extensions,
+            ..CompressOptions::default()
+        }).unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::IllegalConfig));
+        assert!(decompress(".R.ck.DBc").is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[apply(compress_decompress_cases)]
+    fn test_parallel_fen_matches_sequential_fen(_decoded_moves: &str, encoded_moves_seperated_by_space: &str) {
+        let encoded_game = encoded_moves_seperated_by_space.replace(' ', "");
+        let sequential_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        let parallel_game = decompress_with_options(&encoded_game, &DecompressOptions {
+            parallel_fen: true,
+            ..DecompressOptions::default()
+        }).unwrap();
+        let to_fens = |positions: Vec<PositionData>| positions.into_iter().map(|position| position.fen).collect::<Vec<String>>();
+        assert_eq!(to_fens(parallel_game.positions), to_fens(sequential_game.positions));
+    }
+}
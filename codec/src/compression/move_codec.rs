@@ -0,0 +1,126 @@
+use std::str::Chars;
+use crate::base::a_move::{Move, MoveData};
+use crate::base::errors::ChessError;
+use crate::compression::compress::{compress_payload, encode_next_move};
+use crate::compression::decompress::{decode_game, decode_next_move, DecodedMove, DecompressOptions, PositionData};
+use crate::compression::version::{decode_next_move_v2, encode_next_move_v2};
+use crate::game::game_state::GameState;
+
+/// encodes single moves, the game-logic plumbing (playing the moves, computing positions, ..) is done by the caller.
+///
+/// Implementing this trait is enough to experiment with a custom move encoding (see [compress_with_codec]
+/// and [decompress_with_codec]). The encoding has to be self-delimiting, since the moves aren't separated.
+pub trait MoveCodec {
+    /// returns the chars that encode the (legal) move played in the given state
+    fn encode_move(&self, next_move: Move, game_state: &GameState, half_move_index: usize) -> Result<String, ChessError>;
+
+    /// decodes the move starting at the next char, only consumes the chars of that move.
+    /// Is only called if there is at least one char left.
+    fn decode_move(&self, encoded_chars: &mut Chars, game_state: &GameState, half_move_index: usize) -> Result<DecodedMove, ChessError>;
+}
+
+/// the encoding of [FormatVersion::V1](crate::compression::version::FormatVersion::V1):
+/// a move is its to-position (if no other figure can reach it) or its from- and to-position,
+/// plus the promotion type char for pawn promotions
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CompactTargetCodec;
+
+impl MoveCodec for CompactTargetCodec {
+    fn encode_move(&self, next_move: Move, game_state: &GameState, half_move_index: usize) -> Result<String, ChessError> {
+        encode_next_move(next_move, game_state, half_move_index)
+    }
+
+    fn decode_move(&self, encoded_chars: &mut Chars, game_state: &GameState, half_move_index: usize) -> Result<DecodedMove, ChessError> {
+        decode_next_move(encoded_chars, game_state, half_move_index)
+    }
+}
+
+/// the encoding of [FormatVersion::V2](crate::compression::version::FormatVersion::V2):
+/// a move is its index in [GameState::ordered_legal_moves]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct LegalMoveIndexCodec;
+
+impl MoveCodec for LegalMoveIndexCodec {
+    fn encode_move(&self, next_move: Move, game_state: &GameState, half_move_index: usize) -> Result<String, ChessError> {
+        encode_next_move_v2(next_move, game_state, half_move_index)
+    }
+
+    fn decode_move(&self, encoded_chars: &mut Chars, game_state: &GameState, half_move_index: usize) -> Result<DecodedMove, ChessError> {
+        decode_next_move_v2(encoded_chars, game_state, half_move_index)
+    }
+}
+
+/// compresses a game from the classic start position with the given codec.
+/// The result is just the encoded moves (no format header), so it can only be decoded with the same codec.
+pub fn compress_with_codec(moves: Vec<Move>, codec: &impl MoveCodec) -> Result<String, ChessError> {
+    compress_payload(GameState::classic(), moves, codec, None)
+}
+
+/// decompresses a game that was compressed by [compress_with_codec] with the same codec
+pub fn decompress_with_codec(encoded_moves: &str, codec: &impl MoveCodec) -> Result<(Vec<PositionData>, Vec<MoveData>), ChessError> {
+    let game = decode_game(GameState::classic(), encoded_moves, codec, Default::default(), Vec::new(), &DecompressOptions::default())?;
+    Ok((game.positions, game.moves))
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::a_move::FromTo;
+    use crate::base::errors::ErrorKind;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::base64::{decode_base64, encode_base64};
+    use crate::compression::compress::compress;
+    use super::*;
+
+    /// encodes every move as its from- and to-position
+    struct FromToCodec;
+
+    impl MoveCodec for FromToCodec {
+        fn encode_move(&self, next_move: Move, _game_state: &GameState, _half_move_index: usize) -> Result<String, ChessError> {
+            Ok(format!("{}{}", encode_base64(next_move.from_to.from), encode_base64(next_move.from_to.to)))
+        }
+
+        fn decode_move(&self, encoded_chars: &mut Chars, _game_state: &GameState, _half_move_index: usize) -> Result<DecodedMove, ChessError> {
+            let from = decode_base64(encoded_chars.next().expect("at least one char should be left"))?;
+            let Some(to_char) = encoded_chars.next() else {
+                return Ok(DecodedMove::Incomplete(ChessError::new(ErrorKind::IllegalFormat, "the to-position is missing".to_string())));
+            };
+            Ok(DecodedMove::Complete(Move::new(FromTo::new(from, decode_base64(to_char)?))))
+        }
+    }
+
+    #[rstest(
+        decoded_moves,
+        case(""),
+        case("e2e4, e7e5, g1f3"),
+        case("d2d3, g7g6, c1e3, f8g7, b1c3, g8f6, d1d2, e8h8, e1a1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_codecs_round_trip(decoded_moves: &str) {
+        let given_moves: Vec<Move> = parse_to_vec(decoded_moves, ",").unwrap();
+        let codecs: [&dyn MoveCodec; 3] = [&CompactTargetCodec, &LegalMoveIndexCodec, &FromToCodec];
+        for codec in codecs {
+            let encoded_game = compress_payload(GameState::classic(), given_moves.clone(), codec, None).unwrap();
+            let game = decode_game(GameState::classic(), &encoded_game, codec, Default::default(), Vec::new(), &DecompressOptions::default()).unwrap();
+            let decoded: Vec<Move> = game.moves.iter().map(MoveData::given_move).collect();
+            assert_eq!(decoded, given_moves);
+        }
+    }
+
+    #[test]
+    fn test_custom_codec() {
+        let given_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3", ",").unwrap();
+        assert_eq!(compress_with_codec(given_moves.clone(), &CompactTargetCodec).unwrap(), compress(given_moves.clone()).unwrap());
+        let encoded_game = compress_with_codec(given_moves.clone(), &FromToCodec).unwrap();
+        assert_eq!(encoded_game, "Mc0kGV");
+        let (positions, moves) = decompress_with_codec(&encoded_game, &FromToCodec).unwrap();
+        assert_eq!(positions.len(), 4);
+        assert_eq!(moves.iter().map(MoveData::given_move).collect::<Vec<Move>>(), given_moves);
+        let Err(error) = decompress_with_codec("Mc0kG", &FromToCodec) else {
+            panic!("an incomplete move should be rejected");
+        };
+        assert!(matches!(error.kind, ErrorKind::IllegalFormat));
+    }
+}
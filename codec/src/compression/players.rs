@@ -0,0 +1,107 @@
+use crate::compression::decompress::DecompressedGame;
+use crate::compression::summary::GameResult;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Players {
+    pub white: Player,
+    pub black: Player,
+    /// the result from the metadata of the game or (if it isn't given) as far as it can be told from the final position
+    pub result: GameResult,
+}
+
+/// a player as seen from the metadata of the game
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Player {
+    pub rating: Option<u16>,
+    pub opponent_rating: Option<u16>,
+    /// 1 for a win, 0.5 for a draw and 0 for a loss (None if the game is undecided)
+    pub score: Option<f64>,
+}
+
+impl Player {
+    /// the score the player was expected to make according to the Elo formula
+    pub fn expected_score(&self) -> Option<f64> {
+        let (rating, opponent_rating) = (self.rating?, self.opponent_rating?);
+        Some(1.0 / (1.0 + 10_f64.powf((opponent_rating as f64 - rating as f64) / 400.0)))
+    }
+
+    /// the linear performance rating of this one game (the opponent's rating +400 for a win, -400 for a loss)
+    pub fn performance_rating(&self) -> Option<i32> {
+        let (opponent_rating, score) = (self.opponent_rating?, self.score?);
+        Some(opponent_rating as i32 + (400.0 * (2.0 * score - 1.0)) as i32)
+    }
+}
+
+impl DecompressedGame {
+    pub fn players(&self) -> Players {
+        let metadata = &self.extensions.metadata;
+        let result = metadata.result.unwrap_or(self.result);
+        let (white_score, black_score) = match result {
+            GameResult::WhiteWins => (Some(1.0), Some(0.0)),
+            GameResult::BlackWins => (Some(0.0), Some(1.0)),
+            GameResult::Draw => (Some(0.5), Some(0.5)),
+            // GameResult is non_exhaustive, results other than the above (like Undecided) have no score
+            _ => (None, None),
+        };
+        Players {
+            white: Player { rating: metadata.white_rating, opponent_rating: metadata.black_rating, score: white_score },
+            black: Player { rating: metadata.black_rating, opponent_rating: metadata.white_rating, score: black_score },
+            result,
+        }
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+    use crate::base::a_move::Move;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::{compress_with_options, CompressOptions};
+    use crate::compression::decompress::{decompress_with_options, DecompressOptions};
+    use crate::compression::extension::{Extensions, Metadata};
+
+    fn decompressed_game(moves: &str, metadata: Metadata) -> DecompressedGame {
+        let given_moves: Vec<Move> = parse_to_vec(moves, ",").unwrap();
+        let extensions = Extensions { metadata, ..Extensions::default() };
+        let encoded_game = compress_with_options(given_moves, &CompressOptions { extensions, ..CompressOptions::default() }).unwrap();
+        decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap()
+    }
+
+    #[rstest(
+        white_rating, black_rating, result, expected_white_expected_score, expected_white_performance, expected_black_performance,
+        case(1500, 1500, GameResult::Draw, 0.5, 1500, 1500),
+        case(1600, 1200, GameResult::BlackWins, 0.909, 800, 2000),
+        case(2000, 2200, GameResult::WhiteWins, 0.240, 2600, 1600),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_players(
+        white_rating: u16,
+        black_rating: u16,
+        result: GameResult,
+        expected_white_expected_score: f64,
+        expected_white_performance: i32,
+        expected_black_performance: i32,
+    ) {
+        let metadata = Metadata { white_rating: Some(white_rating), black_rating: Some(black_rating), result: Some(result), ..Metadata::default() };
+        let players = decompressed_game("e2e4, e7e5", metadata).players();
+        assert_eq!(players.result, result);
+        let white_expected_score = players.white.expected_score().unwrap();
+        assert!((white_expected_score - expected_white_expected_score).abs() < 0.001, "expected score was {white_expected_score}");
+        assert!((white_expected_score + players.black.expected_score().unwrap() - 1.0).abs() < 0.001);
+        assert_eq!(players.white.performance_rating(), Some(expected_white_performance));
+        assert_eq!(players.black.performance_rating(), Some(expected_black_performance));
+    }
+
+    #[test]
+    fn test_players_without_metadata() {
+        // fool's mate
+        let players = decompressed_game("f2f3, e7e5, g2g4, d8h4", Metadata::default()).players();
+        assert_eq!(players.result, GameResult::BlackWins);
+        assert_eq!(players.black.score, Some(1.0));
+        assert_eq!(players.black.expected_score(), None);
+        assert_eq!(players.black.performance_rating(), None);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::compression::decompress::{decompress, decompress_with_options, DecodeWarning, DecompressOptions, DecompressedGame};
+
+/// shorter runs of token chars are ignored, since too many ordinary words would be valid games
+pub const MIN_TOKEN_LEN: usize = 6;
+
+/// dashes that word processors, chat clients and fonts put in place of '-'
+const SMART_DASHES: [char; 6] = ['‐', '‑', '‒', '–', '—', '−'];
+
+/// at most this many 'O' and '0' are swapped when repairing confusable chars,
+/// since the number of tries grows exponentially with it
+const MAX_CONFUSABLE_SWAPS: usize = 2;
+
+/// a (possibly mangled) encoded game found in a text
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TokenCandidate {
+    /// the byte offset of the candidate within the text
+    pub byte_offset: usize,
+    /// the candidate as it was found in the text
+    pub found: String,
+    /// the encoded game after the repairs were applied (the same as `found` if no repairs were needed)
+    pub token: String,
+    pub repairs: Vec<TokenRepair>,
+    pub half_moves: usize,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TokenRepair {
+    /// smart dashes (e.g. '–' or '—') were replaced by '-'
+    SmartDashesReplaced,
+    /// a '.' (e.g. the end of the sentence) was removed from the end
+    TrailingDotStripped,
+    /// an '_' was inserted at the given char index (markdown tends to swallow them)
+    UnderscoreInserted { index: usize },
+}
+
+/// finds the encoded games embedded in a text (e.g. chat logs or forum posts).
+///
+/// Every run of at least [MIN_TOKEN_LEN] chars that could be part of an encoded game is checked.
+/// If it doesn't decode as is, the usual mangling is undone (see [TokenRepair]).
+/// Runs that still don't decode aren't returned, the others are returned in the order they appear in the text.
+pub fn scan_text_for_tokens(text: &str) -> Vec<TokenCandidate> {
+    let mut candidates: Vec<TokenCandidate> = Vec::new();
+    let mut run_start: Option<usize> = None;
+    for (byte_index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (is_token_char(c), run_start) {
+            (true, None) => run_start = Some(byte_index),
+            (false, Some(start)) => {
+                let found = &text[start..byte_index];
+                if found.chars().count() >= MIN_TOKEN_LEN {
+                    candidates.extend(check_candidate(start, found));
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    candidates
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' || SMART_DASHES.contains(&c)
+}
+
+fn check_candidate(byte_offset: usize, found: &str) -> Option<TokenCandidate> {
+    let mut repairs: Vec<TokenRepair> = Vec::new();
+    let mut token = found.to_string();
+    if token.contains(SMART_DASHES) {
+        token = token.replace(SMART_DASHES, "-");
+        repairs.push(TokenRepair::SmartDashesReplaced);
+    }
+    if let Some(without_dot) = token.strip_suffix('.') {
+        // a format header starts with '.' and ends with '.', but there's always a game after it
+        if decompress(&token).is_err() {
+            token = without_dot.to_string();
+            repairs.push(TokenRepair::TrailingDotStripped);
+        }
+    }
+
+    let to_candidate = |token: String, repairs: Vec<TokenRepair>, half_moves: usize| TokenCandidate {
+        byte_offset,
+        found: found.to_string(),
+        token,
+        repairs,
+        half_moves,
+    };
+    if let Ok((_, moves)) = decompress(&token) {
+        return Some(to_candidate(token, repairs, moves.len()));
+    }
+    let chars: Vec<char> = token.chars().collect();
+    (0..=chars.len()).find_map(|index| {
+        let with_underscore: String = chars[..index].iter().chain(['_'].iter()).chain(chars[index..].iter()).collect();
+        let (_, moves) = decompress(&with_underscore).ok()?;
+        let mut repairs = repairs.clone();
+        repairs.push(TokenRepair::UnderscoreInserted { index });
+        Some(to_candidate(with_underscore, repairs, moves.len()))
+    })
+}
+
+/// the repair pass of [DecompressOptions::repair_confusables]: smart dashes are always replaced,
+/// 'O' and '0' are swapped in as few places as possible so that the game decodes (i.e. only contains legal moves).
+pub(crate) fn decompress_with_confusables_replaced(encoded_match: &str, options: &DecompressOptions) -> Option<DecompressedGame> {
+    let options = DecompressOptions { repair_confusables: false, ..options.clone() };
+    let mut chars: Vec<char> = encoded_match.chars().collect();
+    let mut dash_replacements: Vec<DecodeWarning> = Vec::new();
+    for (index, c) in chars.iter_mut().enumerate() {
+        if SMART_DASHES.contains(c) {
+            dash_replacements.push(DecodeWarning::ConfusableCharReplaced { index, found: *c, replaced_by: '-' });
+            *c = '-';
+        }
+    }
+    let swappable_indices: Vec<usize> = (0..chars.len()).filter(|&index| chars[index] == 'O' || chars[index] == '0').collect();
+    // the unchanged game was already tried if there were no smart dashes
+    let min_swap_count = if dash_replacements.is_empty() { 1 } else { 0 };
+    for swap_count in min_swap_count..=MAX_CONFUSABLE_SWAPS.min(swappable_indices.len()) {
+        for swapped in combinations(&swappable_indices, swap_count) {
+            let mut repaired_chars = chars.clone();
+            let mut replacements = dash_replacements.clone();
+            for index in swapped {
+                let replaced_by = if chars[index] == 'O' { '0' } else { 'O' };
+                repaired_chars[index] = replaced_by;
+                replacements.push(DecodeWarning::ConfusableCharReplaced { index, found: chars[index], replaced_by });
+            }
+            let repaired_match: String = repaired_chars.into_iter().collect();
+            if let Ok(mut game) = decompress_with_options(&repaired_match, &options) {
+                replacements.append(&mut game.warnings);
+                game.warnings = replacements;
+                return Some(game);
+            }
+        }
+    }
+    None
+}
+
+/// all subsets of `items` with `count` elements (in the order of `items`)
+fn combinations(items: &[usize], count: usize) -> Vec<Vec<usize>> {
+    if count == 0 {
+        return vec![Vec::new()];
+    }
+    (0..items.len()).flat_map(|first| {
+        combinations(&items[first + 1..], count - 1).into_iter().map(move |mut rest| {
+            rest.insert(0, items[first]);
+            rest
+        })
+    }).collect()
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        text, expected_tokens,
+        case("no games in here", vec![]),
+        case("look at ckGV5qh!", vec![("ckGV5qh", vec![])]),
+        case("https://example.org/game/ckGV5qh?ref=chat and ckGV5q", vec![("ckGV5qh", vec![]), ("ckGV5q", vec![])]),
+        case("my game: ckGV5qh.", vec![("ckGV5qh", vec![TokenRepair::TrailingDotStripped])]),
+        case("the game .C.ckGVE4 is checked", vec![(".C.ckGVE4", vec![])]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_scan_text_for_tokens(text: &str, expected_tokens: Vec<(&str, Vec<TokenRepair>)>) {
+        let candidates = scan_text_for_tokens(text);
+        let actual_tokens: Vec<(&str, Vec<TokenRepair>)> = candidates.iter()
+            .map(|candidate| (candidate.token.as_str(), candidate.repairs.clone()))
+            .collect();
+        assert_eq!(actual_tokens, expected_tokens);
+        for candidate in candidates {
+            assert_eq!(&text[candidate.byte_offset..candidate.byte_offset + candidate.found.len()], candidate.found);
+        }
+    }
+
+    #[test]
+    fn test_scan_text_for_tokens_repairs_mangled_chars() {
+        // 1.Nf3 Nf6 2.Ng1 Ng8 3.e4
+        let token = "GV-tVGt-c";
+        assert!(decompress(token).is_ok());
+
+        let with_smart_dash = token.replace('-', "–");
+        let candidates = scan_text_for_tokens(&format!("see {with_smart_dash} for details"));
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].byte_offset, 4);
+        assert_eq!(candidates[0].found, with_smart_dash);
+        assert_eq!(candidates[0].token, token);
+        assert_eq!(candidates[0].repairs, vec![TokenRepair::SmartDashesReplaced]);
+    }
+
+    #[test]
+    fn test_scan_text_for_tokens_reinserts_underscores() {
+        // 1.e4 Nf6 2.Nf3 Rg8, the rook move names its origin since the knight could go to g8 as well
+        let token = "c-tGV_-";
+        let Ok((_, moves)) = decompress(token) else {
+            panic!("{token} should be a valid game");
+        };
+        let candidates = scan_text_for_tokens(&token.replace('_', ""));
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].token, token);
+        assert_eq!(candidates[0].repairs, vec![TokenRepair::UnderscoreInserted { index: 5 }]);
+        assert_eq!(candidates[0].half_moves, moves.len());
+    }
+
+    #[rstest(
+        retyped_game, expected_game, expected_replacements,
+        case(".C.ckGV9OFa-tHG_-T2", ".C.ckGV90Fa-tHG_-T2", vec![(8, 'O', '0')]),
+        case("GV–tVGt—c", "GV-tVGt-c", vec![(2, '–', '-'), (7, '—', '-')]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_repair_confusables(retyped_game: &str, expected_game: &str, expected_replacements: Vec<(usize, char, char)>) {
+        assert!(decompress(retyped_game).is_err());
+        let options = DecompressOptions { repair_confusables: true, ..DecompressOptions::default() };
+        let repaired_game = decompress_with_options(retyped_game, &options).unwrap();
+        let (expected_positions, _) = decompress(expected_game).unwrap();
+        assert_eq!(repaired_game.positions.last().unwrap().fen, expected_positions.last().unwrap().fen);
+        let expected_warnings: Vec<DecodeWarning> = expected_replacements.into_iter()
+            .map(|(index, found, replaced_by)| DecodeWarning::ConfusableCharReplaced { index, found, replaced_by })
+            .collect();
+        assert_eq!(repaired_game.warnings, expected_warnings);
+    }
+
+    #[test]
+    fn test_repair_confusables_is_opt_in() {
+        let options = DecompressOptions { repair_confusables: false, ..DecompressOptions::default() };
+        assert!(decompress_with_options(".C.ckGV9OFa-tHG_-T2", &options).is_err());
+        let options = DecompressOptions { repair_confusables: true, ..DecompressOptions::default() };
+        assert!(decompress_with_options("not a game", &options).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::base::errors::ChessError;
+use crate::compression::base64::{decode_base64_index, encode_base64_index};
+use crate::compression::encoded_game::fnv1a_64;
+
+// Scrambling adds a key dependent pseudo-random value (mod 64) to every char of the url-safe base64 form.
+// This keeps games private from casual observers (the token looks like noise and can't be decoded
+// without the key) but is no encryption: someone who knows the game of one token can recover the
+// key stream for all tokens created with the same key.
+// Every char is scrambled independently, so error correction still works on scrambled values.
+
+const SCRAMBLE_SEED: u64 = 0x5c;
+
+/// scrambles a url-safe base64 value, the result is url-safe base64 of the same length
+pub fn scramble(base64_value: &str, key: &str) -> Result<String, ChessError> {
+    shift_by_key_stream(base64_value, key, |value, shift| value + shift)
+}
+
+/// undoes [scramble] given the same key
+pub fn unscramble(scrambled: &str, key: &str) -> Result<String, ChessError> {
+    shift_by_key_stream(scrambled, key, |value, shift| value + 64 - shift)
+}
+
+fn shift_by_key_stream(base64_value: &str, key: &str, shift: fn(usize, usize) -> usize) -> Result<String, ChessError> {
+    let mut key_stream = KeyStream::new(key);
+    base64_value.chars().map(|c| {
+        let value = decode_base64_index(c)?;
+        Ok(encode_base64_index(shift(value, key_stream.next_value()) % 64))
+    }).collect()
+}
+
+/// an endless sequence of 6bit values derived from the key (splitmix64)
+struct KeyStream {
+    state: u64,
+    block: u64,
+    values_left_in_block: u8,
+}
+
+impl KeyStream {
+    fn new(key: &str) -> KeyStream {
+        KeyStream {
+            state: fnv1a_64(SCRAMBLE_SEED, key.as_bytes()),
+            block: 0,
+            values_left_in_block: 0,
+        }
+    }
+
+    fn next_value(&mut self) -> usize {
+        if self.values_left_in_block == 0 {
+            self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = self.state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            self.block = z ^ (z >> 31);
+            self.values_left_in_block = 10;
+        }
+        let value = (self.block & 63) as usize;
+        self.block >>= 6;
+        self.values_left_in_block -= 1;
+        value
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::compression::base64::assert_is_url_safe_base64;
+    use super::*;
+
+    #[rstest(
+        value, key,
+        case("", "secret"),
+        case("ckGV5qh", "secret"),
+        case("ckGV5qh", ""),
+        case("T2uCU-tDL8_EAabcdefghijklmnopqrstuvwxyz0123456789", "a much longer key than the value itself"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_round_trip(value: &str, key: &str) {
+        let scrambled = scramble(value, key).unwrap();
+        assert_eq!(scrambled.len(), value.len());
+        assert_is_url_safe_base64(&scrambled).unwrap();
+        assert_eq!(unscramble(&scrambled, key).unwrap(), value);
+    }
+
+    #[test]
+    fn test_scrambling_depends_on_key() {
+        let value = "T2uCU-tDL8_EA";
+        let scrambled = scramble(value, "secret").unwrap();
+        assert_ne!(scrambled, value);
+        assert_ne!(scrambled, scramble(value, "Secret").unwrap());
+        assert_ne!(unscramble(&scrambled, "wrong key").unwrap(), value);
+    }
+}
@@ -0,0 +1,100 @@
+use std::panic;
+use crate::base::a_move::{Move, MoveData};
+use crate::compression::base64::{decode_base64_index, encode_base64_index};
+use crate::compression::compress::compress;
+use crate::compression::decompress::decompress;
+
+/// the game the self test is run on, it ends with a pawn promotion (to each promotion type in turn)
+const SAMPLE_GAME: &str = "a2a4 h7h6 a4a5 b7b5 a5b6 h6h5 b6c7 h5h4 g2g3 h4g3 c7d8";
+const PROMOTION_CHARS: [char; 4] = ['Q', 'R', 'N', 'B'];
+
+/// the outcome of [self_test]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SelfTestReport {
+    pub checks_run: usize,
+    /// a description of every check that failed
+    pub failures: Vec<String>,
+}
+
+impl SelfTestReport {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    fn check(&mut self, failure_description: String, check: impl FnOnce() -> bool + panic::UnwindSafe) {
+        self.checks_run += 1;
+        if !panic::catch_unwind(check).unwrap_or(false) {
+            self.failures.push(failure_description);
+        }
+    }
+}
+
+/// checks that en- and decoding works as expected in the current environment, e.g. at the startup of apps
+/// that run in environments with unusual string handling (embedded, JS bridges, ..).
+///
+/// Every base64 char is decoded (on its own and as a game), a game ending with each promotion type is
+/// round-tripped and decoding a game that is cut off at every offset must never panic or return foreign moves.
+/// Panics (if the environment lets them unwind) are reported as failures.
+pub fn self_test() -> SelfTestReport {
+    let mut report = SelfTestReport { checks_run: 0, failures: Vec::new() };
+
+    for index in 0..64 {
+        let base64_char = encode_base64_index(index);
+        report.check(
+            format!("base64 char '{base64_char}' doesn't decode to {index}"),
+            move || decode_base64_index(base64_char).is_ok_and(|decoded_index| decoded_index == index),
+        );
+        report.check(
+            format!("decoding the game '{base64_char}' panicked"),
+            move || { let _ = decompress(&base64_char.to_string()); true },
+        );
+    }
+
+    for promotion_char in PROMOTION_CHARS {
+        let sample_game = format!("{SAMPLE_GAME}{promotion_char}");
+        let Some((moves, encoded_game)) = panic::catch_unwind(|| {
+            let moves: Vec<Move> = sample_game.split(' ').map(str::parse).collect::<Result<Vec<Move>, _>>().ok()?;
+            let encoded_game = compress(moves.clone()).ok()?;
+            Some((moves, encoded_game))
+        }).ok().flatten() else {
+            report.checks_run += 1;
+            report.failures.push(format!("the sample game {sample_game} can't be compressed"));
+            continue;
+        };
+        let (given_moves, given_game) = (moves.clone(), encoded_game.clone());
+        report.check(
+            format!("the sample game {sample_game} doesn't survive the round trip"),
+            move || decoded_moves(&given_game).is_some_and(|decoded_moves| decoded_moves == given_moves),
+        );
+        for offset in 0..encoded_game.len() {
+            let (played_moves, truncated_game) = (moves.clone(), encoded_game[..offset].to_string());
+            report.check(
+                format!("decoding the truncated game '{truncated_game}' panicked or returned moves that weren't played"),
+                move || match decompress(&truncated_game) {
+                    Ok((_, moves_data)) => played_moves.starts_with(&moves_data.iter().map(MoveData::given_move).collect::<Vec<Move>>()),
+                    Err(_) => true,
+                },
+            );
+        }
+    }
+    report
+}
+
+fn decoded_moves(encoded_game: &str) -> Option<Vec<Move>> {
+    let (_, moves_data) = decompress(encoded_game).ok()?;
+    Some(moves_data.iter().map(MoveData::given_move).collect())
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test() {
+        let report = self_test();
+        assert!(report.is_ok(), "{:?}", report.failures);
+        assert_eq!(report.checks_run, 2 * 64 + 4 * (1 + "Y3vghpnyfWW7Q".len()));
+    }
+}
@@ -0,0 +1,83 @@
+//! the tables of the encoding as data, so implementations in other languages can generate theirs from this crate
+//! instead of copying them by hand.
+
+use crate::base::a_move::PromotionType;
+use crate::compression::alphabet::Alphabet;
+use crate::compression::base64::URL_SAFE_BASE64_CHARS;
+use crate::compression::header::{BASE32_TAG, CHECKSUM_TAG, ERROR_CORRECTION_TAG, QR_ALPHANUMERIC_TAG, RICH_TAG, SCRAMBLED_TAG, START_FEN_TAG, START_POSITION_TAG, V2_TAG};
+use crate::compression::version::FormatVersion;
+
+/// the url-safe base64 chars (index == value). A position is written as the char
+/// with the index `row * 8 + column`, so a1 is 'A', h1 is 'H' and h8 is '_'.
+pub fn alphabet() -> &'static [char; 64] {
+    &URL_SAFE_BASE64_CHARS
+}
+
+/// the chars of the 5bit alphabets (index == value), None for url-safe base64.
+/// Those alphabets regroup the bits of the url-safe base64 form (see [to_alphabet](crate::alphabet::to_alphabet)).
+pub fn five_bit_alphabet(alphabet: Alphabet) -> Option<&'static [char; 32]> {
+    alphabet.base32_chars()
+}
+
+/// the char that follows the to-position of a pawn promotion (in [FormatVersion::V1])
+pub fn promotion_chars() -> [(PromotionType, char); 4] {
+    [PromotionType::Rook, PromotionType::Knight, PromotionType::Bishop, PromotionType::Queen]
+        .map(|promotion_type| (promotion_type, promotion_type.as_encoded()))
+}
+
+/// the format header tag of every format version, None for the version of games without that tag
+pub fn version_markers() -> [(FormatVersion, Option<char>); 2] {
+    [
+        (FormatVersion::V1, None),
+        (FormatVersion::V2, Some(V2_TAG)),
+    ]
+}
+
+/// all tags of the format header in the order they are written in
+/// (see [FormatHeader](crate::header::FormatHeader)) with what they stand for
+pub fn header_tags() -> [(char, &'static str); 9] {
+    [
+        (QR_ALPHANUMERIC_TAG, "the game is written in the QR alphanumeric alphabet"),
+        (BASE32_TAG, "the game is written in the base32 alphabet"),
+        (CHECKSUM_TAG, "a checksum char follows the moves"),
+        (ERROR_CORRECTION_TAG, "error correction chars follow the moves"),
+        (SCRAMBLED_TAG, "the game is scrambled with a key"),
+        (V2_TAG, "the moves are written in format version 2"),
+        (RICH_TAG, "extension records follow the moves"),
+        (START_POSITION_TAG, "the id of a registered start position follows the header"),
+        (START_FEN_TAG, "the url-safe FEN of the start position follows the header"),
+    ]
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::base::a_move::Move;
+    use crate::base::position::Position;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::base64::encode_base64;
+    use crate::compression::compress::compress;
+    use crate::compression::version::compress_v2;
+    use super::*;
+
+    #[test]
+    fn test_spec_tables_match_the_encoding() {
+        assert_eq!(alphabet()[0], 'A');
+        assert_eq!(alphabet()[63], '_');
+        assert!((0..64i8).all(|index| alphabet()[index as usize] == encode_base64(Position::new_unchecked(index % 8, index / 8))));
+        assert_eq!(five_bit_alphabet(Alphabet::UrlSafeBase64), None);
+        assert_eq!(five_bit_alphabet(Alphabet::Base32).map(|chars| chars[31]), Some('7'));
+
+        let promotion_moves: Vec<Move> = parse_to_vec("a2a4, h7h6, a4a5, b7b5, a5b6, h6h5, b6c7, h5h4, g2g3, h4g3", ",").unwrap();
+        for (promotion_type, promotion_char) in promotion_chars() {
+            let mut moves = promotion_moves.clone();
+            moves.push(format!("c7d8{promotion_char}").parse().unwrap());
+            assert!(compress(moves).unwrap().ends_with(&format!("7{promotion_char}")), "{promotion_type:?}");
+        }
+
+        assert_eq!(version_markers().map(|(version, _)| version), [FormatVersion::V1, FormatVersion::V2]);
+        assert!(compress_v2(vec!["e2e4".parse().unwrap()]).unwrap().starts_with(".2."));
+        assert_eq!(header_tags().iter().map(|(tag, _)| tag).collect::<String>(), "QBCES2RPF");
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::OnceLock;
+use crate::game::game_state::GameState;
+
+/// a well-known start position that the format header can refer to by its id
+/// instead of spelling out its FEN (see [FormatHeader](crate::header::FormatHeader))
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StartPosition {
+    /// letters and digits, matched case-insensitive
+    pub id: String,
+    pub name: String,
+    /// in the form written by [GameState::get_fen](chess_compress_urlsafe_core::GameState::get_fen)
+    pub fen: String,
+}
+
+/// the registered start positions: the classic one, odds, famous studies and every chess960 position
+/// (with the id "F" followed by its index, e.g. "F518"). Ids are never changed or reused, since encoded games refer to them.
+///
+/// Chess960 position 518 is the classic start position, which never needs a header,
+/// so its id is only an alias (the first registered position with a FEN is the one written to the header).
+pub fn start_positions() -> &'static [StartPosition] {
+    static START_POSITIONS: OnceLock<Vec<StartPosition>> = OnceLock::new();
+    START_POSITIONS.get_or_init(|| {
+        let named_start_positions = NAMED_START_POSITIONS.iter().map(|(id, name, fen)| StartPosition {
+            id: id.to_string(),
+            name: name.to_string(),
+            fen: fen.to_string(),
+        });
+        let chess960_start_positions = (0..960).map(|index| StartPosition {
+            id: format!("F{index}"),
+            name: format!("chess960 #{index}"),
+            fen: GameState::chess960(index).expect("the index is in range").get_fen(),
+        });
+        named_start_positions.chain(chess960_start_positions).collect()
+    })
+}
+
+pub fn find_start_position(id: &str) -> Option<&'static StartPosition> {
+    start_positions().iter().find(|start_position| start_position.id.eq_ignore_ascii_case(id))
+}
+
+pub(crate) fn find_start_position_by_fen(fen: &str) -> Option<&'static StartPosition> {
+    start_positions().iter().find(|start_position| start_position.fen == fen)
+}
+
+// id, name and FEN; odds are given by white
+const NAMED_START_POSITIONS: [(&str, &str, &str); 9] = [
+    ("C", "classic", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+    ("N", "knight odds", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1"),
+    ("NN", "two knights odds", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKB1R w KQkq - 0 1"),
+    ("R", "rook odds", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w Kkq - 0 1"),
+    ("Q", "queen odds", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1"),
+    ("PM", "pawn and move odds", "rnbqkbnr/pppppppp/8/8/8/8/PPPPP1PP/RNBQKBNR b KQkq - 0 1"),
+    ("LUCENA", "Lucena position", "1K1k4/1P6/8/8/8/8/r7/2R5 w - - 0 1"),
+    ("SAAVEDRA", "Saavedra position", "8/8/1KP5/3r4/8/8/8/k7 w - - 0 1"),
+    ("RETI", "Réti endgame study", "7K/8/k1P5/7p/8/8/8/8 w - - 0 1"),
+];
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use rstest::*;
+    use crate::base::a_move::Move;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::{compress_from_with_options, CompressOptions};
+    use crate::compression::decompress::decompress;
+    use super::*;
+
+    #[test]
+    fn test_start_positions_are_valid_and_unique() {
+        let mut ids: HashSet<String> = HashSet::new();
+        for start_position in start_positions() {
+            assert!(start_position.id.chars().all(|c| c.is_ascii_alphanumeric()), "{start_position:?}");
+            assert!(ids.insert(start_position.id.to_ascii_uppercase()), "{start_position:?}");
+            assert_eq!(GameState::from_fen(&start_position.fen).unwrap().get_fen(), start_position.fen);
+        }
+        assert_eq!(find_start_position("pm").map(|start_position| start_position.name.as_str()), Some("pawn and move odds"));
+        assert_eq!(find_start_position("X"), None);
+        assert_eq!(find_start_position("F960"), None);
+        assert_eq!(find_start_position_by_fen(&GameState::classic().get_fen()).map(|start_position| start_position.id.as_str()), Some("C"));
+        assert_eq!(start_positions().len(), NAMED_START_POSITIONS.len() + 960);
+    }
+
+    #[test]
+    fn test_games_from_registered_start_positions() {
+        let (positions, _) = decompress(".P.N.c").unwrap();
+        assert_eq!(positions[0].fen, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1");
+        assert_eq!(positions[1].fen, "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/R1BQKBNR b KQkq e3 0 1");
+    }
+
+    #[rstest(
+        start, moves, expected_encoded,
+        case(GameState::chess960(0).unwrap(), "", ".P.F0."),
+        case(GameState::chess960(518).unwrap(), "e2e4", "c"),
+        case(GameState::from_fen("8/8/1KP5/3r4/8/8/8/k7 w - - 0 1").unwrap(), "c6c7", ".P.SAAVEDRA.qy"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_games_from_registered_start_positions_refer_to_their_id(start: GameState, moves: &str, expected_encoded: &str) {
+        let moves: Vec<Move> = parse_to_vec(moves, ",").unwrap();
+        let start_fen = start.get_fen();
+        let encoded = compress_from_with_options(start, moves, &CompressOptions::default()).unwrap().encoded;
+        assert_eq!(encoded, expected_encoded);
+        let (positions, _) = decompress(&encoded).unwrap();
+        assert_eq!(positions[0].fen, start_fen);
+    }
+
+    #[test]
+    fn test_chess960_castling_from_a_registered_start_position() {
+        // bbqnrnkr: the king castles king side from g1 (onto the rook on h1) and stays there
+        let moves: Vec<Move> = parse_to_vec("f1g3, f8g6, g1h1", ",").unwrap();
+        let encoded = compress_from_with_options(GameState::chess960(96).unwrap(), moves, &CompressOptions::default()).unwrap().encoded;
+        assert!(encoded.starts_with(".P.F96."));
+        let (positions, _) = decompress(&encoded).unwrap();
+        assert_eq!(positions[3].fen, "bbqnr1kr/pppppppp/6n1/8/8/6N1/PPPPPPPP/BBQNRRK1 b kq - 3 2");
+    }
+}
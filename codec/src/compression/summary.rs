@@ -0,0 +1,510 @@
+use crate::base::a_move::{Move, MoveData};
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::color::Color;
+use crate::compression::decompress::{decompress_with_options, play_moves, DecompressOptions, DecompressedGame, DrawReason};
+use crate::game::game_state::GameState;
+// GameResult is defined next to GameState, which computes it, but stays part of the summary API
+pub use crate::game::game_result::GameResult;
+use crate::notation::san::to_san;
+
+/// the key facts of an encoded game
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GameSummary {
+    /// the length of the encoded game (in chars)
+    pub encoded_len: usize,
+    pub half_moves: usize,
+    pub captures: usize,
+    pub result: GameResult,
+    /// the name of the (longest) known opening the game starts with
+    pub opening: Option<&'static str>,
+}
+
+/// what link previews (e.g. unfurled links in chats) show of an encoded game
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Preview {
+    pub final_fen: String,
+    /// the number of half moves played
+    pub plies: usize,
+    pub result: GameResult,
+    /// the last move in standard algebraic notation (None if no move was played)
+    pub last_move: Option<String>,
+    /// the name of the (longest) known opening the game starts with
+    pub opening: Option<&'static str>,
+}
+
+/// how a game ended, as specific as it can be told (see [DecompressedGame::infer_result])
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Outcome {
+    /// the result that was stored with the game (see [Metadata::result](crate::extension::Metadata::result))
+    Recorded(GameResult),
+    Checkmate { winner: Color },
+    Stalemate,
+    /// neither player has the material to mate anymore (a dead draw) since the position at `position_index`,
+    /// the moves after it don't change the result
+    InsufficientMaterial { position_index: usize },
+    /// the game goes on, but the player to move could claim a draw
+    DrawClaimable(DrawReason),
+    Undecided,
+}
+
+impl Outcome {
+    /// the result of the game, a claimable draw is [GameResult::Undecided] since it wasn't claimed (yet)
+    pub fn result(&self) -> GameResult {
+        match self {
+            Outcome::Recorded(result) => *result,
+            Outcome::Checkmate { winner: Color::White } => GameResult::WhiteWins,
+            Outcome::Checkmate { winner: Color::Black } => GameResult::BlackWins,
+            Outcome::Stalemate | Outcome::InsufficientMaterial { .. } => GameResult::Draw,
+            Outcome::DrawClaimable(_) | Outcome::Undecided => GameResult::Undecided,
+        }
+    }
+}
+
+/// where two encoded games part ways
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GameDiff {
+    /// the number of half moves both games start with
+    pub common_half_moves: usize,
+    /// the FEN of the position after the common half moves
+    pub fen_at_divergence: String,
+    /// the first move of game a after the divergence in standard algebraic notation (None if game a ends there)
+    pub next_san_a: Option<String>,
+    /// the first move of game b after the divergence in standard algebraic notation (None if game b ends there)
+    pub next_san_b: Option<String>,
+    pub half_moves_a: usize,
+    pub half_moves_b: usize,
+}
+
+impl GameDiff {
+    pub fn are_equal(&self) -> bool {
+        self.next_san_a.is_none() && self.next_san_b.is_none()
+    }
+}
+
+pub fn summarize(encoded_game: &str) -> Result<GameSummary, ChessError> {
+    let game = decompress_with_options(encoded_game, &DecompressOptions::default())?;
+    let moves: Vec<Move> = game.moves.iter().map(MoveData::given_move).collect();
+    // the known openings start from the classic position
+    let is_classic_start = game.positions[0].fen == GameState::classic().get_fen();
+    Ok(GameSummary {
+        encoded_len: encoded_game.chars().count(),
+        half_moves: moves.len(),
+        captures: game.moves.iter().filter(|move_data| move_data.did_catch_figure()).count(),
+        result: game.result,
+        opening: if is_classic_start { get_opening(&moves) } else { None },
+    })
+}
+
+/// computes the [Preview] of a game in a single pass over its moves.
+/// Moves behind the reveal boundary aren't part of the preview, so it doesn't spoil puzzles.
+pub fn preview(encoded_game: &str) -> Result<Preview, ChessError> {
+    let mut moves: Vec<Move> = Vec::new();
+    let mut state_before_last_move: Option<GameState> = None;
+    let final_state = play_moves(encoded_game, |a_move, game_state| {
+        moves.push(a_move);
+        state_before_last_move = Some(game_state.clone());
+    })?;
+    Ok(Preview {
+        final_fen: final_state.get_fen(),
+        plies: moves.len(),
+        result: final_state.get_game_result(),
+        last_move: moves.last().zip(state_before_last_move).map(|(last_move, game_state)| to_san(*last_move, &game_state)),
+        opening: get_opening(&moves),
+    })
+}
+
+/// fails if the games don't start from the same position, since they part ways before the first move then
+pub fn diff(encoded_game_a: &str, encoded_game_b: &str) -> Result<GameDiff, ChessError> {
+    let game_a = decompress_with_options(encoded_game_a, &DecompressOptions::default())?;
+    let game_b = decompress_with_options(encoded_game_b, &DecompressOptions::default())?;
+    let (start_fen_a, start_fen_b) = (&game_a.positions[0].fen, &game_b.positions[0].fen);
+    if start_fen_a != start_fen_b {
+        return Err(ChessError::new(ErrorKind::IllegalConfig, format!("the games start from different positions ({start_fen_a} and {start_fen_b})")));
+    }
+    let moves_a: Vec<Move> = game_a.moves.iter().map(MoveData::given_move).collect();
+    let moves_b: Vec<Move> = game_b.moves.iter().map(MoveData::given_move).collect();
+    let common_half_moves = moves_a.iter().zip(moves_b.iter()).take_while(|(move_a, move_b)| move_a == move_b).count();
+
+    let mut game_state = GameState::from_fen(start_fen_a)?;
+    for a_move in moves_a[..common_half_moves].iter() {
+        game_state = game_state.do_move(*a_move)?.0;
+    }
+    Ok(GameDiff {
+        common_half_moves,
+        fen_at_divergence: game_a.positions[common_half_moves].fen.clone(),
+        next_san_a: moves_a.get(common_half_moves).map(|a_move| to_san(*a_move, &game_state)),
+        next_san_b: moves_b.get(common_half_moves).map(|a_move| to_san(*a_move, &game_state)),
+        half_moves_a: moves_a.len(),
+        half_moves_b: moves_b.len(),
+    })
+}
+
+/// true if both games end in the same position (as identified by its Zobrist key, so the player to move,
+/// castling rights and en passant square count as well), no matter in which order the moves were played.
+/// Games that can't be decoded are never the same.
+pub fn same_final_position(encoded_game_a: &str, encoded_game_b: &str) -> bool {
+    let final_zobrist_key = |encoded_game: &str| {
+        let game = decode_all_moves(encoded_game, true)?;
+        game.positions.last()?.zobrist_key
+    };
+    match (final_zobrist_key(encoded_game_a), final_zobrist_key(encoded_game_b)) {
+        (Some(key_a), Some(key_b)) => key_a == key_b,
+        _ => false,
+    }
+}
+
+/// true if both games consist of the same moves played from the same start position,
+/// even if they are encoded differently (e.g. with another alphabet or with checksum).
+/// Games that can't be decoded are never the same.
+pub fn same_game(encoded_game_a: &str, encoded_game_b: &str) -> bool {
+    let start_and_moves = |encoded_game: &str| {
+        let game = decode_all_moves(encoded_game, false)?;
+        let moves: Vec<Move> = game.moves.iter().map(MoveData::given_move).collect();
+        Some((game.positions[0].fen.clone(), moves))
+    };
+    match (start_and_moves(encoded_game_a), start_and_moves(encoded_game_b)) {
+        (Some(game_a), Some(game_b)) => game_a == game_b,
+        _ => false,
+    }
+}
+
+/// returns the ply (the number of half moves played) at which the game first reaches the claimed position,
+/// None if it never does or the game can't be decoded (see [DecompressedGame::find_fen]).
+pub fn verify_prefix(encoded: &str, claimed_fen: &str) -> Option<usize> {
+    decode_all_moves(encoded, false)?.find_fen(claimed_fen).first().copied()
+}
+
+impl DecompressedGame {
+    /// returns the plies (the number of half moves played) of all positions of the game that match the predicate
+    pub fn find_position(&self, predicate: impl Fn(&GameState) -> bool) -> Vec<usize> {
+        self.positions.iter().enumerate()
+            .filter(|(_, position)| GameState::from_fen(&position.fen).is_ok_and(|game_state| predicate(&game_state)))
+            .map(|(ply, _)| ply)
+            .collect()
+    }
+
+    /// returns the plies (the number of half moves played) at which the game reaches the given position.
+    /// Only the first four fields of the FEN (board, active color, castling rights and en passant square) are compared,
+    /// so the move counters don't have to be right. An illegal FEN is never reached.
+    pub fn find_fen(&self, fen: &str) -> Vec<usize> {
+        let Ok(searched_state) = GameState::from_fen(fen.trim()) else {
+            return Vec::new();
+        };
+        let searched_fen_part1to4 = searched_state.get_fen_part1to4();
+        self.find_position(|game_state| game_state.get_fen_part1to4() == searched_fen_part1to4)
+    }
+
+    /// the outcome of the game: the result stored with the game or else what the final position tells
+    /// (checkmate, stalemate, insufficient material or a draw that can be claimed), so games that were
+    /// encoded without result still show how they ended.
+    pub fn infer_result(&self) -> Outcome {
+        if let Some(result) = self.extensions.metadata.result {
+            return Outcome::Recorded(result);
+        }
+        if let Some(position_index) = self.dead_draw_at {
+            return Outcome::InsufficientMaterial { position_index };
+        }
+        // the positions come from an untrusted token (or were set by hand), one that can't be set up tells nothing
+        let Some(final_state) = self.positions.last().and_then(|position| GameState::from_fen(&position.fen).ok()) else {
+            return Outcome::Undecided;
+        };
+        if final_state.is_checkmate() {
+            return Outcome::Checkmate { winner: final_state.turn_by.toggle() };
+        }
+        if final_state.is_stalemate() {
+            return Outcome::Stalemate;
+        }
+        let final_fen_part1to4 = final_state.get_fen_part1to4();
+        let repetitions = self.positions.iter()
+            .filter(|position| position.fen.split(' ').take(4).eq(final_fen_part1to4.split(' ')))
+            .count();
+        if repetitions >= 3 {
+            return Outcome::DrawClaimable(DrawReason::ThreefoldRepetition);
+        }
+        if final_state.get_half_moves_without_progress() >= 100 {
+            return Outcome::DrawClaimable(DrawReason::FiftyMoveRule);
+        }
+        Outcome::Undecided
+    }
+}
+
+fn decode_all_moves(encoded_game: &str, zobrist_keys: bool) -> Option<DecompressedGame> {
+    decompress_with_options(encoded_game, &DecompressOptions {
+        zobrist_keys,
+        allow_spoilers: true,
+        ..DecompressOptions::default()
+    }).ok()
+}
+
+/// only the move order counts, transpositions into an opening aren't recognized
+fn get_opening(moves: &[Move]) -> Option<&'static str> {
+    let moves: Vec<String> = moves.iter().map(|a_move| a_move.to_string()).collect();
+    OPENINGS.iter()
+        .filter(|(opening_moves, _)| {
+            let opening_moves: Vec<&str> = opening_moves.split(' ').collect();
+            moves.len() >= opening_moves.len() && moves.iter().zip(opening_moves).all(|(a_move, opening_move)| a_move == opening_move)
+        })
+        .max_by_key(|(opening_moves, _)| opening_moves.len())
+        .map(|(_, name)| *name)
+}
+
+static OPENINGS: [(&str, &str); 27] = [
+    ("e2e4", "King's Pawn Opening"),
+    ("e2e4 e7e5", "Open Game"),
+    ("e2e4 e7e5 g1f3 b8c6 f1b5", "Ruy Lopez"),
+    ("e2e4 e7e5 g1f3 b8c6 f1c4", "Italian Game"),
+    ("e2e4 e7e5 g1f3 b8c6 d2d4", "Scotch Game"),
+    ("e2e4 e7e5 g1f3 g8f6", "Petrov's Defence"),
+    ("e2e4 e7e5 g1f3 d7d6", "Philidor Defence"),
+    ("e2e4 e7e5 f2f4", "King's Gambit"),
+    ("e2e4 e7e5 b1c3", "Vienna Game"),
+    ("e2e4 c7c5", "Sicilian Defence"),
+    ("e2e4 e7e6", "French Defence"),
+    ("e2e4 c7c6", "Caro-Kann Defence"),
+    ("e2e4 d7d5", "Scandinavian Defence"),
+    ("e2e4 d7d6", "Pirc Defence"),
+    ("e2e4 g8f6", "Alekhine's Defence"),
+    ("d2d4", "Queen's Pawn Opening"),
+    ("d2d4 d7d5 c2c4", "Queen's Gambit"),
+    ("d2d4 d7d5 c2c4 d5c4", "Queen's Gambit Accepted"),
+    ("d2d4 d7d5 c2c4 e7e6", "Queen's Gambit Declined"),
+    ("d2d4 d7d5 c2c4 c7c6", "Slav Defence"),
+    ("d2d4 g8f6", "Indian Defence"),
+    ("d2d4 g8f6 c2c4 g7g6", "King's Indian Defence"),
+    ("d2d4 g8f6 c2c4 e7e6 b1c3 f8b4", "Nimzo-Indian Defence"),
+    ("d2d4 f7f5", "Dutch Defence"),
+    ("c2c4", "English Opening"),
+    ("g1f3", "Réti Opening"),
+    ("b2b3", "Larsen's Opening"),
+];
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::compress::{compress, compress_from_with_options, compress_with_options, CompressOptions};
+    use crate::compression::decompress::{decompress, PositionData};
+    use crate::compression::extension::{Extensions, Metadata};
+    use super::*;
+
+    #[rstest(
+        decoded_moves, expected_captures, expected_result, expected_opening,
+        case("", 0, GameResult::Undecided, None),
+        case("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6", 0, GameResult::Undecided, Some("Ruy Lopez")),
+        case("e2e4 d7d5 e4d5 d8d5", 2, GameResult::Undecided, Some("Scandinavian Defence")),
+        case("f2f3 e7e5 g2g4 d8h4", 0, GameResult::BlackWins, None),
+        case("e2e4 e7e5 f1c4 b8c6 d1h5 g8f6 h5f7", 1, GameResult::WhiteWins, Some("Open Game")),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_summarize(
+        decoded_moves: &str,
+        expected_captures: usize,
+        expected_result: GameResult,
+        expected_opening: Option<&str>,
+    ) {
+        let moves: Vec<Move> = parse_to_vec(decoded_moves, " ").unwrap();
+        let encoded_game = compress(moves.clone()).unwrap();
+        let summary = summarize(&encoded_game).unwrap();
+        assert_eq!(summary.encoded_len, encoded_game.len());
+        assert_eq!(summary.half_moves, moves.len());
+        assert_eq!(summary.captures, expected_captures);
+        assert_eq!(summary.result, expected_result);
+        assert_eq!(summary.opening, expected_opening);
+    }
+
+    #[rstest(
+        decoded_moves, expected_result, expected_last_move, expected_opening,
+        case("", GameResult::Undecided, None, None),
+        case("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6", GameResult::Undecided, Some("a6"), Some("Ruy Lopez")),
+        case("f2f3 e7e5 g2g4 d8h4", GameResult::BlackWins, Some("Qh4#"), None),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_preview(
+        decoded_moves: &str,
+        expected_result: GameResult,
+        expected_last_move: Option<&str>,
+        expected_opening: Option<&str>,
+    ) {
+        let moves: Vec<Move> = parse_to_vec(decoded_moves, " ").unwrap();
+        let encoded_game = compress(moves.clone()).unwrap();
+        let game_preview = preview(&encoded_game).unwrap();
+        let (positions, _) = decompress(&encoded_game).unwrap();
+        assert_eq!(game_preview.final_fen, positions.last().unwrap().fen);
+        assert_eq!(game_preview.plies, moves.len());
+        assert_eq!(game_preview.result, expected_result);
+        assert_eq!(game_preview.last_move.as_deref(), expected_last_move);
+        assert_eq!(game_preview.opening, expected_opening);
+    }
+
+    #[rstest(
+        decoded_moves_a, decoded_moves_b, expected_common_half_moves, expected_next_san_a, expected_next_san_b,
+        case("e2e4 e7e5", "e2e4 e7e5", 2, None, None),
+        case("e2e4 e7e5 g1f3", "e2e4 e7e5", 2, Some("Nf3"), None),
+        case("e2e4 e7e5 g1f3", "e2e4 c7c5 g1f3", 1, Some("e5"), Some("c5")),
+        case("d2d4", "e2e4", 0, Some("d4"), Some("e4")),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_diff(
+        decoded_moves_a: &str,
+        decoded_moves_b: &str,
+        expected_common_half_moves: usize,
+        expected_next_san_a: Option<&str>,
+        expected_next_san_b: Option<&str>,
+    ) {
+        let encoded_game_a = compress(parse_to_vec(decoded_moves_a, " ").unwrap()).unwrap();
+        let encoded_game_b = compress(parse_to_vec(decoded_moves_b, " ").unwrap()).unwrap();
+        let game_diff = diff(&encoded_game_a, &encoded_game_b).unwrap();
+        assert_eq!(game_diff.common_half_moves, expected_common_half_moves);
+        assert_eq!(game_diff.next_san_a.as_deref(), expected_next_san_a);
+        assert_eq!(game_diff.next_san_b.as_deref(), expected_next_san_b);
+        assert_eq!(game_diff.are_equal(), expected_next_san_a.is_none() && expected_next_san_b.is_none());
+    }
+
+    #[rstest(
+        start_fen, decoded_moves, expected_captures, expected_result,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1", "e2e4", 0, GameResult::Undecided),
+        case("7k/8/5K2/8/8/8/8/6Q1 w - - 0 1", "g1g6", 0, GameResult::Draw),
+        case("7k/8/5K2/8/8/8/8/6Q1 w - - 0 1", "g1g7", 0, GameResult::WhiteWins),
+        case("7k/8/5K2/8/8/8/5p2/6Q1 b - - 0 1", "f2g1Q", 1, GameResult::Undecided),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_summarize_games_from_a_start_position(
+        start_fen: &str,
+        decoded_moves: &str,
+        expected_captures: usize,
+        expected_result: GameResult,
+    ) {
+        let start = GameState::from_fen(start_fen).unwrap();
+        let moves: Vec<Move> = parse_to_vec(decoded_moves, " ").unwrap();
+        let encoded_game = compress_from_with_options(start, moves.clone(), &CompressOptions::default()).unwrap().encoded;
+        let summary = summarize(&encoded_game).unwrap();
+        assert_eq!(summary.half_moves, moves.len());
+        assert_eq!(summary.captures, expected_captures);
+        assert_eq!(summary.result, expected_result);
+        // the known openings start from the classic position
+        assert_eq!(summary.opening, None);
+    }
+
+    #[test]
+    fn test_diff_games_from_a_start_position() {
+        let start = GameState::from_fen("7k/8/5K2/8/8/8/8/6Q1 w - - 0 1").unwrap();
+        let encode = |decoded_moves: &str| compress_from_with_options(start.clone(), parse_to_vec(decoded_moves, " ").unwrap(), &CompressOptions::default()).unwrap().encoded;
+        let game_diff = diff(&encode("g1g7"), &encode("g1g6")).unwrap();
+        assert_eq!(game_diff.common_half_moves, 0);
+        assert_eq!(game_diff.fen_at_divergence, "7k/8/5K2/8/8/8/8/6Q1 w - - 0 1");
+        assert_eq!(game_diff.next_san_a.as_deref(), Some("Qg7#"));
+        assert_eq!(game_diff.next_san_b.as_deref(), Some("Qg6"));
+        assert!(diff(".F.7k-8-6Q1-8-8-8-8-K7_w_-_-_0_1.G", ".F.7k-8-6Q1-8-8-8-8-K7_w_-_-_0_1.G").unwrap().are_equal());
+        // a knight odds game and a classic one part ways before the first move
+        assert!(diff(".P.N.c", "c").is_err());
+    }
+
+    #[rstest(
+        decoded_moves_a, decoded_moves_b, expected_same_final_position, expected_same_game,
+        case("e2e4 e7e5", "e2e4 e7e5", true, true),
+        case("g1f3 g8f6 b1c3", "b1c3 g8f6 g1f3", true, false),
+        case("e2e4 e7e5 g1f3", "e2e4 e7e5", false, false),
+        case("g1f3 g8f6 f3g1 f6g8", "", true, false),
+        // the same figures, but different en passant squares
+        case("e2e4 g8f6 e4e5 d7d5", "e2e4 g8f6 e4e5 d7d6 g1f3 d6d5 f3g1", false, false),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_same_final_position_and_same_game(
+        decoded_moves_a: &str,
+        decoded_moves_b: &str,
+        expected_same_final_position: bool,
+        expected_same_game: bool,
+    ) {
+        let encoded_game_a = compress(parse_to_vec(decoded_moves_a, " ").unwrap()).unwrap();
+        let encoded_game_b = compress(parse_to_vec(decoded_moves_b, " ").unwrap()).unwrap();
+        assert_eq!(same_final_position(&encoded_game_a, &encoded_game_b), expected_same_final_position);
+        assert_eq!(same_game(&encoded_game_a, &encoded_game_b), expected_same_game);
+    }
+
+    #[test]
+    fn test_same_game_ignores_encoding() {
+        assert!(same_game("ckGV5qh", ".C.ckGV5qhI9"));
+        assert!(same_game("Ka", "a"));
+        assert!(!same_game("ckGV5qh", "not a game"));
+        assert!(!same_final_position("not a game", "not a game"));
+    }
+
+    #[rstest(
+        claimed_fen, expected_ply,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", Some(0)),
+        case("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2", Some(2)),
+        case("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 7 23", Some(2)),
+        case("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3", Some(4)),
+        case("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e6 0 2", None),
+        case("8/8/4k3/8/8/4K3/4P3/8 w - - 0 1", None),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_verify_prefix(claimed_fen: &str, expected_ply: Option<usize>) {
+        let encoded_game = compress(parse_to_vec("e2e4 e7e5 g1f3 b8c6 f1b5", " ").unwrap()).unwrap();
+        assert_eq!(verify_prefix(&encoded_game, claimed_fen), expected_ply);
+    }
+
+    #[rstest(
+        decoded_moves, result, expected_outcome,
+        case("e2e4 e7e5", None, Outcome::Undecided),
+        case("e2e4 e7e5", Some(GameResult::WhiteWins), Outcome::Recorded(GameResult::WhiteWins)),
+        case("f2f3 e7e5 g2g4 d8h4", None, Outcome::Checkmate { winner: Color::Black }),
+        case("e2e3 a7a5 d1h5 a8a6 h5a5 h7h5 h2h4 a6h6 a5c7 f7f6 c7d7 e8f7 d7b7 d8d3 b7b8 d3h7 b8c8 f7g6 c8e6", None, Outcome::Stalemate),
+        case("g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8", None, Outcome::DrawClaimable(DrawReason::ThreefoldRepetition)),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_infer_result(decoded_moves: &str, result: Option<GameResult>, expected_outcome: Outcome) {
+        let extensions = Extensions { metadata: Metadata { result, ..Metadata::default() }, ..Extensions::default() };
+        let encoded_game = compress_with_options(parse_to_vec(decoded_moves, " ").unwrap(), &CompressOptions { extensions, ..CompressOptions::default() }).unwrap();
+        let game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        let outcome = game.infer_result();
+        assert_eq!(outcome, expected_outcome);
+        assert_eq!(outcome.result(), result.unwrap_or(game.result));
+    }
+
+    #[rstest(
+        start_fen, decoded_moves, expected_outcome,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "f2f3 e7e5 g2g4", Outcome::Undecided),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "f2f3 e7e5 g2g4 d8h4", Outcome::Checkmate { winner: Color::Black }),
+        case("7k/8/6Q1/8/8/8/8/K7 w - - 0 1", "a1b1", Outcome::Stalemate),
+        case("4k3/8/8/8/8/8/3r4/4K3 w - - 0 1", "e1d2 e8e7 d2d3", Outcome::InsufficientMaterial { position_index: 1 }),
+        case("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", "e1e2", Outcome::InsufficientMaterial { position_index: 0 }),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_infer_result_of_games_from_a_start_position(start_fen: &str, decoded_moves: &str, expected_outcome: Outcome) {
+        let start = GameState::from_fen(start_fen).unwrap();
+        let encoded_game = compress_from_with_options(start, parse_to_vec(decoded_moves, " ").unwrap(), &CompressOptions::default()).unwrap().encoded;
+        let decompressed_game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+        let outcome = decompressed_game.infer_result();
+        assert_eq!(outcome, expected_outcome);
+        assert_eq!(outcome.result(), decompressed_game.result);
+    }
+
+    #[test]
+    fn test_infer_result_of_positions_that_can_not_be_set_up() {
+        let game = DecompressedGame {
+            positions: vec![PositionData::new("not a fen".to_string())],
+            moves: Vec::new(),
+            warnings: Vec::new(),
+            extensions: Extensions::default(),
+            result: GameResult::Undecided,
+            dead_draw_at: None,
+        };
+        assert_eq!(game.infer_result(), Outcome::Undecided);
+    }
+
+    #[test]
+    fn test_find_position() {
+        // the queens get traded on d5
+        let encoded_game = compress(parse_to_vec("e2e4 d7d5 e4d5 d8d5 d1f3 d5f3 g1f3 e7e5 f3g1 e5e4 g1f3", " ").unwrap()).unwrap();
+        let game = decompress_with_options(&encoded_game, &DecompressOptions::default()).unwrap();
+
+        let without_queens = game.find_position(|game_state| !game_state.board.get_fen_part1().contains(['q', 'Q']));
+        assert_eq!(without_queens, vec![7, 8, 9, 10, 11]);
+        assert_eq!(game.find_fen("rnb1kbnr/ppp1pppp/8/8/8/5N2/PPPP1PPP/RNB1KB1R b KQkq - 0 4"), vec![7]);
+        assert_eq!(game.find_fen("not a fen"), Vec::<usize>::new());
+    }
+}
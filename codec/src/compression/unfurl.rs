@@ -0,0 +1,110 @@
+use std::fmt::Write;
+use crate::base::errors::ChessError;
+use crate::base::position::Position;
+use crate::compression::summary::Preview;
+use crate::game::board::Board;
+use crate::game::game_state::GameState;
+
+const SVG_SQUARE_SIZE: usize = 40;
+const SVG_LIGHT_SQUARE_COLOR: &str = "#f0d9b5";
+const SVG_DARK_SQUARE_COLOR: &str = "#b58863";
+
+/// a one line summary of the game for chat messages, e.g. "Ruy Lopez · 3...a6 · 6 plies · *"
+pub fn unfurl_text(preview: &Preview) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(opening) = preview.opening {
+        parts.push(opening.to_string());
+    }
+    if let Some(last_move) = &preview.last_move {
+        // the move number is taken from the final position, so games from a custom position are numbered correctly
+        let mut fen_fields = preview.final_fen.split(' ').skip(1);
+        let is_white_to_move = fen_fields.next() == Some("w");
+        let round: usize = fen_fields.nth(3).and_then(|round| round.parse().ok()).unwrap_or(1);
+        parts.push(if is_white_to_move {
+            format!("{}...{last_move}", round.saturating_sub(1))
+        } else {
+            format!("{round}.{last_move}")
+        });
+    } else {
+        parts.push("start position".to_string());
+    }
+    parts.push(format!("{} {}", preview.plies, if preview.plies == 1 { "ply" } else { "plies" }));
+    parts.push(preview.result.to_string());
+    parts.join(" · ")
+}
+
+/// the final position drawn with unicode chess symbols (from white's point of view),
+/// fails if the final FEN of the preview isn't valid
+pub fn unfurl_board_unicode(preview: &Preview) -> Result<String, ChessError> {
+    Ok(final_board(preview)?.to_string().trim_start().to_string())
+}
+
+/// the final position as a self-contained svg image (from white's point of view),
+/// fails if the final FEN of the preview isn't valid
+pub fn unfurl_board_svg(preview: &Preview) -> Result<String, ChessError> {
+    let board = final_board(preview)?;
+    let board_size = 8 * SVG_SQUARE_SIZE;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{board_size}" height="{board_size}" viewBox="0 0 {board_size} {board_size}">"#);
+    for row in (0..8).rev() {
+        for column in 0..8 {
+            let x = column as usize * SVG_SQUARE_SIZE;
+            let y = (7 - row) as usize * SVG_SQUARE_SIZE;
+            let square_color = if (column + row) % 2 == 0 { SVG_DARK_SQUARE_COLOR } else { SVG_LIGHT_SQUARE_COLOR };
+            write!(svg, r#"<rect x="{x}" y="{y}" width="{SVG_SQUARE_SIZE}" height="{SVG_SQUARE_SIZE}" fill="{square_color}"/>"#)
+                .expect("writing to a String doesn't fail");
+            if let Some(figure) = board.get_figure(Position::new_unchecked(column, row)) {
+                let center_x = x + SVG_SQUARE_SIZE / 2;
+                let center_y = y + SVG_SQUARE_SIZE / 2;
+                write!(svg, r#"<text x="{center_x}" y="{center_y}" font-size="{}" text-anchor="middle" dominant-baseline="central">{figure}</text>"#, SVG_SQUARE_SIZE * 4 / 5)
+                    .expect("writing to a String doesn't fail");
+            }
+        }
+    }
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+fn final_board(preview: &Preview) -> Result<Board, ChessError> {
+    // the fields of a preview are public, so its FEN isn't necessarily one of a decoded game
+    GameState::from_fen(&preview.final_fen).map(|game_state| game_state.board)
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::compression::summary::preview;
+    use super::*;
+
+    #[rstest(
+        encoded_game, expected_text,
+        case("", "start position · 0 plies · *"),
+        case("ckGV", "Open Game · 2.Nf3 · 3 plies · *"),
+        case(".F.8-8-4k3-8-8-8-4P3-4K3_b_-_-_0_1.l", "1...Kf5 · 1 ply · *"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_unfurl_text(encoded_game: &str, expected_text: &str) {
+        assert_eq!(unfurl_text(&preview(encoded_game).unwrap()), expected_text);
+    }
+
+    #[test]
+    fn test_unfurl_board() {
+        let game_preview = preview("ckGV").unwrap();
+        assert_eq!(
+            unfurl_board_unicode(&game_preview).unwrap(),
+            "♜♞♝♛♚♝♞♜ 8\n♟♟♟♟_♟♟♟ 7\n________ 6\n____♟___ 5\n____♙___ 4\n_____♘__ 3\n♙♙♙♙_♙♙♙ 2\n♖♘♗♕♔♗_♖ 1\nabcdefgh\n",
+        );
+        let svg = unfurl_board_svg(&game_preview).unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect ").count(), 64);
+        assert_eq!(svg.matches("<text ").count(), 32);
+    }
+    #[test]
+    fn test_unfurl_board_of_an_invalid_fen() {
+        let game_preview = Preview { final_fen: "8/8/8/8/8/8/8/8 w - - 0 1".to_string(), ..preview("").unwrap() };
+        assert!(unfurl_board_unicode(&game_preview).is_err());
+        assert!(unfurl_board_svg(&game_preview).is_err());
+    }
+}
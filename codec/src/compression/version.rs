@@ -0,0 +1,165 @@
+use std::str::Chars;
+use crate::base::a_move::{Move, MoveData};
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::compression::base64::{decode_base64_index, encode_base64_index};
+use crate::compression::compress::{compress_from_with_options, CompressOptions};
+use crate::compression::decompress::{decompress_with_options, DecodedMove, DecompressOptions};
+use crate::compression::header::FormatHeader;
+use crate::compression::move_codec::{CompactTargetCodec, LegalMoveIndexCodec, MoveCodec};
+use crate::game::game_state::{GameState, IllegalMoveReason};
+
+/// the way moves are written down in the canonical (url-safe base64) form of a game.
+///
+/// The version is part of the [FormatHeader], games without header are [FormatVersion::V1].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum FormatVersion {
+    /// a move is its to-position (if no other figure can reach it) or its from- and to-position,
+    /// plus the promotion type char for pawn promotions (1 to 3 chars per move)
+    #[default]
+    V1,
+    /// a move is its index in [GameState::ordered_legal_moves] (1 char if there are at most 64 legal moves, else 2).
+    /// The result is shorter but can't be read without a legal move generator.
+    V2,
+}
+
+impl FormatVersion {
+    /// the codec that encodes the moves of this format version
+    pub fn codec(&self) -> &'static dyn MoveCodec {
+        match self {
+            FormatVersion::V1 => &CompactTargetCodec,
+            FormatVersion::V2 => &LegalMoveIndexCodec,
+        }
+    }
+}
+
+/// compresses a game in [FormatVersion::V2], the version tag in the format header lets [decompress](crate::decompress::decompress)
+/// tell the versions apart, so games of every version keep decoding.
+pub fn compress_v2(moves: Vec<Move>) -> Result<String, ChessError> {
+    compress_from_with_options(GameState::classic(), moves, &CompressOptions {
+        version: FormatVersion::V2,
+        ..CompressOptions::default()
+    }).map(|game| game.encoded)
+}
+
+/// re-encodes a game in the given format version, the rest of the format header (alphabet, checksum, ..) is kept.
+/// Scrambled games can't be converted, since that would require the key.
+pub fn convert(encoded: &str, target: FormatVersion) -> Result<String, ChessError> {
+    let (header, _) = FormatHeader::split_from(encoded)?;
+    if header.scrambled {
+        return Err(ChessError::new(ErrorKind::IllegalConfig, format!("{encoded} is scrambled, it can't be converted without key")));
+    }
+    let game = decompress_with_options(encoded, &DecompressOptions {
+        allow_spoilers: true,
+        ..DecompressOptions::default()
+    })?;
+    let moves: Vec<Move> = game.moves.iter().map(MoveData::given_move).collect();
+    compress_from_with_options(header.start_game_state()?, moves, &CompressOptions {
+        alphabet: header.alphabet,
+        checksum: header.checksum,
+        error_correction: header.error_correction,
+        scramble_key: None,
+        version: target,
+        extensions: game.extensions,
+        assume_queen_promotion: false,
+        metrics: None,
+    }).map(|game| game.encoded)
+}
+
+/// returns the chars (1 or 2) that encode the move played in the given state in the v2 format
+pub(crate) fn encode_next_move_v2(next_move: Move, game_state: &GameState, half_move_index: usize) -> Result<String, ChessError> {
+    let legal_moves = game_state.ordered_legal_moves();
+    let Some(move_index) = legal_moves.iter().position(|legal_move| *legal_move == next_move) else {
+        return Err(ChessError::new(
+            ErrorKind::IllegalMoveInGame {
+                half_move_index,
+                a_move: next_move,
+                reason: game_state.is_legal(next_move).err().unwrap_or(IllegalMoveReason::UnreachableTarget),
+            },
+            format!("half move {half_move_index} ({next_move}) is illegal in {}", game_state.get_fen()),
+        ));
+    };
+    let mut encoded_move = String::with_capacity(2);
+    if legal_moves.len() > 64 {
+        encoded_move.push(encode_base64_index(move_index / 64));
+    }
+    encoded_move.push(encode_base64_index(move_index % 64));
+    Ok(encoded_move)
+}
+
+/// decodes the v2 move starting at the next char, only consumes the chars of that move.
+pub(crate) fn decode_next_move_v2(
+    encoded_chars: &mut Chars,
+    game_state: &GameState,
+    half_move_index: usize,
+) -> Result<DecodedMove, ChessError> {
+    let legal_moves = game_state.ordered_legal_moves();
+    let Some(first_char) = encoded_chars.next() else {
+        return Ok(DecodedMove::Incomplete(ChessError::new(ErrorKind::IllegalFormat, format!("half move {half_move_index} is missing"))));
+    };
+    let mut move_index = decode_base64_index(first_char)?;
+    if legal_moves.len() > 64 {
+        let Some(second_char) = encoded_chars.next() else {
+            return Ok(DecodedMove::Incomplete(ChessError::new(
+                ErrorKind::IllegalFormat,
+                format!("second char missing for half move {half_move_index}, {} legal moves need 2 chars", legal_moves.len()),
+            )));
+        };
+        move_index = move_index * 64 + decode_base64_index(second_char)?;
+    }
+    match legal_moves.get(move_index) {
+        Some(next_move) => Ok(DecodedMove::Complete(*next_move)),
+        None => Err(ChessError::new(
+            ErrorKind::IllegalFormat,
+            format!("half move {half_move_index} is the legal move with index {move_index}, but there are only {} legal moves", legal_moves.len()),
+        )),
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::alphabet::Alphabet;
+    use crate::compression::compress::{compress, compress_with_options};
+
+    #[rstest(
+        moves_str, expected_v2,
+        case("", ".2."),
+        case("e2e4 e7e5 g1f3", ".2.NIN"),
+        case("e2e4 d7d5 e4d5 d8d5 b1c3 d5a5", ".2.NGdWBJ"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_convert(moves_str: &str, expected_v2: &str) {
+        let moves: Vec<Move> = parse_to_vec(moves_str, " ").unwrap();
+        let v1 = compress(moves.clone()).unwrap();
+        let v2 = convert(&v1, FormatVersion::V2).unwrap();
+        assert_eq!(v2, expected_v2);
+        assert_eq!(compress_v2(moves.clone()).unwrap(), v2);
+        assert_eq!(convert(&v2, FormatVersion::V1).unwrap(), v1);
+
+        let game = decompress_with_options(&v2, &DecompressOptions::default()).unwrap();
+        let decoded_moves: Vec<Move> = game.moves.iter().map(MoveData::given_move).collect();
+        assert_eq!(decoded_moves, moves);
+    }
+
+    #[test]
+    fn test_convert_keeps_header() {
+        let options = CompressOptions { alphabet: Alphabet::Base32, checksum: true, ..CompressOptions::default() };
+        let v1 = compress_with_options(parse_to_vec("e2e4 e7e5 g1f3", " ").unwrap(), &options).unwrap();
+        let v2 = convert(&v1, FormatVersion::V2).unwrap();
+        let (header, _) = FormatHeader::split_from(&v2).unwrap();
+        assert_eq!(header, FormatHeader { version: FormatVersion::V2, ..FormatHeader::split_from(&v1).unwrap().0 });
+        assert_eq!(convert(&v2, FormatVersion::V1).unwrap(), v1);
+    }
+
+    #[test]
+    fn test_convert_rejects_scrambled_games() {
+        let options = CompressOptions { scramble_key: Some("key".to_string()), ..CompressOptions::default() };
+        let scrambled = compress_with_options(parse_to_vec("e2e4", " ").unwrap(), &options).unwrap();
+        assert!(matches!(convert(&scrambled, FormatVersion::V2).unwrap_err().kind, ErrorKind::IllegalConfig));
+    }
+}
@@ -0,0 +1,10 @@
+// the compression formats and notations, the rules of chess come from chess_compress_urlsafe-core
+use chess_compress_urlsafe_core::{base, figure, game};
+
+mod compression;
+mod notation;
+#[cfg(feature = "corpus")]
+pub mod corpus;
+
+pub use compression::*;
+pub use notation::*;
@@ -0,0 +1,134 @@
+use std::io::BufRead;
+use crate::base::a_move::Move;
+use crate::base::errors::ChessError;
+use crate::compression::compress::compress;
+use crate::notation::move_stream::MoveStream;
+
+/// how far a [PgnConverter] got, which is also all that's needed to resume the conversion after a restart
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    pub games_processed: usize,
+    /// the number of processed games that couldn't be converted
+    pub errors: usize,
+    /// the position in the input right after the last processed game
+    pub byte_offset: u64,
+}
+
+type ProgressCallback<'a> = Box<dyn FnMut(&Checkpoint) + 'a>;
+
+/// compresses all games of a (multi game) PGN one after the other, e.g. to convert a database dump.
+///
+/// Yields one result per game, a game that can't be converted doesn't stop the conversion of the following ones.
+/// Jobs that have to survive restarts should persist the [PgnConverter::checkpoint] (e.g. from the progress callback)
+/// and continue with [PgnConverter::resume].
+pub struct PgnConverter<'a, R: BufRead> {
+    move_stream: MoveStream<R>,
+    start_byte_offset: u64,
+    checkpoint: Checkpoint,
+    on_progress: Option<ProgressCallback<'a>>,
+}
+
+impl<'a, R: BufRead> PgnConverter<'a, R> {
+    pub fn new(reader: R) -> PgnConverter<'a, R> {
+        PgnConverter::resume(reader, Checkpoint::default())
+    }
+
+    /// continues a conversion, the reader has to start at `checkpoint.byte_offset` of the original input
+    pub fn resume(reader: R, checkpoint: Checkpoint) -> PgnConverter<'a, R> {
+        PgnConverter {
+            move_stream: MoveStream::new(reader),
+            start_byte_offset: checkpoint.byte_offset,
+            checkpoint,
+            on_progress: None,
+        }
+    }
+
+    /// the callback is called after every game with the current checkpoint
+    pub fn with_progress_callback(mut self, on_progress: impl FnMut(&Checkpoint) + 'a) -> PgnConverter<'a, R> {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.checkpoint
+    }
+}
+
+impl<R: BufRead> Iterator for PgnConverter<'_, R> {
+    type Item = Result<String, ChessError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut moves: Vec<Move> = Vec::new();
+        let stream_result = loop {
+            match self.move_stream.next() {
+                Some(Ok(next_move)) => moves.push(next_move),
+                Some(Err(error)) => break Err(error),
+                None => break Ok(()),
+            }
+        };
+        // only whitespace (or tag pairs) after the last game
+        if stream_result.is_ok() && moves.is_empty() && self.move_stream.is_at_end() {
+            return None;
+        }
+        let encoded_game = match stream_result {
+            Ok(()) => {
+                self.move_stream.start_next_game();
+                compress(moves)
+            }
+            Err(error) => {
+                self.move_stream.skip_to_next_game();
+                Err(error)
+            }
+        };
+
+        self.checkpoint.games_processed += 1;
+        if encoded_game.is_err() {
+            self.checkpoint.errors += 1;
+        }
+        self.checkpoint.byte_offset = self.start_byte_offset + self.move_stream.bytes_read();
+        if let Some(on_progress) = self.on_progress.as_mut() {
+            on_progress(&self.checkpoint);
+        }
+        Some(encoded_game)
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PGN: &str = "[Game \"1\"]\n1. e4 e5 1-0\n\n[Game \"2\"]\n1. e4 e5 2. Ke3 Nc6 0-1\n\n[Game \"3\"]\n1. d4 *\n\n";
+
+    #[test]
+    fn test_pgn_converter() {
+        let mut progress: Vec<Checkpoint> = Vec::new();
+        let encoded_games: Vec<Result<String, ChessError>> = PgnConverter::new(PGN.as_bytes())
+            .with_progress_callback(|checkpoint| progress.push(*checkpoint))
+            .collect();
+        assert_eq!(encoded_games.len(), 3);
+        assert_eq!(encoded_games[0].as_deref().unwrap(), "ck");
+        assert!(encoded_games[1].is_err());
+        assert_eq!(encoded_games[2].as_deref().unwrap(), compress(vec!["d2d4".parse().unwrap()]).unwrap());
+
+        let games_and_errors: Vec<(usize, usize)> = progress.iter().map(|checkpoint| (checkpoint.games_processed, checkpoint.errors)).collect();
+        assert_eq!(games_and_errors, vec![(1, 0), (2, 1), (3, 1)]);
+        // only the empty line after the last game is left
+        assert_eq!(&PGN[progress[2].byte_offset as usize..], "\n");
+    }
+
+    #[test]
+    fn test_pgn_converter_resumes_at_checkpoint() {
+        let mut converter = PgnConverter::new(PGN.as_bytes());
+        converter.next();
+        let checkpoint = converter.checkpoint();
+
+        let resumed_input = &PGN[checkpoint.byte_offset as usize..];
+        let mut resumed_converter = PgnConverter::resume(resumed_input.as_bytes(), checkpoint);
+        let remaining_games: Vec<Result<String, ChessError>> = resumed_converter.by_ref().collect();
+        assert_eq!(remaining_games.len(), 2);
+        assert_eq!(resumed_converter.checkpoint(), Checkpoint { games_processed: 3, errors: 1, byte_offset: PGN.len() as u64 - 1 });
+    }
+}
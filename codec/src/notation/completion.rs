@@ -0,0 +1,94 @@
+use crate::base::a_move::Move;
+use crate::figure::figure::FigureType;
+use crate::game::game_state::GameState;
+use crate::notation::san::to_san;
+
+/**
+ * returns the legal moves that start with the given partial input, e.g. "N" matches all knight moves
+ * and "Nbd" only the moves of the knight on the b-file to the d-file.
+ * Moves match in standard algebraic notation ("exd5", "O-O"), in long algebraic notation ("Ng1-f3")
+ * and in coordinate notation ("e4d5", "e1h1" or "e1g1" for castling, "e7e8q" for promotions).
+ * Markers for captures, checks and promotions may be left out.
+ * The moves are in the order of [GameState::get_legal_moves].
+ */
+pub fn complete_move(game_state: &GameState, partial: &str) -> Vec<Move> {
+    game_state.get_legal_moves().into_iter().filter(|a_move| {
+        get_notations(*a_move, game_state).iter().any(|notation| starts_with(notation, partial))
+    }).collect()
+}
+
+fn get_notations(a_move: Move, game_state: &GameState) -> Vec<String> {
+    let from_to = a_move.from_to;
+    let coordinate_notation = a_move.to_string();
+    let mut notations = vec![to_san(a_move, game_state), coordinate_notation.to_lowercase()];
+    if game_state.looks_like_castling(from_to).unwrap_or(false) {
+        // the more common coordinate notation of castling points to the target of the king
+        let king_target_column = if from_to.from.column < from_to.to.column { 'g' } else { 'c' };
+        notations.push(format!("{}{king_target_column}{}", from_to.from, from_to.to.row + 1));
+    } else if let Some(figure) = game_state.board.get_figure(from_to.from) {
+        let separator = if game_state.board.is_empty(from_to.to) { '-' } else { 'x' };
+        let figure_char = if figure.fig_type == FigureType::Pawn { String::new() } else { figure.fig_type.to_string() };
+        notations.push(format!("{figure_char}{}{separator}{}{}", from_to.from, from_to.to, a_move.promotion_type.map(|promotion_type| promotion_type.to_string()).unwrap_or_default()));
+    }
+    notations.push(coordinate_notation);
+    notations
+}
+
+/// like str::starts_with, but markers (like "x", "+" or "=") of the notation may be missing in the partial input
+fn starts_with(notation: &str, partial: &str) -> bool {
+    let mut notation_chars = notation.chars();
+    'partial: for partial_char in partial.chars().map(|c| if c == '0' { 'O' } else { c }) {
+        for notation_char in notation_chars.by_ref() {
+            if notation_char == partial_char {
+                continue 'partial;
+            }
+            if !"x+#=-".contains(notation_char) {
+                return false;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use rstest::*;
+    use crate::base::util::tests::parse_to_set;
+    use super::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        game_state, partial, expected_moves,
+        case("", "N", "b1a3, b1c3, g1f3, g1h3"),
+        case("", "Nf", "g1f3"),
+        case("", "e", "e2e3, e2e4"),
+        case("", "e2", "e2e3, e2e4"),
+        case("", "Ng1-", "g1f3, g1h3"),
+        case("", "K", ""),
+        case("", "e5", ""),
+        case("e2e4 d7d5", "ex", "e4d5"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7", "O", "e1h1"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7", "0-0", "e1h1"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7", "e1g", "e1h1"),
+        case("white ♔e1 ♘b1 ♘f3 ♚e8", "Nbd", "b1d2"),
+        case("white ♔e1 ♘e2 ♘a4 ♜e8 ♚h8", "Nc", "a4c3, a4c5"),
+        case("white ♔h1 ♙b7 ♚e8", "b8", "b7b8Q, b7b8R, b7b8N, b7b8B"),
+        case("white ♔h1 ♙b7 ♚e8", "b8=N", "b7b8N"),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8n", "b7b8N"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_complete_move(
+        game_state: GameState,
+        partial: &str,
+        expected_moves: &str,
+    ) {
+        let expected_moves: HashSet<Move> = parse_to_set(expected_moves, ",").unwrap();
+        let actual_moves: HashSet<Move> = complete_move(&game_state, partial).into_iter().collect();
+        assert_eq!(actual_moves, expected_moves);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::base::a_move::Move;
+use crate::base::errors::ChessError;
+use crate::compression::compress::compress;
+use crate::game::game_state::GameState;
+use crate::notation::move_stream::MoveStream;
+use crate::notation::uci::parse_uci;
+
+/// why the same game given in UCI and SAN didn't compress to the same result
+#[derive(Debug)]
+pub enum MismatchReport {
+    /// the moves in UCI notation couldn't be parsed (or compressed)
+    InvalidUci { half_move_index: usize, error: ChessError },
+    /// the movetext in SAN couldn't be parsed (or compressed)
+    InvalidSan { half_move_index: usize, error: ChessError },
+    /// both inputs are valid, but differ from this half move on (a None move means that game already ended)
+    Diverged { half_move_index: usize, uci_move: Option<Move>, san_move: Option<Move> },
+}
+
+/**
+ * compresses a game given as space separated UCI moves (e.g. "e2e4 e7e5") and as SAN movetext
+ * (e.g. "1. e4 e5", see [MoveStream]) and returns the compressed game if both result in the same.
+ * This catches diverging parsers when the same games are ingested from two sources.
+ */
+pub fn cross_check(moves_uci: &str, movetext_san: &str) -> Result<String, MismatchReport> {
+    let mut game_state = GameState::classic();
+    let mut uci_moves: Vec<Move> = Vec::new();
+    for uci in moves_uci.split_whitespace() {
+        let a_move = parse_uci(uci, &game_state).map_err(|error| MismatchReport::InvalidUci { half_move_index: uci_moves.len(), error })?;
+        game_state = game_state.do_move(a_move).map_err(|error| MismatchReport::InvalidUci { half_move_index: uci_moves.len(), error })?.0;
+        uci_moves.push(a_move);
+    }
+    let mut san_moves: Vec<Move> = Vec::new();
+    for a_move in MoveStream::new(movetext_san.as_bytes()) {
+        san_moves.push(a_move.map_err(|error| MismatchReport::InvalidSan { half_move_index: san_moves.len(), error })?);
+    }
+
+    // compressing the UCI moves also checks if they are legal
+    let uci_token = compress(uci_moves.clone()).map_err(|error| MismatchReport::InvalidUci { half_move_index: 0, error })?;
+    let san_token = compress(san_moves.clone()).map_err(|error| MismatchReport::InvalidSan { half_move_index: 0, error })?;
+    if uci_token == san_token {
+        return Ok(uci_token);
+    }
+    let half_move_index = uci_moves.iter().zip(san_moves.iter()).take_while(|(uci_move, san_move)| uci_move == san_move).count();
+    Err(MismatchReport::Diverged {
+        half_move_index,
+        uci_move: uci_moves.get(half_move_index).copied(),
+        san_move: san_moves.get(half_move_index).copied(),
+    })
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        moves_uci, movetext_san, expected_token,
+        case("", "", ""),
+        case("e2e4 e7e5 g1f3", "1. e4 e5 2. Nf3 *", "ckGV"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7 e1g1", "1. Nf3 Nf6 2. g3 g6 3. Bg2 Bg7 4. O-O", "GV-tWuO2EH"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_cross_check(
+        moves_uci: &str,
+        movetext_san: &str,
+        expected_token: &str,
+    ) {
+        assert_eq!(cross_check(moves_uci, movetext_san).unwrap(), expected_token);
+    }
+
+    #[test]
+    fn test_cross_check_reports_divergence() {
+        let Err(MismatchReport::Diverged { half_move_index, uci_move, san_move }) = cross_check("e2e4 e7e5 g1f3", "1. e4 e5 2. Nc3") else {
+            panic!("games should diverge");
+        };
+        assert_eq!(half_move_index, 2);
+        assert_eq!(uci_move, Some("g1f3".parse().unwrap()));
+        assert_eq!(san_move, Some("b1c3".parse().unwrap()));
+
+        let Err(MismatchReport::Diverged { half_move_index, uci_move, san_move }) = cross_check("e2e4", "1. e4 e5") else {
+            panic!("games should diverge");
+        };
+        assert_eq!((half_move_index, uci_move), (1, None));
+        assert_eq!(san_move, Some("e7e5".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_cross_check_reports_invalid_input() {
+        assert!(matches!(cross_check("e2e4 e7e6x", "1. e4 e6"), Err(MismatchReport::InvalidUci { half_move_index: 1, .. })));
+        assert!(matches!(cross_check("e2e4 e7e6", "1. e4 Ke7"), Err(MismatchReport::InvalidSan { half_move_index: 1, .. })));
+    }
+}
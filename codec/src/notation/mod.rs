@@ -0,0 +1,7 @@
+pub mod bulk;
+pub mod completion;
+pub mod cross_check;
+pub mod move_stream;
+pub mod pgn;
+pub mod san;
+pub mod uci;
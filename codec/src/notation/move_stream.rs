@@ -0,0 +1,326 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+use crate::base::a_move::Move;
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::game::game_state::GameState;
+use crate::notation::san::{parse_localized_san, PieceLetterSet};
+
+/// lazily reads the moves of a game in PGN (or plain SAN) from any BufRead.
+///
+/// Tag pairs, comments, variations, move numbers and annotation glyphs are skipped, only the moves
+/// of the main line are returned. Input is read line by line, so memory use doesn't depend on the
+/// length of the stream.
+/// Iteration stops at the result of the game (e.g. "1-0") and after the first error.
+/// Move numbers are checked, so that skipped moves and variations pasted without parentheses
+/// are reported as such (instead of as the illegal move that follows them).
+/// Call [MoveStream::start_next_game] to continue with the next game of a multi game PGN file.
+pub struct MoveStream<R: BufRead> {
+    reader: R,
+    game_state: GameState,
+    pending_tokens: VecDeque<String>,
+    is_in_comment: bool,
+    variation_depth: usize,
+    is_game_over: bool,
+    half_moves_played: usize,
+    previous_san: Option<String>,
+    bytes_read: u64,
+    is_reader_exhausted: bool,
+    piece_letters: PieceLetterSet,
+}
+
+impl<R: BufRead> MoveStream<R> {
+    pub fn new(reader: R) -> MoveStream<R> {
+        MoveStream {
+            reader,
+            game_state: GameState::classic(),
+            pending_tokens: VecDeque::new(),
+            is_in_comment: false,
+            variation_depth: 0,
+            is_game_over: false,
+            half_moves_played: 0,
+            previous_san: None,
+            bytes_read: 0,
+            is_reader_exhausted: false,
+            piece_letters: PieceLetterSet::English,
+        }
+    }
+
+    /// reads the figures of the moves with the given letters (e.g. of a PGN exported by a German chess program)
+    pub fn with_piece_letters(mut self, piece_letters: PieceLetterSet) -> MoveStream<R> {
+        self.piece_letters = piece_letters;
+        self
+    }
+
+    /// resets the game after the previous one ended, iteration continues with the moves of the next game
+    pub fn start_next_game(&mut self) {
+        self.game_state = GameState::classic();
+        self.is_game_over = false;
+        self.half_moves_played = 0;
+        self.previous_san = None;
+    }
+
+    /// skips the rest of the current game (e.g. after an error) up to and including its result,
+    /// then resets the game like [MoveStream::start_next_game].
+    /// If the game has no result, the next game is skipped as well.
+    pub fn skip_to_next_game(&mut self) {
+        loop {
+            let Some(token) = self.pending_tokens.pop_front() else {
+                match self.read_tokens() {
+                    Ok(true) => continue,
+                    _ => break,
+                }
+            };
+            if is_game_result(&token) {
+                break;
+            }
+        }
+        self.start_next_game();
+    }
+
+    /// true once the reader is exhausted and all of its moves were returned
+    pub fn is_at_end(&self) -> bool {
+        self.is_reader_exhausted && self.pending_tokens.is_empty()
+    }
+
+    /// the number of bytes read from the reader so far (whole lines are read at once)
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// the FEN of the position after the last move returned
+    pub fn get_fen(&self) -> String {
+        self.game_state.get_fen()
+    }
+
+    /// returns false if the end of the reader is reached
+    fn read_tokens(&mut self) -> Result<bool, ChessError> {
+        let mut line = String::new();
+        while self.pending_tokens.is_empty() {
+            line.clear();
+            let bytes_read = self.reader.read_line(&mut line)
+                .map_err(|error| ChessError::new(ErrorKind::Io, format!("couldn't read PGN: {error}")).with_source(error))?;
+            if bytes_read == 0 {
+                self.is_reader_exhausted = true;
+                return Ok(false);
+            }
+            self.bytes_read += bytes_read as u64;
+            self.tokenize(&line);
+        }
+        Ok(true)
+    }
+
+    fn tokenize(&mut self, line: &str) {
+        let trimmed_line = line.trim_start();
+        // tag pairs and escaped lines
+        if !self.is_in_comment && (trimmed_line.starts_with('[') || trimmed_line.starts_with('%')) {
+            return;
+        }
+        let mut token = String::new();
+        for c in line.chars() {
+            if self.is_in_comment {
+                self.is_in_comment = c != '}';
+                continue;
+            }
+            match c {
+                '{' => self.is_in_comment = true,
+                ';' => break,
+                '(' => self.variation_depth += 1,
+                ')' => self.variation_depth = self.variation_depth.saturating_sub(1),
+                _ if c.is_whitespace() => {}
+                _ => {
+                    if self.variation_depth == 0 {
+                        token.push(c);
+                    }
+                    continue;
+                }
+            }
+            self.push_token(&mut token);
+        }
+        self.push_token(&mut token);
+    }
+
+    fn push_token(&mut self, token: &mut String) {
+        // move numbers can be followed by the move without whitespace ("1.e4", "3...Nf6")
+        let without_move_number: &str = match token.find('.') {
+            Some(dot_index) if token.starts_with(|c: char| c.is_ascii_digit()) => {
+                let move_number_len = dot_index + token[dot_index..].chars().take_while(|c| *c == '.').count();
+                self.pending_tokens.push_back(token[..move_number_len].to_string());
+                &token[move_number_len..]
+            }
+            _ => token.as_str(),
+        };
+        let is_annotation = without_move_number.starts_with('$') || without_move_number.chars().all(|c| c == '!' || c == '?');
+        if !is_annotation {
+            self.pending_tokens.push_back(without_move_number.to_string());
+        }
+        token.clear();
+    }
+
+    /// checks a move number (e.g. "12." or "12...") against the number of half moves played so far
+    fn check_move_number(&self, move_number_token: &str) -> Result<(), ChessError> {
+        let ply = self.half_moves_played;
+        let expected_number = 1 + ply / 2;
+        let is_black_to_move = ply % 2 == 1;
+        let (number, dots) = move_number_token.split_at(move_number_token.find('.').unwrap_or(move_number_token.len()));
+        let Ok(number) = number.parse::<usize>() else {
+            return Err(ChessError::new(ErrorKind::IllegalFormat, format!("illegal move number {move_number_token} at ply {ply}")));
+        };
+        let announces_black_move = dots.len() > 1;
+        let msg = if number > expected_number {
+            format!("move number {move_number_token} at ply {ply} skips move {expected_number}")
+        } else if number < expected_number || (is_black_to_move && !announces_black_move) {
+            format!("move number {move_number_token} at ply {ply} goes back to an earlier move, was a variation pasted without parentheses?")
+        } else if !is_black_to_move && announces_black_move {
+            format!("move number {move_number_token} at ply {ply} announces a move by black, but white is to move")
+        } else {
+            return Ok(());
+        };
+        Err(ChessError::new(ErrorKind::IllegalFormat, msg))
+    }
+}
+
+impl<R: BufRead> Iterator for MoveStream<R> {
+    type Item = Result<Move, ChessError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_game_over {
+            return None;
+        }
+        let token = loop {
+            if let Some(token) = self.pending_tokens.pop_front() {
+                break token;
+            }
+            match self.read_tokens() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(error) => {
+                    self.is_game_over = true;
+                    return Some(Err(error));
+                }
+            }
+        };
+        if is_game_result(&token) {
+            self.is_game_over = true;
+            return None;
+        }
+        if token.ends_with('.') {
+            return match self.check_move_number(&token) {
+                Ok(()) => self.next(),
+                Err(error) => {
+                    self.is_game_over = true;
+                    Some(Err(error))
+                }
+            };
+        }
+        let parsed_move = parse_localized_san(&token, &self.game_state, self.piece_letters)
+            .and_then(|next_move| Ok((next_move, self.game_state.do_move(next_move)?.0)));
+        match parsed_move {
+            Ok((next_move, new_game_state)) => {
+                self.game_state = new_game_state;
+                self.half_moves_played += 1;
+                self.previous_san = Some(token);
+                Some(Ok(next_move))
+            }
+            Err(_) if self.previous_san.as_ref() == Some(&token) => {
+                self.is_game_over = true;
+                Some(Err(ChessError::new(ErrorKind::IllegalFormat, format!("ply {} ({token}) duplicates the previous ply", self.half_moves_played))))
+            }
+            Err(error) => {
+                self.is_game_over = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+fn is_game_result(token: &str) -> bool {
+    matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*")
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::util::tests::parse_to_vec;
+    use super::*;
+
+    #[rstest(
+        pgn, expected_moves,
+        case("", ""),
+        case("e4 e5 Nf3", "e2e4, e7e5, g1f3"),
+        case("1. e4 e5 2. Nf3 Nc6 3. Bc4 Nf6 4. Ng5 d5 5. exd5 Nxd5?! 1-0", "e2e4, e7e5, g1f3, b8c6, f1c4, g8f6, f3g5, d7d5, e4d5, f6d5"),
+        case("1.e4 c5 2.Nf3 d6 *", "e2e4, c7c5, g1f3, d7d6"),
+        case("1. e4 {best by test} 1... e5 $1 (1... c5 {sicilian} 2. Nf3 (2. c3)) 2. Qh5 ; comment till end of line d4\nNc6 3. Bc4 Nf6?? 4. Qxf7# 1-0", "e2e4, e7e5, d1h5, b8c6, f1c4, g8f6, h5f7"),
+        case("[Event \"Casual\"]\n[Result \"1/2-1/2\"]\n\n1. d4 d5 {a multi\nline comment 2. c4} 2. c4 1/2-1/2", "d2d4, d7d5, c2c4"),
+        case("1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 4. Ba4 Nf6 5. O-O Be7 *", "e2e4, e7e5, g1f3, b8c6, f1b5, a7a6, b5a4, g8f6, e1h1, f8e7"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_move_stream(
+        pgn: &str,
+        expected_moves: &str,
+    ) {
+        let expected_moves: Vec<Move> = parse_to_vec(expected_moves, ",").unwrap();
+        let actual_moves: Vec<Move> = MoveStream::new(pgn.as_bytes()).collect::<Result<Vec<Move>, ChessError>>().unwrap();
+        assert_eq!(actual_moves, expected_moves);
+    }
+
+    #[test]
+    fn test_move_stream_with_piece_letters() {
+        let pgn = "1. e4 e5 2. Sf3 Sc6 3. Lb5 a6 4. La4 Sf6 5. O-O Le7 *";
+        let expected_moves: Vec<Move> = parse_to_vec("e2e4, e7e5, g1f3, b8c6, f1b5, a7a6, b5a4, g8f6, e1h1, f8e7", ",").unwrap();
+        let actual_moves: Vec<Move> = MoveStream::new(pgn.as_bytes())
+            .with_piece_letters(PieceLetterSet::German)
+            .collect::<Result<Vec<Move>, ChessError>>().unwrap();
+        assert_eq!(actual_moves, expected_moves);
+    }
+
+    #[test]
+    fn test_move_stream_stops_after_error() {
+        let mut move_stream = MoveStream::new("1. e4 e5 2. Ke3 Nc6".as_bytes());
+        assert!(move_stream.next().unwrap().is_ok());
+        assert!(move_stream.next().unwrap().is_ok());
+        assert!(move_stream.next().unwrap().is_err());
+        assert!(move_stream.next().is_none());
+    }
+
+    #[test]
+    fn test_move_stream_reports_read_errors_as_io() {
+        let error = MoveStream::new(&b"1. e4 \xff"[..]).next().unwrap().unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::Io));
+        let source = std::error::Error::source(&error).unwrap();
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+    }
+
+    #[test]
+    fn test_move_stream_reads_multiple_games() {
+        let pgn = "[Game \"1\"]\n1. e4 e5 1-0\n\n[Game \"2\"]\n1. d4 0-1\n";
+        let mut move_stream = MoveStream::new(pgn.as_bytes());
+        assert_eq!(move_stream.by_ref().count(), 2);
+        assert_eq!(move_stream.get_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2");
+        move_stream.start_next_game();
+        let second_game: Vec<Move> = move_stream.by_ref().map(|a_move| a_move.unwrap()).collect();
+        assert_eq!(second_game, vec!["d2d4".parse::<Move>().unwrap()]);
+        move_stream.start_next_game();
+        assert!(move_stream.next().is_none());
+    }
+
+    #[rstest(
+        pgn, expected_valid_moves, expected_error,
+        case("1. e4 e5 2. Nf3 Nf3 3. Bc4", 3, "ply 3 (Nf3) duplicates the previous ply"),
+        case("1. e4 e5 3. Nf3", 2, "move number 3. at ply 2 skips move 2"),
+        case("1. e4 e5 2. Nf3 2. Nc3 Nc6", 3, "move number 2. at ply 3 goes back to an earlier move, was a variation pasted without parentheses?"),
+        case("1. e4 e5 2. Nf3 Nc6 1... c5", 4, "move number 1... at ply 4 goes back to an earlier move, was a variation pasted without parentheses?"),
+        case("1... e5", 0, "move number 1... at ply 0 announces a move by black, but white is to move"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_move_stream_reports_pasting_mistakes(pgn: &str, expected_valid_moves: usize, expected_error: &str) {
+        let mut move_stream = MoveStream::new(pgn.as_bytes());
+        for _ in 0..expected_valid_moves {
+            assert!(move_stream.next().unwrap().is_ok());
+        }
+        let error = move_stream.next().unwrap().unwrap_err();
+        assert_eq!(error.msg, expected_error);
+        assert!(move_stream.next().is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::base::a_move::{Move, MoveData};
+use crate::base::color::Color;
+use crate::base::errors::ChessError;
+use crate::compression::compress::compress;
+use crate::compression::decompress::DecompressedGame;
+use crate::game::game_state::GameState;
+use crate::notation::move_stream::MoveStream;
+use crate::notation::san::to_san;
+
+/// parses PGN movetext (e.g. "1. e4 e5 2. Nf3 Nc6") of a game from the classic start position into its moves.
+///
+/// Tag pairs, comments, variations and annotation glyphs are skipped (see [MoveStream]),
+/// SAN is disambiguated by the position the move is played in.
+pub fn parse_movetext(movetext: &str) -> Result<Vec<Move>, ChessError> {
+    MoveStream::new(movetext.as_bytes()).collect()
+}
+
+/// compresses a game given as PGN (see [parse_movetext])
+pub fn compress_pgn(pgn: &str) -> Result<String, ChessError> {
+    compress(parse_movetext(pgn)?)
+}
+
+/// renders the moves of a game played from `start` as PGN movetext with move numbers (e.g. "1. e4 e5 2. Nf3")
+pub fn to_movetext(start: &GameState, moves: &[Move]) -> String {
+    let mut game_state = start.clone();
+    let mut movetext = String::new();
+    for (half_move_index, a_move) in moves.iter().enumerate() {
+        // the rendering stops at a move that can't be played
+        let Ok((game_state_after_move, _)) = game_state.do_move(*a_move) else {
+            break;
+        };
+        if !movetext.is_empty() {
+            movetext.push(' ');
+        }
+        match game_state.turn_by {
+            Color::White => movetext.push_str(&format!("{}. ", game_state.get_fullmove_number())),
+            Color::Black if half_move_index == 0 => movetext.push_str(&format!("{}... ", game_state.get_fullmove_number())),
+            Color::Black => {}
+        }
+        movetext.push_str(&to_san(*a_move, &game_state));
+        game_state = game_state_after_move;
+    }
+    movetext
+}
+
+impl DecompressedGame {
+    /// the moves in standard algebraic notation (e.g. "Nf3", "exd5", "O-O" or "Qxf7#")
+    pub fn to_san(&self) -> Vec<String> {
+        let mut game_state = self.start_game_state();
+        self.moves.iter().map_while(|move_data| {
+            let a_move = move_data.given_move();
+            let (game_state_after_move, _) = game_state.do_move(a_move).ok()?;
+            let san = to_san(a_move, &game_state);
+            game_state = game_state_after_move;
+            Some(san)
+        }).collect()
+    }
+
+    /// the PGN movetext of the game followed by its result
+    /// (the one stored with the game or else as far as it can be told from the final position)
+    pub fn to_pgn(&self) -> String {
+        let start = self.start_game_state();
+        let moves: Vec<Move> = self.moves.iter().map(MoveData::given_move).collect();
+        let result = self.extensions.metadata.result.unwrap_or(self.result);
+        let movetext = to_movetext(&start, &moves);
+        if movetext.is_empty() {
+            result.to_string()
+        } else {
+            format!("{movetext} {result}")
+        }
+    }
+
+    fn start_game_state(&self) -> GameState {
+        let start_fen = &self.positions.first().expect("there is always a start position").fen;
+        GameState::from_fen(start_fen).expect("the FENs of a decompressed game should be valid")
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::compression::decompress::{decompress_with_options, DecompressOptions};
+    use super::*;
+
+    #[rstest(
+        movetext, expected_moves,
+        case("", ""),
+        case("1. e4 e5 2. Nf3 Nc6", "e2e4, e7e5, g1f3, b8c6"),
+        case("1. Nf3 Nf6 2. c4 g6 3. Nc3 Bg7 4. d4 O-O 5. Bf4 d5 6. Qb3 dxc4 7. Qxc4 c6 8. e4 Nbd7", "g1f3, g8f6, c2c4, g7g6, b1c3, f8g7, d2d4, e8h8, c1f4, d7d5, d1b3, d5c4, b3c4, c7c6, e2e4, b8d7"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_movetext(movetext: &str, expected_moves: &str) {
+        let expected_moves: Vec<Move> = parse_to_vec(expected_moves, ",").unwrap();
+        assert_eq!(parse_movetext(movetext).unwrap(), expected_moves);
+    }
+
+    #[test]
+    fn test_compress_pgn() {
+        assert_eq!(compress_pgn("[Event \"Casual\"]\n\n1. e4 e5 2. Nf3 {the main line} 1-0").unwrap(), "ckGV");
+        assert!(compress_pgn("1. e4 e5 2. Ke3").is_err());
+    }
+
+    #[rstest(
+        encoded_game, expected_pgn,
+        case("", "*"),
+        case("ckGV", "1. e4 e5 2. Nf3 *"),
+        case(".F.8-8-4k3-8-8-8-4P3-4K3_b_-_-_0_7.lc", "7... Kf5 8. e4+ *"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_pgn(encoded_game: &str, expected_pgn: &str) {
+        let game = decompress_with_options(encoded_game, &DecompressOptions::default()).unwrap();
+        let pgn = game.to_pgn();
+        assert_eq!(pgn, expected_pgn);
+        assert_eq!(compress_pgn(&pgn).is_ok(), game.positions[0].fen == GameState::classic().get_fen());
+    }
+
+    #[test]
+    fn test_to_san() {
+        let moves: Vec<Move> = parse_to_vec("e2e4, d7d5, e4d5, d8d5, g1f3, c8g4, f1e2, b8c6, e1h1, e8a8, b1c3, d5a5, f3d4, g4e2, d4c6, e2d1, c6a7", ",").unwrap();
+        let game = decompress_with_options(&compress(moves).unwrap(), &DecompressOptions::default()).unwrap();
+        assert_eq!(game.to_san().join(" "), "e4 d5 exd5 Qxd5 Nf3 Bg4 Be2 Nc6 O-O O-O-O Nc3 Qa5 Nd4 Bxe2 Nxc6 Bxd1 Nxa7+");
+        assert_eq!(parse_movetext(&game.to_pgn()).unwrap(), game.moves.iter().map(MoveData::given_move).collect::<Vec<Move>>());
+    }
+}
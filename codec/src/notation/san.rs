@@ -0,0 +1,346 @@
+use crate::base::a_move::{FromTo, Move, PromotionType};
+use crate::base::a_move::CastlingType;
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::position::Position;
+use crate::figure::figure::{Figure, FigureType};
+use crate::figure::functions::is_reachable_by::get_positions_to_reach_target_from;
+use crate::game::game_state::GameState;
+
+/// the letters the figures are written with in SAN, they depend on the language of the PGN or user
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum PieceLetterSet {
+    /// K, Q, R, B, N
+    #[default]
+    English,
+    /// K (König), D (Dame), T (Turm), L (Läufer), S (Springer)
+    German,
+    /// R (roi), D (dame), T (tour), F (fou), C (cavalier)
+    French,
+    /// R (rey), D (dama), T (torre), A (alfil), C (caballo), Italian uses the same letters
+    Spanish,
+    /// K (koning), D (dame), T (toren), L (loper), P (paard)
+    Dutch,
+}
+
+impl PieceLetterSet {
+    /// the letters of king, queen, rook, bishop and knight
+    fn letters(&self) -> [char; 5] {
+        match self {
+            PieceLetterSet::English => ['K', 'Q', 'R', 'B', 'N'],
+            PieceLetterSet::German => ['K', 'D', 'T', 'L', 'S'],
+            PieceLetterSet::French => ['R', 'D', 'T', 'F', 'C'],
+            PieceLetterSet::Spanish => ['R', 'D', 'T', 'A', 'C'],
+            PieceLetterSet::Dutch => ['K', 'D', 'T', 'L', 'P'],
+        }
+    }
+
+    pub fn figure_type(&self, letter: char) -> Option<FigureType> {
+        let [king, queen, rook, bishop, knight] = self.letters();
+        match letter {
+            _ if letter == king => Some(FigureType::King),
+            _ if letter == queen => Some(FigureType::Queen),
+            _ if letter == rook => Some(FigureType::Rook),
+            _ if letter == bishop => Some(FigureType::Bishop),
+            _ if letter == knight => Some(FigureType::Knight),
+            _ => None,
+        }
+    }
+
+    fn parse_promotion_type(&self, promotion: &str) -> Result<PromotionType, ChessError> {
+        let mut letters = promotion.chars();
+        let promotion_type = match (letters.next().and_then(|letter| self.figure_type(letter)), letters.next()) {
+            (Some(FigureType::Queen), None) => Some(PromotionType::Queen),
+            (Some(FigureType::Rook), None) => Some(PromotionType::Rook),
+            (Some(FigureType::Knight), None) => Some(PromotionType::Knight),
+            (Some(FigureType::Bishop), None) => Some(PromotionType::Bishop),
+            _ => None,
+        };
+        promotion_type.ok_or_else(|| {
+            let [_, queen, rook, bishop, knight] = self.letters();
+            ChessError::new(
+                ErrorKind::IllegalFormat,
+                format!("unknown pawn promotion type: {promotion}. Only '{queen}{rook}{knight}{bishop}' are allowed."),
+            )
+        })
+    }
+}
+
+/**
+ * parses a move in standard algebraic notation (e.g. "e4", "Nbd7", "exd8=Q+", "O-O") given the
+ * state of the game it's played in.
+ * Check/mate markers, annotations like "!?" and "e.p." are ignored. Figures that can't move because
+ * they are pinned to their king don't count when resolving ambiguities (just like in SAN).
+ * Long algebraic notation (e.g. "Ng1-f3" or "e2e4") is accepted as well.
+ */
+pub fn parse_san(san: &str, game_state: &GameState) -> Result<Move, ChessError> {
+    parse_localized_san(san, game_state, PieceLetterSet::English)
+}
+
+/// like [parse_san], but the figures are written with the given letters (e.g. "Sf3" in German)
+pub fn parse_localized_san(san: &str, game_state: &GameState, piece_letters: PieceLetterSet) -> Result<Move, ChessError> {
+    let illegal_format = || ChessError::new(ErrorKind::IllegalFormat, format!("not a move in standard algebraic notation: {san}"));
+
+    let trimmed = san.trim_end_matches(['+', '#', '!', '?']);
+    let trimmed = trimmed.strip_suffix("e.p.").unwrap_or(trimmed);
+    match trimmed {
+        "O-O" | "0-0" => return parse_castling(CastlingType::KingSide, san, game_state),
+        "O-O-O" | "0-0-0" => return parse_castling(CastlingType::QueenSide, san, game_state),
+        _ => {}
+    }
+
+    let (trimmed, promotion_type) = split_promotion(trimmed, piece_letters)?;
+    let (figure_type, rest) = match trimmed.chars().next() {
+        Some('a'..='h') => (FigureType::Pawn, trimmed),
+        Some(letter) => match piece_letters.figure_type(letter) {
+            Some(figure_type) => (figure_type, &trimmed[letter.len_utf8()..]),
+            None => return Err(illegal_format()),
+        },
+        None => return Err(illegal_format()),
+    };
+    let squares: Vec<char> = rest.chars().filter(|c| *c != 'x' && *c != '-').collect();
+    if !(2..=4).contains(&squares.len()) || !squares.iter().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(illegal_format());
+    }
+    let (disambiguation, target) = squares.split_at(squares.len() - 2);
+    let target = target.iter().collect::<String>().parse::<Position>()?;
+    let mut from_column: Option<i8> = None;
+    let mut from_row: Option<i8> = None;
+    for c in disambiguation {
+        match c {
+            'a'..='h' if from_column.is_none() && from_row.is_none() => from_column = Some(*c as i8 - 'a' as i8),
+            '1'..='8' if from_row.is_none() => from_row = Some(*c as i8 - '1' as i8),
+            _ => return Err(illegal_format()),
+        }
+    }
+
+    if promotion_type.is_some() && figure_type != FigureType::Pawn {
+        return Err(illegal_format());
+    }
+    let is_promotion_row = target.row == 0 || target.row == 7;
+    if figure_type == FigureType::Pawn && is_promotion_row != promotion_type.is_some() {
+        return Err(ChessError::new(ErrorKind::IllegalMove, format!("{san} has to promote the pawn if and only if it moves to the last row")));
+    }
+    if let Some(Figure { fig_type: FigureType::King, color: _ }) = game_state.board.get_figure(target) {
+        return Err(ChessError::new(ErrorKind::IllegalMove, format!("{san} would capture a king")));
+    }
+
+    let candidates: Vec<Move> = get_positions_to_reach_target_from(target, game_state)?.into_iter()
+        .filter(|from| game_state.board.contains_figure(*from, figure_type, game_state.turn_by))
+        .filter(|from| from_column.map(|column| from.column == column).unwrap_or(true))
+        .filter(|from| from_row.map(|row| from.row == row).unwrap_or(true))
+        .map(|from| Move { from_to: FromTo::new(from, target), promotion_type })
+        .filter(|a_move| !game_state.leaves_king_in_check(*a_move))
+        .collect();
+    match candidates.as_slice() {
+        [a_move] => Ok(*a_move),
+        [] => Err(ChessError::new(ErrorKind::IllegalMove, format!("{san} isn't a legal move for {}", game_state.turn_by))),
+        _ => Err(ChessError::new(ErrorKind::IllegalMove, format!("{san} is ambiguous, it could be any of {candidates:?}"))),
+    }
+}
+
+/// splits off "=Q" or a trailing "Q" (of pawn moves like "e8Q")
+fn split_promotion(san: &str, piece_letters: PieceLetterSet) -> Result<(&str, Option<PromotionType>), ChessError> {
+    if let Some((rest, promotion)) = san.split_once('=') {
+        return Ok((rest, Some(piece_letters.parse_promotion_type(promotion)?)));
+    }
+    let mut chars = san.chars().rev();
+    if let (Some(last), Some(second_to_last)) = (chars.next(), chars.next()) {
+        if let (Ok(promotion_type), true) = (piece_letters.parse_promotion_type(&last.to_string()), second_to_last.is_ascii_digit()) {
+            return Ok((&san[..san.len() - last.len_utf8()], Some(promotion_type)));
+        }
+    }
+    Ok((san, None))
+}
+
+/// castling moves are encoded as the king capturing its own rook
+fn parse_castling(castling_type: CastlingType, san: &str, game_state: &GameState) -> Result<Move, ChessError> {
+    game_state.get_castling_move(castling_type).ok_or_else(|| ChessError::new(
+        ErrorKind::IllegalMove,
+        format!("{san} isn't a legal move for {}", game_state.turn_by),
+    ))
+}
+
+/// returns the move in standard algebraic notation (incl. check and mate markers), the move has to be legal
+pub fn to_san(a_move: Move, game_state: &GameState) -> String {
+    let from_to = a_move.from_to;
+    let moving_figure = game_state.board.get_figure(from_to.from).expect("a legal move starts at a figure");
+    let mut san = String::with_capacity(7);
+    if game_state.looks_like_castling(from_to).unwrap_or(false) {
+        san.push_str(if from_to.from.column < from_to.to.column { "O-O" } else { "O-O-O" });
+    } else {
+        let is_capture = !game_state.board.is_empty(from_to.to)
+            || (moving_figure.fig_type == FigureType::Pawn && from_to.from.column != from_to.to.column);
+        if moving_figure.fig_type == FigureType::Pawn {
+            if is_capture {
+                san.push(column_char(from_to.from));
+            }
+        } else {
+            san.push(moving_figure.fig_type.as_encoded());
+            // figures of the same type that could go there as well
+            let other_origins: Vec<Position> = get_positions_to_reach_target_from(from_to.to, game_state).unwrap_or_default().into_iter()
+                .filter(|from| *from != from_to.from && game_state.board.contains_figure(*from, moving_figure.fig_type, moving_figure.color))
+                .filter(|from| !game_state.leaves_king_in_check(Move::new(FromTo::new(*from, from_to.to))))
+                .collect();
+            if !other_origins.is_empty() {
+                if other_origins.iter().all(|from| from.column != from_to.from.column) {
+                    san.push(column_char(from_to.from));
+                } else if other_origins.iter().all(|from| from.row != from_to.from.row) {
+                    san.push(row_char(from_to.from));
+                } else {
+                    san.push_str(&from_to.from.to_string());
+                }
+            }
+        }
+        if is_capture {
+            san.push('x');
+        }
+        san.push_str(&from_to.to.to_string());
+        if let Some(promotion_type) = a_move.promotion_type {
+            san.push('=');
+            san.push(promotion_type.as_encoded());
+        }
+    }
+
+    if let Ok((new_game_state, _)) = game_state.do_move(a_move) {
+        if new_game_state.is_in_check(new_game_state.turn_by) {
+            san.push(if new_game_state.get_legal_moves().is_empty() { '#' } else { '+' });
+        }
+    }
+    san
+}
+
+fn column_char(pos: Position) -> char {
+    (b'a' + pos.column as u8) as char
+}
+
+fn row_char(pos: Position) -> char {
+    (b'1' + pos.row as u8) as char
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        game_state, san, expected_move,
+        case("", "e4", "e2e4"),
+        case("", "Nf3", "g1f3"),
+        case("", "Ng1-f3", "g1f3"),
+        case("", "e2e4", "e2e4"),
+        case("e2e4 d7d5", "exd5", "e4d5"),
+        case("e2e4 d7d5", "Bb5+", "f1b5"),
+        case("e2e4 a7a6 e4e5 d7d5", "exd6", "e5d6"),
+        case("e2e4 a7a6 e4e5 d7d5", "exd6e.p.", "e5d6"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7", "O-O", "e1h1"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7 e1h1", "0-0", "e8h8"),
+        case("white ♔e1 ♖a1 ♚e8", "O-O-O", "e1a1"),
+        case("white ♔e1 ♘b1 ♘f3 ♚e8", "Nbd2", "b1d2"),
+        case("white ♔e1 ♘b1 ♘f3 ♚e8", "Nfd2", "f3d2"),
+        case("white ♔e1 ♖a1 ♖a5 ♚e8", "R1a3", "a1a3"),
+        case("white ♔e1 ♕a1 ♕a5 ♕e5 ♚h8", "Qa5c3", "a5c3"),
+        case("white ♔e1 ♘e2 ♘a4 ♜e8 ♚h8", "Nc3", "a4c3"),
+        case("white ♔h1 ♙b7 ♚e8", "b8=Q", "b7b8Q"),
+        case("white ♔h1 ♙b7 ♜a8 ♚e8", "bxa8=N#", "b7a8N"),
+        case("white ♔h1 ♙b7 ♚e8", "b8R", "b7b8R"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_san(
+        game_state: GameState,
+        san: &str,
+        expected_move: Move,
+    ) {
+        let actual_move = parse_san(san, &game_state).unwrap();
+        assert_eq!(actual_move, expected_move);
+    }
+
+    #[rstest(
+        game_state, san,
+        case("", "e5"),
+        case("", "Nd2"),
+        case("", "O-O"),
+        case("", "Ke2"),
+        case("", "Xe4"),
+        case("", "e"),
+        case("", "e4=Q"),
+        case("white ♔e1 ♘b1 ♘f3 ♚e8", "Nd2?"),
+        case("white ♔h1 ♙b7 ♚e8", "b8"),
+        case("white ♔e1 ♖h1 ♜f8 ♚a8", "O-O"),
+        case("white ♔e1 ♖a1 ♜e8 ♚a8", "O-O-O"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_san_fails(
+        game_state: GameState,
+        san: &str,
+    ) {
+        assert!(parse_san(san, &game_state).is_err());
+    }
+
+    #[rstest(
+        game_state, san, piece_letters, expected_move,
+        case("", "Sf3", PieceLetterSet::German, "g1f3"),
+        case("e2e4 d7d5", "Lb5+", PieceLetterSet::German, "f1b5"),
+        case("white ♔h1 ♙b7 ♚e8", "b8=D", PieceLetterSet::German, "b7b8Q"),
+        case("white ♔h1 ♙b7 ♚e8", "b8T", PieceLetterSet::German, "b7b8R"),
+        case("", "Cf3", PieceLetterSet::French, "g1f3"),
+        case("white ♔e1 ♖a1 ♖a5 ♚e8", "T1a3", PieceLetterSet::French, "a1a3"),
+        case("white ♔e1 ♖a1 ♚e8", "Rf2", PieceLetterSet::French, "e1f2"),
+        case("white ♔h1 ♙b7 ♚e8", "b8=F", PieceLetterSet::French, "b7b8B"),
+        case("e2e4 d7d5", "Ab5+", PieceLetterSet::Spanish, "f1b5"),
+        case("", "Pf3", PieceLetterSet::Dutch, "g1f3"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7", "O-O", PieceLetterSet::German, "e1h1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_localized_san(
+        game_state: GameState,
+        san: &str,
+        piece_letters: PieceLetterSet,
+        expected_move: Move,
+    ) {
+        assert_eq!(parse_localized_san(san, &game_state, piece_letters).unwrap(), expected_move);
+    }
+
+    #[rstest(
+        game_state, san, piece_letters,
+        case("", "Nf3", PieceLetterSet::German),
+        case("white ♔h1 ♙b7 ♚e8", "b8=Q", PieceLetterSet::French),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_localized_san_rejects_other_letters(
+        game_state: GameState,
+        san: &str,
+        piece_letters: PieceLetterSet,
+    ) {
+        assert!(parse_localized_san(san, &game_state, piece_letters).is_err());
+    }
+
+    #[rstest(
+        game_state, a_move, expected_san,
+        case("", "e2e4", "e4"),
+        case("", "g1f3", "Nf3"),
+        case("e2e4 d7d5", "e4d5", "exd5"),
+        case("e2e4 a7a6 e4e5 d7d5", "e5d6", "exd6"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7", "e1h1", "O-O"),
+        case("white ♔e1 ♖a1 ♚e8", "e1a1", "O-O-O"),
+        case("white ♔e1 ♘b1 ♘f3 ♚e8", "b1d2", "Nbd2"),
+        case("white ♔e1 ♖a1 ♖a5 ♚e8", "a1a3", "R1a3"),
+        case("white ♔e1 ♕a1 ♕a5 ♕e5 ♚h8", "a5c3", "Qa5c3+"),
+        case("white ♔e1 ♘e2 ♘a4 ♜e8 ♚h8", "a4c3", "Nc3"),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8Q", "b8=Q+"),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8N", "b8=N"),
+        case("f2f3 e7e5 g2g4", "d8h4", "Qh4#"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_san(
+        game_state: GameState,
+        a_move: Move,
+        expected_san: &str,
+    ) {
+        assert_eq!(to_san(a_move, &game_state), expected_san);
+        assert_eq!(parse_san(expected_san, &game_state).unwrap(), a_move);
+    }
+}
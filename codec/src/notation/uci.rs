@@ -0,0 +1,98 @@
+use crate::base::a_move::{CastlingType, FromTo, Move};
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::position::Position;
+use crate::figure::figure::FigureType;
+use crate::game::game_state::GameState;
+
+/**
+ * parses a move in UCI notation (e.g. "e2e4", "e7e8q") given the state of the game it's played in.
+ * UCI castling moves point to the target of the king ("e1g1") and are converted to the king
+ * capturing its own rook ("e1h1"), which is also accepted.
+ */
+pub fn parse_uci(uci: &str, game_state: &GameState) -> Result<Move, ChessError> {
+    let illegal_format = || ChessError::new(ErrorKind::IllegalFormat, format!("not a move in UCI notation: {uci}"));
+    if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+        return Err(illegal_format());
+    }
+    let (from_to, promotion) = uci.split_at(4);
+    let from_to = from_to.parse::<FromTo>()?;
+    let a_move = if promotion.is_empty() {
+        Move::new(from_to)
+    } else {
+        Move::new_with_promotion(from_to, promotion.to_ascii_uppercase().parse()?)
+    };
+
+    let ground_row = game_state.turn_by.get_ground_row();
+    let is_castling_by_king_target = game_state.board.contains_figure(from_to.from, FigureType::King, game_state.turn_by)
+        && from_to.from.row == ground_row
+        && from_to.to.row == ground_row
+        && (from_to.from.column - from_to.to.column).abs() == 2;
+    if is_castling_by_king_target {
+        let castling_type = if from_to.from.column < from_to.to.column { CastlingType::KingSide } else { CastlingType::QueenSide };
+        let rook_column = game_state.castling_rights().columns.rook(castling_type);
+        return Ok(Move::new(FromTo::new(from_to.from, Position::new_unchecked(rook_column, ground_row))));
+    }
+    Ok(a_move)
+}
+
+/// returns the move in UCI notation (e.g. "e2e4", "e7e8q"), the counterpart of [parse_uci].
+/// Castling moves point to the target of the king ("e1g1") instead of the rook.
+pub fn to_uci(a_move: Move, game_state: &GameState) -> String {
+    let from_to = a_move.from_to;
+    if game_state.looks_like_castling(from_to).unwrap_or(false) {
+        let king_target_column = if from_to.from.column < from_to.to.column { 6 } else { 2 };
+        return FromTo::new(from_to.from, Position::new_unchecked(king_target_column, from_to.from.row)).to_string();
+    }
+    match a_move.promotion_type {
+        None => from_to.to_string(),
+        Some(promotion_type) => format!("{from_to}{}", promotion_type.as_encoded().to_ascii_lowercase()),
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        game_state, uci, expected_move,
+        case("", "e2e4", "e2e4"),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8q", "b7b8Q"),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8N", "b7b8N"),
+        case("white ♔e1 ♖h1 ♚e8", "e1g1", "e1h1"),
+        case("white ♔e1 ♖a1 ♚e8", "e1c1", "e1a1"),
+        case("white ♔e1 ♖a1 ♚e8", "e1a1", "e1a1"),
+        case("black ♔e1 ♜h8 ♚e8", "e8g8", "e8h8"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_uci(
+        game_state: GameState,
+        uci: &str,
+        expected_move: Move,
+    ) {
+        assert_eq!(parse_uci(uci, &game_state).unwrap(), expected_move);
+    }
+
+    #[rstest(
+        game_state, a_move, expected_uci,
+        case("", "e2e4", "e2e4"),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8N", "b7b8n"),
+        case("white ♔e1 ♖h1 ♚e8", "e1h1", "e1g1"),
+        case("white ♔e1 ♖a1 ♚e8", "e1a1", "e1c1"),
+        case("black ♔e1 ♜h8 ♚e8", "e8h8", "e8g8"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_uci(
+        game_state: GameState,
+        a_move: Move,
+        expected_uci: &str,
+    ) {
+        let uci = to_uci(a_move, &game_state);
+        assert_eq!(uci, expected_uci);
+        assert_eq!(parse_uci(&uci, &game_state).unwrap(), a_move);
+    }
+}
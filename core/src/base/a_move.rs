@@ -6,6 +6,7 @@ use std::hash::{Hash, Hasher};
 use crate::base::errors::{ChessError, ErrorKind};
 use crate::base::a_move::MoveType::{Castling, EnPassant, Normal, PawnPromotion};
 use crate::figure::figure::FigureType;
+use crate::game::game_state::GameState;
 
 // TODO MoveData should implement Claim as soon as it's added to the language.
 // see https://smallcultfollowing.com/babysteps/blog/2024/06/21/claim-auto-and-otherwise/
@@ -27,7 +28,7 @@ impl MoveData {
             given_from_to: given_move,
             figure_moved,
             figure_captured,
-            move_type: Normal.into()
+            move_type: Normal
         }
     }
 
@@ -75,13 +76,21 @@ impl MoveData {
             figure_moved: FigureType::King,
             figure_captured: None,
             move_type: Castling {
-                castling_type: castling_type,
+                castling_type,
                 king_move: FromTo::new(king_from, king_to),
                 rook_move: FromTo::new(rook_from, rook_to),
             },
         }
     }
 
+    /// the move as it was given (e.g. to compress it again)
+    pub fn given_move(&self) -> Move {
+        match self.move_type {
+            PawnPromotion { promoted_to } => Move::new_with_promotion(self.given_from_to, promoted_to),
+            _ => Move::new(self.given_from_to),
+        }
+    }
+
     pub fn did_catch_figure(&self) -> bool {
         self.figure_captured.is_some()
     }
@@ -94,6 +103,82 @@ impl MoveData {
     pub fn did_make_progress(&self) -> bool {
         self.is_pawn_move() || self.did_catch_figure()
     }
+
+    /// classifies the move (e.g. to pick a sound effect or animation), `game_state_after` is the position
+    /// the move led to. Checks are detected there, since the move alone doesn't tell.
+    pub fn effect(&self, game_state_after: &GameState) -> MoveEffect {
+        if game_state_after.is_in_check(game_state_after.turn_by) {
+            return if game_state_after.is_checkmate() { MoveEffect::Mate } else { MoveEffect::Check };
+        }
+        match self.move_type {
+            PawnPromotion { .. } => MoveEffect::Promote,
+            Castling { .. } => MoveEffect::Castle,
+            _ if self.did_catch_figure() => MoveEffect::Capture,
+            _ => MoveEffect::Quiet,
+        }
+    }
+
+    /// the squares a viewer should highlight for this move
+    pub fn squares(&self) -> MoveSquares {
+        match self.move_type {
+            Castling { king_move, rook_move, .. } => MoveSquares {
+                from: king_move.from,
+                to: king_move.to,
+                rook_move: Some(rook_move),
+                captured_pawn_pos: None,
+            },
+            EnPassant { captured_pawn_pos } => MoveSquares {
+                from: self.given_from_to.from,
+                to: self.given_from_to.to,
+                rook_move: None,
+                captured_pawn_pos: Some(captured_pawn_pos),
+            },
+            _ => MoveSquares {
+                from: self.given_from_to.from,
+                to: self.given_from_to.to,
+                rook_move: None,
+                captured_pawn_pos: None,
+            },
+        }
+    }
+}
+
+/// the squares involved in a move (see [MoveData::squares])
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MoveSquares {
+    /// where the moving figure came from (the king's square when castling, not the rook it was pointed to)
+    pub from: Position,
+    pub to: Position,
+    /// the move of the rook when castling
+    pub rook_move: Option<FromTo>,
+    /// the square of the pawn that was captured en passant (which differs from `to`)
+    pub captured_pawn_pos: Option<Position>,
+}
+
+/// what a move does as far as viewers are concerned (see [MoveData::effect]).
+/// If more than one applies, the most notable one is used (Mate before Check before Promote before Castle before Capture).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MoveEffect {
+    Quiet,
+    Capture,
+    Castle,
+    Promote,
+    Check,
+    Mate,
+}
+
+/// prints the move with the code of its MoveType appended (e.g. "e1h1c" for king side castling,
+/// "e5d6e" for en-passant or "e7e8Q" for a promotion), normal moves are printed without code.
+/// The result can be parsed back into a Move.
+impl Display for MoveData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.given_from_to)?;
+        if self.move_type != Normal {
+            write!(f, "{}", self.move_type)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -102,10 +187,11 @@ pub struct FromTo {
     pub to: Position,
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
+#[allow(clippy::derived_hash_with_manual_eq)]
 impl Hash for FromTo {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_usize((self.from.index<< 6) + self.to.index);
+        self.from.hash(state);
+        self.to.hash(state);
     }
 }
 
@@ -127,6 +213,21 @@ impl FromTo {
             to: self.to.toggle_row(),
         }
     }
+
+    pub fn flip_files(&self) -> Self {
+        Self {
+            from: self.from.toggle_column(),
+            to: self.to.toggle_column(),
+        }
+    }
+}
+
+impl TryFrom<&str> for FromTo {
+    type Error = ChessError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        code.parse()
+    }
 }
 
 impl str::FromStr for FromTo {
@@ -152,6 +253,8 @@ impl fmt::Debug for FromTo {
     }
 }
 
+/// Move deliberately doesn't implement Default (there is no sensible default move),
+/// use `Option<Move>` (e.g. in fixed size arrays) for slots that may not contain a move yet.
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub struct Move {
     pub from_to: FromTo,
@@ -181,6 +284,28 @@ impl Move {
             promotion_type: self.promotion_type,
         }
     }
+
+    /// mirrors the move between the king and the queen side (a-file <-> h-file)
+    pub fn flip_files(&self) -> Self {
+        Self {
+            from_to: self.from_to.flip_files(),
+            promotion_type: self.promotion_type,
+        }
+    }
+}
+
+impl From<MoveData> for Move {
+    fn from(move_data: MoveData) -> Self {
+        move_data.given_move()
+    }
+}
+
+impl TryFrom<&str> for Move {
+    type Error = ChessError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        code.parse()
+    }
 }
 
 impl str::FromStr for Move {
@@ -194,14 +319,28 @@ impl str::FromStr for Move {
             }
             5 => {
                 let from_to = code[0..4].parse::<FromTo>()?;
-                let pawn_move_type = code[4..5].parse::<PromotionType>()?;
-                Ok(Move::new_with_promotion(from_to, pawn_move_type))
+                // the annotations of castling and en-passant (see Display of MoveData) only have to fit the move
+                let fits_annotation = match &code[4..5] {
+                    "c" => Some(from_to.from.row == from_to.to.row && from_to.from.column < from_to.to.column),
+                    "C" => Some(from_to.from.row == from_to.to.row && from_to.from.column > from_to.to.column),
+                    "e" => Some(
+                        (from_to.from.row == 3 || from_to.from.row == 4)
+                            && from_to.from.get_row_distance(from_to.to) == 1
+                            && (from_to.from.column - from_to.to.column).abs() == 1
+                    ),
+                    _ => None,
+                };
+                match fits_annotation {
+                    Some(true) => Ok(Move::new(from_to)),
+                    Some(false) => Err(ChessError::new(ErrorKind::IllegalFormat, format!("the annotation of {code} doesn't fit the move"))),
+                    None => {
+                        let pawn_move_type = code[4..5].parse::<PromotionType>()?;
+                        Ok(Move::new_with_promotion(from_to, pawn_move_type))
+                    }
+                }
             }
             _ => {
-                return Err(ChessError {
-                    msg: format!("illegal move format: {}", code),
-                    kind: ErrorKind::IllegalFormat,
-                })
+                Err(ChessError::new(ErrorKind::IllegalFormat, format!("illegal move format: {}", code)))
             }
         }
     }
@@ -229,19 +368,7 @@ impl Hash for Move {
     }
 }
 
-// Default is needed, so that Move can be stored in a TinyVec
-impl Default for Move {
-    fn default() -> Self {
-        Move::new(FromTo::new(
-            // default values should never be used, so illegal values are fine
-            // (they are necessary for TinyVec)
-            Position::new_unchecked(9, 9),
-            Position::new_unchecked(9, 9),
-        ))
-    }
-}
-
-pub fn toggle_rows(moves: &Vec<Move>) -> Vec<Move> {
+pub fn toggle_rows(moves: &[Move]) -> Vec<Move> {
     moves.iter().map(|a_move| a_move.toggle_rows()).collect()
 }
 
@@ -284,10 +411,7 @@ impl str::FromStr for PromotionType {
             "R" => Ok(PromotionType::Rook),
             "N" => Ok(PromotionType::Knight),
             "B" => Ok(PromotionType::Bishop),
-            _ => Err(ChessError{
-                msg: format!("unknown pawn promotion type: {}. Only 'QRNB' are allowed.", s),
-                kind: ErrorKind::IllegalFormat
-            }),
+            _ => Err(ChessError::new(ErrorKind::IllegalFormat, format!("unknown pawn promotion type: {}. Only 'QRNB' are allowed.", s))),
         }
     }
 }
@@ -327,6 +451,7 @@ impl Display for MoveType {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum MoveType {
     Normal,
     PawnPromotion{ promoted_to: PromotionType },
@@ -340,8 +465,26 @@ pub enum MoveType {
 #[cfg(test)]
 mod tests {
     use rstest::*;
-    use crate::base::a_move::{FromTo, Move, PromotionType};
+    use crate::base::a_move::{FromTo, Move, MoveData, MoveEffect, PromotionType};
     use crate::base::position::Position;
+    use crate::game::game_state::GameState;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        game_state, a_move, expected_effect,
+        case("", "e2e4", MoveEffect::Quiet),
+        case("e2e4 d7d5", "e4d5", MoveEffect::Capture),
+        case("white ♔e1 ♖h1 ♚a8", "e1h1", MoveEffect::Castle),
+        case("white ♔h1 ♙b7 ♜a8 ♚e8", "b7a8N", MoveEffect::Promote),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8Q", MoveEffect::Check),
+        case("f2f3 e7e5 g2g4", "d8h4", MoveEffect::Mate),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_move_effect(game_state: GameState, a_move: Move, expected_effect: MoveEffect) {
+        let (game_state_after, move_data) = game_state.do_move(a_move).unwrap();
+        assert_eq!(move_data.effect(&game_state_after), expected_effect);
+    }
 
     #[rstest(
         from_to, from, to,
@@ -375,10 +518,85 @@ mod tests {
     ) {
         assert_eq!(from, a_move.from_to.from);
         assert_eq!(to, a_move.from_to.to);
-        let given_promotion_type: Option<PromotionType> = promotes_to.map(|it| it.parse().expect(format!("unknown PromotionType: {it}").as_str()));
+        let given_promotion_type: Option<PromotionType> = promotes_to.map(|it| it.parse().unwrap_or_else(|_| panic!("unknown PromotionType: {it}")));
         assert_eq!(given_promotion_type, a_move.promotion_type);
     }
 
+    #[rstest(
+        code, expected_move,
+        case("e1h1c", "e1h1"),
+        case("e8a8C", "e8a8"),
+        case("e5d6e", "e5d6"),
+        case("d4e3e", "d4e3"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_move_from_annotated_str(
+        code: &str,
+        expected_move: Move,
+    ) {
+        assert_eq!(code.parse::<Move>().unwrap(), expected_move);
+    }
+
+    #[rstest(
+        code,
+        case("e1h1C"),
+        case("e1a1c"),
+        case("e1e2c"),
+        case("e2e4e"),
+        case("e5e6e"),
+        case("e2e4x"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_move_from_str_fails(
+        code: &str,
+    ) {
+        assert!(code.parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn test_move_data_display_can_be_parsed_back() {
+        let moves: Vec<Move> = crate::base::util::tests::parse_to_vec(
+            "e2e4, a7a6, e4e5, d7d5, e5d6, g8f6, g1f3, b7b5, f1e2, c8b7, e1h1, b7f3, d6c7, b8c6, c7d8Q", ","
+        ).unwrap();
+        let mut game_state = GameState::classic();
+        let moves_data: Vec<MoveData> = moves.iter().map(|a_move| {
+            let (next_game_state, move_data) = game_state.do_move(*a_move).unwrap();
+            game_state = next_game_state;
+            move_data
+        }).collect();
+        let codes: Vec<String> = moves_data.iter().map(|move_data| move_data.to_string()).collect();
+        assert_eq!(codes[4], "e5d6e");
+        assert_eq!(codes[10], "e1h1c");
+        assert_eq!(codes[14], "c7d8Q");
+        let parsed_moves: Vec<Move> = codes.iter().map(|code| code.parse().unwrap()).collect();
+        assert_eq!(parsed_moves, moves);
+        let given_moves: Vec<Move> = moves_data.into_iter().map(Move::from).collect();
+        assert_eq!(given_moves, moves);
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        assert_eq!(Move::try_from("c7d8Q").unwrap(), Move::new_with_promotion(FromTo::try_from("c7d8").unwrap(), PromotionType::Queen));
+        assert_eq!(FromTo::try_from("e2e4").unwrap(), FromTo::new(Position::try_from("e2").unwrap(), Position::try_from("e4").unwrap()));
+        assert!(Move::try_from("e2e4x").is_err());
+        assert!(Position::try_from("i1").is_err());
+    }
+
+    #[rstest(
+        a_move, expected_move,
+        case("b1c3", "g1f3"),
+        case("e1a1", "d1h1"),
+        case("g7f8Q", "b7c8Q"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_flip_files(
+        a_move: Move,
+        expected_move: Move,
+    ) {
+        assert_eq!(a_move.flip_files(), expected_move);
+        assert_eq!(expected_move.flip_files(), a_move);
+    }
+
     #[rstest(
         given_promotion_type,
         case("R"),
@@ -0,0 +1,74 @@
+use std::fmt::*;
+use crate::base::a_move::Move;
+use crate::base::position::Position;
+use crate::game::game_state::IllegalMoveReason;
+
+#[derive(Debug)]
+pub struct ChessError {
+    pub msg: String,
+    pub kind: ErrorKind,
+    /// the error (e.g. of parsing a number or reading a file) that caused this one, see [std::error::Error::source]
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl ChessError {
+    pub fn new(kind: ErrorKind, msg: impl Into<String>) -> ChessError {
+        ChessError {
+            msg: msg.into(),
+            kind,
+            source: None,
+        }
+    }
+
+    /// the error (e.g. of parsing a number or reading a file) that caused this one
+    pub fn with_source(mut self, source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> ChessError {
+        self.source = Some(source.into());
+        self
+    }
+}
+
+impl Display for ChessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "{:?}: {}", self.kind, self.msg)
+    }
+}
+
+impl std::error::Error for ChessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    IllegalConfig,
+    IllegalFormat,
+    /// a one-char move (e.g. of a hand-edited game) could have been made by more than one figure.
+    /// The suggested encodings (url-safe base64, one per origin) name the origin and would have been unambiguous.
+    AmbiguousOrigin {
+        target: Position,
+        origins: Vec<Position>,
+        suggested_encodings: Vec<String>,
+    },
+    IllegalMove,
+    /// the move at `half_move_index` of the game can't be played (this is what [ErrorKind::IllegalMove] is
+    /// when the error can be pinned to a move of a game, e.g. by compress)
+    IllegalMoveInGame {
+        half_move_index: usize,
+        a_move: Move,
+        reason: IllegalMoveReason,
+    },
+    /// the char at `char_index` isn't part of the alphabet.
+    /// The index points into the url-safe base64 form of the game (without format header)
+    InvalidChar {
+        char_index: usize,
+        found: char,
+    },
+    /// the encoded game was altered (e.g. mistyped), so it's not worth to look at its moves
+    ChecksumMismatch,
+    /// the input exceeds a limit set by the caller (see DecompressOptions)
+    LimitExceeded,
+    /// reading or writing (e.g. an archive file) failed
+    Io,
+}
@@ -0,0 +1,7 @@
+pub mod a_move;
+pub(crate) mod direction;
+pub mod errors;
+pub mod position;
+pub mod color;
+pub mod util;
+
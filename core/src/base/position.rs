@@ -1,60 +1,82 @@
 use std::fmt;
 use std::fmt::Formatter;
+use std::hash::{Hash, Hasher};
 use std::iter::{Iterator};
 use std::ops::Range;
 use std::str;
 use crate::base::color::Color;
 use crate::base::direction::Direction;
 use crate::base::errors::{ChessError, ErrorKind};
-use crate::game::board::{Board, FieldContent, USIZE_RANGE_063};
+use crate::game::board::{Board, FieldContent};
 
-#[derive(Copy, Clone, Eq, Hash)]
+#[derive(Copy, Clone, Eq)]
 pub struct Position {
-    pub index: usize,
+    pub index: SquareIndex,
     pub column: i8,
     pub row: i8,
 }
 
+/// the index of a square (a1 is 0, h1 is 7, a2 is 8, ..., h8 is 63), it can only be created for valid squares
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SquareIndex(u8);
+
+impl SquareIndex {
+    pub const fn new(index: usize) -> Option<SquareIndex> {
+        if index < 64 {
+            Some(SquareIndex(index as u8))
+        } else {
+            None
+        }
+    }
+
+    pub const fn get(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<SquareIndex> for Position {
+    fn from(index: SquareIndex) -> Self {
+        Position::from_index_unchecked(index.get())
+    }
+}
+
+impl TryFrom<usize> for SquareIndex {
+    type Error = ChessError;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        SquareIndex::new(index).ok_or_else(|| ChessError::new(
+            ErrorKind::IllegalConfig,
+            format!("square index has to be smaller than 64 but is {index}"),
+        ))
+    }
+}
+
 impl Position {
-    pub fn new_checked(column: i8, row: i8) -> Option<Position> {
-        if !(I8_RANGE_07.contains(&column) && I8_RANGE_07.contains(&row)) {
+    pub const fn new_checked(column: i8, row: i8) -> Option<Position> {
+        if column < 0 || column > 7 || row < 0 || row > 7 {
             return None
         }
         Some(Position::new_unchecked(column, row))
     }
 
+    /// panics if column or row isn't in 0..8 (use [Position::new_checked] for values that aren't known to be in range)
     pub const fn new_unchecked(column: i8, row: i8) -> Position {
-        // debug_assert!(
-        //     I8_RANGE_07.contains(&column) && I8_RANGE_07.contains(&row),
-        //     "column and row were expected to be 0..64 but were column: {} and row: {}",
-        //     column, row
-        // );
+        assert!(column >= 0 && column < 8 && row >= 0 && row < 8, "column and row were expected to be 0..8");
         Position {
-            index: ((row*8)+column) as usize,
+            index: SquareIndex(((row*8)+column) as u8),
             column,
             row,
         }
     }
 
-    pub fn from_index_unchecked(index: usize) -> Position {
-        debug_assert!(
-            USIZE_RANGE_063.contains(&index),
-            "index was expected to be 0..64 but was {}",
-            index
-        );
+    /// panics if the index isn't in 0..64
+    pub const fn from_index_unchecked(index: usize) -> Position {
+        assert!(index < 64, "index was expected to be 0..64");
         let i = index as i8;
-        let column = i % 8;
-        let row = i/8;
-        debug_assert!(
-          I8_RANGE_07.contains(&column) && I8_RANGE_07.contains(&row),
-          "column and row were expected to be 0..64 but were column: {} and row: {}",
-          column, row
-        );
-
         Position {
-            index: ((row*8)+column) as usize,
-            column,
-            row,
+            index: SquareIndex(index as u8),
+            column: i % 8,
+            row: i / 8,
         }
     }
 
@@ -149,18 +171,16 @@ impl Position {
                 None => { 1 }
                 Some(pos) => {
                     match board.get_figure(*pos) {
-                        None => { 1 }
-                        Some(figure) => {
-                            if figure.color == fig_color { 0 } else { 1 }
-                        }
+                        Some(figure) if figure.color == fig_color => { 0 }
+                        _ => { 1 }
                     }
                 }
             }
         })
     }
 
-    pub fn reachable_directed_positions<'a, 'b>(
-        &'a self,
+    pub fn reachable_directed_positions<'b>(
+        &self,
         fig_color: Color,
         direction: Direction,
         board: &'b Board,
@@ -168,8 +188,8 @@ impl Position {
         DirectedPosIterator::new(*self, fig_color, direction, board)
     }
 
-    pub fn reachable_knight_positions<'a, 'b>(
-        &'a self,
+    pub fn reachable_knight_positions<'b>(
+        &self,
         knight_color: Color,
         board: &'b Board,
     ) -> KnightPosIterator<'b> {
@@ -190,12 +210,24 @@ impl Position {
         column_diff != 0 && row_diff != 0 && (column_diff + row_diff) == 3
     }
 
-    pub fn toggle_row(&self) -> Position {
+    pub const fn toggle_row(&self) -> Position {
         Position::new_unchecked(
             self.column, 7-self.row,
         )
     }
 
+    pub const fn toggle_column(&self) -> Position {
+        Position::new_unchecked(
+            7-self.column, self.row,
+        )
+    }
+
+    /// parses a position given from black's perspective (with the board rotated by 180°, so "a1" is h8)
+    pub fn parse_flipped(code: &str) -> Result<Position, ChessError> {
+        let pos = code.parse::<Position>()?;
+        Ok(pos.toggle_row().toggle_column())
+    }
+
     pub fn get_direction(&self, to: Position) -> Option<Direction> {
         if *self == to {
            return None;
@@ -233,26 +265,28 @@ impl Position {
     }
 }
 
+impl TryFrom<&str> for Position {
+    type Error = ChessError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        code.parse()
+    }
+}
+
 impl str::FromStr for Position {
     type Err = ChessError;
 
     fn from_str(code: &str) -> Result<Self, Self::Err> {
         let mut char_iter = code.chars();
         if code.len()!=2 {
-            return Err(ChessError{
-                msg: format!("Position str: {code} should consist of 2 chars not {}", code.len()),
-                kind: ErrorKind::IllegalFormat
-            });
+            return Err(ChessError::new(ErrorKind::IllegalFormat, format!("Position str: {code} should consist of 2 chars not {}", code.len())));
         }
 
         fn get_index(maybe_char: Option<char>, offset: u8, index_type: &str, code: &str) -> Result<i8, ChessError> {
             let ascii_char = maybe_char.unwrap();
             let ascii_index = ascii_char as u8;
             if ascii_index<offset || ascii_index>=(offset+8) {
-                return Err(ChessError{
-                    msg: format!("illegal {index_type} char '{ascii_char}' in Position code: {code}"),
-                    kind: ErrorKind::IllegalFormat
-                })
+                return Err(ChessError::new(ErrorKind::IllegalFormat, format!("illegal {index_type} char '{ascii_char}' in Position code: {code}")))
             };
             Ok((ascii_index - offset) as i8)
         }
@@ -270,6 +304,12 @@ impl PartialEq for Position {
     }
 }
 
+impl Hash for Position {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
 impl fmt::Display for Position {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}{}", (self.column + 97) as u8 as char, (self.row+49) as u8 as char)
@@ -282,6 +322,25 @@ impl fmt::Debug for Position {
     }
 }
 
+/// a position is serialized as its index (0..64)
+#[cfg(feature = "serde")]
+impl serde::Serialize for Position {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.index.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Position {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Position, D::Error> {
+        let index = <u8 as serde::Deserialize>::deserialize(deserializer)?;
+        let Some(index) = SquareIndex::new(index as usize) else {
+            return Err(serde::de::Error::custom(format!("position index has to be smaller than 64 but is {index}")));
+        };
+        Ok(Position::from(index))
+    }
+}
+
 pub struct DirectedPosIterator<'a> {
     latest_position: Option<Position>,
     direction: Direction,
@@ -295,7 +354,7 @@ impl DirectedPosIterator<'_> {
         fig_color: Color,
         direction: Direction,
         board: &Board,
-    ) -> DirectedPosIterator {
+    ) -> DirectedPosIterator<'_> {
         DirectedPosIterator {
             latest_position: Some(fig_pos),
             direction,
@@ -309,15 +368,8 @@ impl Iterator for DirectedPosIterator<'_> {
     type Item = Position;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let latest_pos = match self.latest_position {
-            Some(pos) => pos,
-            None => return None,
-        };
-
-        let new_pos = match latest_pos.step(self.direction) {
-            Some(pos) => pos,
-            None => return None,
-        };
+        let latest_pos = self.latest_position?;
+        let new_pos = latest_pos.step(self.direction)?;
         let some_new_pos = Some(new_pos);
 
         match self.board.get_content_type(new_pos, self.moving_fig_color) {
@@ -346,7 +398,7 @@ impl KnightPosIterator<'_> {
         knight_position: Position,
         knight_color: Color,
         board: &Board,
-    ) -> KnightPosIterator {
+    ) -> KnightPosIterator<'_> {
         KnightPosIterator {
             knight_pos: knight_position,
             knight_color,
@@ -391,15 +443,80 @@ impl Iterator for KnightPosIterator<'_> {
     }
 }
 
-// Default is needed, so that Move can be stored in a TinyVec/ArrayVec
-impl Default for Position {
-    fn default() -> Self {
-        Position::new_unchecked(0, 0)
-    }
-}
-
 pub const I8_RANGE_07: Range<i8> = 0..8;
 
+pub const A1: Position = Position::new_unchecked(0, 0);
+pub const B1: Position = Position::new_unchecked(1, 0);
+pub const C1: Position = Position::new_unchecked(2, 0);
+pub const D1: Position = Position::new_unchecked(3, 0);
+pub const E1: Position = Position::new_unchecked(4, 0);
+pub const F1: Position = Position::new_unchecked(5, 0);
+pub const G1: Position = Position::new_unchecked(6, 0);
+pub const H1: Position = Position::new_unchecked(7, 0);
+
+pub const A2: Position = Position::new_unchecked(0, 1);
+pub const B2: Position = Position::new_unchecked(1, 1);
+pub const C2: Position = Position::new_unchecked(2, 1);
+pub const D2: Position = Position::new_unchecked(3, 1);
+pub const E2: Position = Position::new_unchecked(4, 1);
+pub const F2: Position = Position::new_unchecked(5, 1);
+pub const G2: Position = Position::new_unchecked(6, 1);
+pub const H2: Position = Position::new_unchecked(7, 1);
+
+pub const A3: Position = Position::new_unchecked(0, 2);
+pub const B3: Position = Position::new_unchecked(1, 2);
+pub const C3: Position = Position::new_unchecked(2, 2);
+pub const D3: Position = Position::new_unchecked(3, 2);
+pub const E3: Position = Position::new_unchecked(4, 2);
+pub const F3: Position = Position::new_unchecked(5, 2);
+pub const G3: Position = Position::new_unchecked(6, 2);
+pub const H3: Position = Position::new_unchecked(7, 2);
+
+pub const A4: Position = Position::new_unchecked(0, 3);
+pub const B4: Position = Position::new_unchecked(1, 3);
+pub const C4: Position = Position::new_unchecked(2, 3);
+pub const D4: Position = Position::new_unchecked(3, 3);
+pub const E4: Position = Position::new_unchecked(4, 3);
+pub const F4: Position = Position::new_unchecked(5, 3);
+pub const G4: Position = Position::new_unchecked(6, 3);
+pub const H4: Position = Position::new_unchecked(7, 3);
+
+pub const A5: Position = Position::new_unchecked(0, 4);
+pub const B5: Position = Position::new_unchecked(1, 4);
+pub const C5: Position = Position::new_unchecked(2, 4);
+pub const D5: Position = Position::new_unchecked(3, 4);
+pub const E5: Position = Position::new_unchecked(4, 4);
+pub const F5: Position = Position::new_unchecked(5, 4);
+pub const G5: Position = Position::new_unchecked(6, 4);
+pub const H5: Position = Position::new_unchecked(7, 4);
+
+pub const A6: Position = Position::new_unchecked(0, 5);
+pub const B6: Position = Position::new_unchecked(1, 5);
+pub const C6: Position = Position::new_unchecked(2, 5);
+pub const D6: Position = Position::new_unchecked(3, 5);
+pub const E6: Position = Position::new_unchecked(4, 5);
+pub const F6: Position = Position::new_unchecked(5, 5);
+pub const G6: Position = Position::new_unchecked(6, 5);
+pub const H6: Position = Position::new_unchecked(7, 5);
+
+pub const A7: Position = Position::new_unchecked(0, 6);
+pub const B7: Position = Position::new_unchecked(1, 6);
+pub const C7: Position = Position::new_unchecked(2, 6);
+pub const D7: Position = Position::new_unchecked(3, 6);
+pub const E7: Position = Position::new_unchecked(4, 6);
+pub const F7: Position = Position::new_unchecked(5, 6);
+pub const G7: Position = Position::new_unchecked(6, 6);
+pub const H7: Position = Position::new_unchecked(7, 6);
+
+pub const A8: Position = Position::new_unchecked(0, 7);
+pub const B8: Position = Position::new_unchecked(1, 7);
+pub const C8: Position = Position::new_unchecked(2, 7);
+pub const D8: Position = Position::new_unchecked(3, 7);
+pub const E8: Position = Position::new_unchecked(4, 7);
+pub const F8: Position = Position::new_unchecked(5, 7);
+pub const G8: Position = Position::new_unchecked(6, 7);
+pub const H8: Position = Position::new_unchecked(7, 7);
+
 
 //------------------------------Tests------------------------
 
@@ -418,7 +535,27 @@ mod tests {
     )]
     fn test_position_unchecked_new(column: i8, row: i8, expected_index: usize) {
         let pos = Position::new_unchecked(column, row);
-        assert_eq!(pos.index, expected_index);
+        assert_eq!(pos.index.get(), expected_index);
+    }
+
+    #[rstest(
+    column, row,
+    case(-1, 0),
+    case(8, 0),
+    case(0, -1),
+    case(0, 8),
+    case(9, -1),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    #[should_panic(expected = "column and row were expected to be 0..8")]
+    fn test_position_unchecked_new_panics_out_of_range(column: i8, row: i8) {
+        Position::new_unchecked(column, row);
+    }
+
+    #[test]
+    #[should_panic(expected = "index was expected to be 0..64")]
+    fn test_position_from_index_unchecked_panics_out_of_range() {
+        Position::from_index_unchecked(64);
     }
 
     #[rstest(
@@ -432,7 +569,57 @@ mod tests {
     fn test_position_from_str(pos: Position, expected_column: i8, expected_row: i8, expected_index: usize) {
         assert_eq!(pos.column, expected_column);
         assert_eq!(pos.row, expected_row);
-        assert_eq!(pos.index, expected_index);
+        assert_eq!(pos.index.get(), expected_index);
+    }
+
+    #[rstest(
+    square, expected_pos,
+    case(A1, "a1"),
+    case(E4, "e4"),
+    case(H1, "h1"),
+    case(A8, "a8"),
+    case(H8, "h8"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_square_constants(square: Position, expected_pos: Position) {
+        assert_eq!(square, expected_pos);
+        assert_eq!(square.index, expected_pos.index);
+    }
+
+    #[rstest(
+    index, expected_pos,
+    case(0, Some("a1")),
+    case(28, Some("e4")),
+    case(63, Some("h8")),
+    case(64, None),
+    case(81, None),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_square_index(index: usize, expected_pos: Option<&str>) {
+        let actual_pos = SquareIndex::new(index).map(|index| Position::from(index).to_string());
+        assert_eq!(actual_pos.as_deref(), expected_pos);
+        assert_eq!(SquareIndex::try_from(index).is_ok(), expected_pos.is_some());
+    }
+
+    #[test]
+    fn test_positions_can_be_computed_at_compile_time() {
+        const D5: Option<Position> = Position::new_checked(3, 4);
+        const FLIPPED_A1: Position = A1.toggle_row().toggle_column();
+        assert_eq!(D5.unwrap().to_string(), "d5");
+        assert_eq!(FLIPPED_A1, H8);
+        assert_eq!(Position::from_index_unchecked(28), E4);
+    }
+
+    #[rstest(
+    flipped_pos, expected_pos,
+    case("a1", "h8"),
+    case("h8", "a1"),
+    case("e2", "d7"),
+    case("c6", "f3"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_flipped(flipped_pos: &str, expected_pos: Position) {
+        assert_eq!(Position::parse_flipped(flipped_pos).unwrap(), expected_pos);
     }
 
     #[rstest(
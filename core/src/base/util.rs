@@ -2,6 +2,7 @@ use std::fmt;
 use std::fmt::Display;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Disallowable {
     value: bool,
 }
@@ -28,50 +29,45 @@ impl Display for Disallowable {
     }
 }
 
-pub fn vec_to_str<A: Display>(vec: &Vec<A>, separator: &str) -> String {
+pub fn vec_to_str<A: Display>(vec: &[A], separator: &str) -> String {
     format!("[{}]", vec.iter().map(|pos|format!("{pos}")).collect::<Vec<String>>().join(separator))
 }
 
-#[cfg(test)]
+// also used by the tests of chess_compress_urlsafe-codec (through the test-util feature)
+#[cfg(any(test, feature = "test-util"))]
+#[doc(hidden)]
 pub mod tests {
     use std::collections::HashSet;
     use std::fmt::Display;
     use std::hash::Hash;
     use std::str::FromStr;
-    use itertools::Itertools;
     use crate::base::errors::ChessError;
     use crate::base::errors::ErrorKind::IllegalConfig;
 
-    pub fn vec_has_uniquely_same_elements_as_set<A: Eq>(vec: &Vec<A>, set: &HashSet<A>) -> bool {
+    pub fn vec_has_uniquely_same_elements_as_set<A: Eq>(vec: &[A], set: &HashSet<A>) -> bool {
         if vec.len() != set.len() {
             return false;
         };
 
-        vec.iter().all(|it|set.iter().contains(it))
+        vec.iter().all(|it|set.iter().any(|element| element == it))
     }
 
     pub fn parse_to_vec<A: FromStr<Err=ChessError>>(str: &str, separator: &str) -> Result<Vec<A>, ChessError> {
         if separator.is_empty() {
-            return Err(ChessError{
-                msg: "separator mus not be empty".to_string(),
-                kind: IllegalConfig
-            })
+            return Err(ChessError::new(IllegalConfig, "separator mus not be empty".to_string()))
         }
         str.split(separator).map(str::trim).filter(|it| !it.is_empty()).map(|it|{
             it.parse::<A>()
         }).collect()
     }
 
-    pub fn vec_into_set<A: Copy + Hash + Eq>(vec: &Vec<A>) -> HashSet<A> {
-        vec.iter().map(|it| *it).collect()
+    pub fn vec_into_set<A: Copy + Hash + Eq>(vec: &[A]) -> HashSet<A> {
+        vec.iter().copied().collect()
     }
 
     pub fn parse_to_set<A: FromStr<Err=ChessError> + Hash + Eq>(str: &str, separator: &str) -> Result<HashSet<A>, ChessError> {
         if separator.is_empty() {
-            return Err(ChessError{
-                msg: "separator mus not be empty".to_string(),
-                kind: IllegalConfig
-            })
+            return Err(ChessError::new(IllegalConfig, "separator mus not be empty".to_string()))
         }
         str.split(separator).map(str::trim).filter(|it| !it.is_empty()).map(|it| {
             it.parse::<A>()
@@ -7,6 +7,7 @@ use crate::base::errors::{ChessError, ErrorKind};
 use crate::base::position::Position;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Figure {
     pub fig_type: FigureType,
     pub color: Color,
@@ -23,6 +24,20 @@ impl Figure {
             King => {if self.color == Color::White {'K'} else {'k'}}
         }
     }
+
+    pub fn from_fen_char(fen_char: char) -> Option<Figure> {
+        let fig_type = match fen_char.to_ascii_lowercase() {
+            'p' => Pawn,
+            'r' => Rook,
+            'n' => Knight,
+            'b' => Bishop,
+            'q' => Queen,
+            'k' => King,
+            _ => return None,
+        };
+        let color = if fen_char.is_ascii_uppercase() {Color::White} else {Color::Black};
+        Some(Figure{fig_type, color})
+    }
 }
 
 impl FromStr for Figure {
@@ -42,10 +57,7 @@ impl FromStr for Figure {
             "♛" => Ok(Figure { fig_type: Queen, color: Color::Black }),
             "♔" => Ok(Figure { fig_type: King, color: Color::White }),
             "♚" => Ok(Figure { fig_type: King, color: Color::Black }),
-            _ => Err(ChessError{
-                msg: format!("unexpected character, utf-chess symbol like ♙ expected but got {}", desc),
-                kind: ErrorKind::IllegalFormat,
-            })
+            _ => Err(ChessError::new(ErrorKind::IllegalFormat, format!("unexpected character, utf-chess symbol like ♙ expected but got {}", desc)))
         }
     }
 }
@@ -86,6 +98,8 @@ impl FromStr for FigureAndPosition {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum FigureType {
     Pawn,
     Rook,
@@ -125,10 +139,7 @@ impl FromStr for FigureType {
             "B" => Ok(Bishop),
             "Q" => Ok(Queen),
             "K" => Ok(King),
-            _ => Err(ChessError{
-                msg: format!("unexpected character, char P, R, N, B, Q, or K expected but got {}", desc),
-                kind: ErrorKind::IllegalFormat,
-            })
+            _ => Err(ChessError::new(ErrorKind::IllegalFormat, format!("unexpected character, char P, R, N, B, Q, or K expected but got {}", desc)))
         }
     }
 }
@@ -9,6 +9,8 @@ use crate::figure::figure::{Figure, FigureType};
 use crate::game::board::Board;
 use crate::game::game_state::GameState;
 
+/// returns the positions of the figures of the player whose turn it is that can move to target,
+/// ascending by their index (a1=0, b1=1, ..., h8=63), so e.g. SAN disambiguation doesn't depend on the search order
 pub fn get_positions_to_reach_target_from(
     target: Position,
     game_state: &GameState,
@@ -18,22 +20,52 @@ pub fn get_positions_to_reach_target_from(
     if let Some(figure) = game_state.board.get_figure(target) {
         // solve castling outside of this method
         if figure.color==active_color {
-            return Err(ChessError {
-                msg: format!("move captures figure of same color on {target}"),
-                kind: ErrorKind::IllegalMove,
-            })
+            return Err(ChessError::new(ErrorKind::IllegalMove, format!("move captures figure of same color on {target}")))
         }
     }
 
-    let origins = inner_get_positions_to_reach_target_from(
+    let mut origins = inner_get_positions_to_reach_target_from(
         target,
         active_color,
         &game_state.board,
         game_state.en_passant_intercept_pos
     );
+    origins.sort_unstable_by_key(|origin| origin.index.get());
     Ok(origins)
 }
 
+/// returns the figures of the player whose turn it is that can move to target (and where they stand),
+/// exactly as the codec sees it: a move is encoded with one char if there is only one candidate.
+/// Pins are ignored (a pinned figure is still a candidate), castling isn't included
+/// and a target occupied by an own figure has no candidates.
+pub fn origin_candidates(game_state: &GameState, target: Position) -> Vec<(Position, FigureType)> {
+    get_positions_to_reach_target_from(target, game_state).unwrap_or_default().into_iter()
+        .map(|origin| {
+            let figure = game_state.board.get_figure(origin).expect("a candidate origin contains a figure");
+            (origin, figure.fig_type)
+        })
+        .collect()
+}
+
+/**
+ * returns true if a figure of attacker_color could capture a figure on target
+ * (or, if target is empty, a figure placed there).
+ */
+pub fn is_attacked_by(
+    target: Position,
+    attacker_color: Color,
+    board: &Board,
+) -> bool {
+    if board.is_empty(target) {
+        // straight pawn moves only count on empty targets and pawn captures only on occupied ones
+        let mut board_with_target = board.clone();
+        board_with_target.set_figure(target, Figure{fig_type: King, color: attacker_color.toggle()});
+        return is_attacked_by(target, attacker_color, &board_with_target);
+    }
+    // en-passant only captures pawns, so it's of no interest here
+    !inner_get_positions_to_reach_target_from(target, attacker_color, board, None).is_empty()
+}
+
 fn inner_get_positions_to_reach_target_from(
     target: Position,
     active_color: Color,
@@ -58,7 +90,7 @@ fn inner_get_positions_to_reach_target_from(
                         None
                     };
                 };
-                distance = distance + 1;
+                distance += 1;
                 current_pos = pos;
             } else {
                 return None;
@@ -98,9 +130,9 @@ fn inner_get_positions_to_reach_target_from(
     // check pawn moves
     if (active_color== White && target.row>1) || (active_color== Black && target.row<6) {
         fn contains_active_pawn(pos: Option<Position>, active_color: Color, board: &Board) -> bool {
-            pos.map(
+            pos.and_then(
                 |pos| board.get_figure(pos)
-            ).flatten().map(
+            ).map(
                 |figure| { figure.fig_type == Pawn && figure.color == active_color }
             ).unwrap_or(false)
         }
@@ -199,9 +231,29 @@ mod tests {
         let actual_origins = {
             let origins_vec: Vec<Position> = get_positions_to_reach_target_from(target, &game_state).unwrap();
             let origins_set: HashSet<Position> = vec_into_set(&origins_vec);
-            assert_eq!(true, vec_has_uniquely_same_elements_as_set(&origins_vec, &origins_set), "origins_vec contains duplicates. as vec: {}, as set: {}", vec_to_str(&origins_vec,","), set_to_str(&origins_set,","));
+            assert!(vec_has_uniquely_same_elements_as_set(&origins_vec, &origins_set), "origins_vec contains duplicates. as vec: {}, as set: {}", vec_to_str(&origins_vec,","), set_to_str(&origins_set,","));
+            assert!(origins_vec.windows(2).all(|pair| pair[0].index.get() < pair[1].index.get()), "origins aren't ordered by index: {}", vec_to_str(&origins_vec,","));
             origins_set
         };
         assert_eq!(actual_origins, expected_origins, "actual vs expected position set");
     }
-}
\ No newline at end of file
+
+    #[rstest(
+        game_state, target, expected_candidates,
+        case("", "c3", vec![("b1", Knight), ("c2", Pawn)]),
+        case("e2e4 e7e5", "e2", vec![("d1", Queen), ("e1", King), ("f1", Bishop), ("g1", Knight)]),
+        case("", "e1", vec![]),
+        case("a2a4 h7h5 a4a5 b7b5", "b6", vec![("a5", Pawn)]),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_origin_candidates(
+        game_state: GameState,
+        target: Position,
+        expected_candidates: Vec<(&str, FigureType)>,
+    ) {
+        let expected_candidates: Vec<(Position, FigureType)> = expected_candidates.into_iter()
+            .map(|(origin, figure_type)| (origin.parse().unwrap(), figure_type))
+            .collect();
+        assert_eq!(origin_candidates(&game_state, target), expected_candidates);
+    }
+}
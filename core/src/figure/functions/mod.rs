@@ -0,0 +1 @@
+pub mod is_reachable_by;
@@ -0,0 +1,3 @@
+pub mod functions;
+#[allow(clippy::module_inception)]
+pub mod figure;
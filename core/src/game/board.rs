@@ -111,15 +111,15 @@ impl Board {
     }
 
     pub fn get_figure(&self, pos: Position) -> Option<Figure> {
-        self.state[pos.index]
+        self.state[pos.index.get()]
     }
 
     /**
     * returns if a figure was caught/replaced on that position
     */
     pub fn set_figure(&mut self, pos: Position, figure: Figure) -> CaptureInfoOption {
-        let old_content = self.state[pos.index];
-        self.state[pos.index] = Some(figure);
+        let old_content = self.state[pos.index.get()];
+        self.state[pos.index.get()] = Some(figure);
 
         if let Some(old_figure) = old_content {
             CaptureInfoOption::from_some(old_figure, pos)
@@ -131,7 +131,7 @@ impl Board {
 
     pub fn clear_field(&mut self, pos: Position) {
         self.number_of_figures -= 1;
-        self.state[pos.index] = None;
+        self.state[pos.index.get()] = None;
     }
 
     pub fn contains_sufficient_material_to_continue(&self) -> bool {
@@ -200,7 +200,7 @@ impl Board {
     }
 
     pub fn contains_figure(&self, pos: Position, fig_type: FigureType, color: Color) -> bool {
-        match self.state[pos.index] {
+        match self.state[pos.index.get()] {
             None => false,
             Some(figure) => {
                 figure.fig_type == fig_type && figure.color == color
@@ -209,7 +209,7 @@ impl Board {
     }
 
     pub fn contains_color(&self, pos: Position, color: Color) -> bool {
-        match self.state[pos.index] {
+        match self.state[pos.index.get()] {
             None => false,
             Some(figure) => figure.color == color
         }
@@ -262,7 +262,7 @@ impl Display for Board {
         writeln!(f)?;
         for row_index in I8_RANGE_07.rev() {
             for column_index in I8_RANGE_07 {
-                let figure_index = Position::new_unchecked(column_index, row_index).index;
+                let figure_index = Position::new_unchecked(column_index, row_index).index.get();
                 let fig_option = self.state[figure_index];
                 match fig_option {
                     None => {write!(f, "_")},
@@ -275,6 +275,29 @@ impl Display for Board {
     }
 }
 
+/// the board is serialized as the sequence of its 64 fields
+#[cfg(feature = "serde")]
+impl serde::Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.state.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Board, D::Error> {
+        let fields = <Vec<Option<Figure>> as serde::Deserialize>::deserialize(deserializer)?;
+        let number_of_fields = fields.len();
+        let state: [Option<Figure>; 64] = fields.try_into().map_err(|_| {
+            serde::de::Error::invalid_length(number_of_fields, &"64 fields")
+        })?;
+        Ok(Board {
+            number_of_figures: state.iter().filter(|field| field.is_some()).count() as isize,
+            state,
+        })
+    }
+}
+
 pub const USIZE_RANGE_063: Range<usize> = 0..64;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -0,0 +1,25 @@
+use std::fmt;
+
+/// the result as far as it can be told from the final position
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    /// by stalemate or insufficient material
+    Draw,
+    /// the game may have been resigned, timed out or agreed drawn, but the moves alone don't tell
+    Undecided,
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Undecided => "*",
+        };
+        write!(f, "{result}")
+    }
+}
@@ -0,0 +1,1940 @@
+use std::{fmt,str};
+use crate::base::a_move::{CastlingType, EXPECTED_MAX_NUMBER_OF_MOVES, FromTo, Move, MoveData, MoveType, PromotionType};
+use crate::base::a_move::CastlingType::{KingSide, QueenSide};
+use crate::base::color::Color;
+use crate::base::direction::Direction;
+use crate::base::errors::{ChessError, ErrorKind};
+use crate::base::position::{A1, A8, E1, E8, H1, H8, Position};
+use crate::base::util::Disallowable;
+use crate::game::game_result::GameResult;
+use crate::figure::figure::{Figure, FigureAndPosition, FigureType};
+use crate::figure::functions::is_reachable_by::{get_positions_to_reach_target_from, is_attacked_by};
+use crate::game::board::{Board, CaptureInfoOption, USIZE_RANGE_063};
+use crate::game::position_history::PositionHistory;
+use crate::game::zobrist::{zobrist_key, zobrist_key_after_move};
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameState {
+    pub board: Board,
+    pub turn_by: Color,
+    white_king_pos: Position,
+    black_king_pos: Position,
+    pub en_passant_intercept_pos: Option<Position>,
+    pub is_white_queen_side_castling_still_allowed: Disallowable,
+    pub is_white_king_side_castling_still_allowed: Disallowable,
+    pub is_black_queen_side_castling_still_allowed: Disallowable,
+    pub is_black_king_side_castling_still_allowed: Disallowable,
+    #[cfg_attr(feature = "serde", serde(default))]
+    castling_columns: CastlingColumns,
+    moves_played_data: MovesPlayedData,
+    position_hash: u64,
+}
+
+impl GameState {
+    pub fn classic() -> GameState {
+        GameState {
+            board: Board::classic(),
+            turn_by: Color::White,
+            white_king_pos: WHITE_KING_STARTING_POS,
+            black_king_pos: BLACK_KING_STARTING_POS,
+            en_passant_intercept_pos: None,
+            is_white_queen_side_castling_still_allowed: Disallowable::new(true),
+            is_white_king_side_castling_still_allowed: Disallowable::new(true),
+            is_black_queen_side_castling_still_allowed: Disallowable::new(true),
+            is_black_king_side_castling_still_allowed: Disallowable::new(true),
+            castling_columns: CastlingColumns::CLASSIC,
+            moves_played_data: MovesPlayedData::new(),
+            position_hash: 0,
+        }.with_position_hash()
+    }
+
+
+    /// the chess960 start position with the given index (0-959, Scharnagl numbering), 518 is the classic start position
+    pub fn chess960(index: u16) -> Result<GameState, ChessError> {
+        if index >= 960 {
+            return Err(ChessError::new(ErrorKind::IllegalConfig, format!("chess960 start positions are numbered from 0 to 959, not {index}")));
+        }
+        let mut ground_row: [Option<char>; 8] = [None; 8];
+        let mut n = index as usize;
+        ground_row[(n % 4) * 2 + 1] = Some('B');
+        n /= 4;
+        ground_row[(n % 4) * 2] = Some('B');
+        n /= 4;
+        let mut place_on_nth_free_column = |nth: usize, figure: char| {
+            let column = (0..8).filter(|column| ground_row[*column].is_none()).nth(nth).expect("there are enough free columns");
+            ground_row[column] = Some(figure);
+        };
+        place_on_nth_free_column(n % 6, 'Q');
+        n /= 6;
+        let (first_knight, second_knight) = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)][n];
+        // the second knight is placed first, so placing it doesn't move the column of the first one
+        place_on_nth_free_column(second_knight, 'N');
+        place_on_nth_free_column(first_knight, 'N');
+        for figure in ['R', 'K', 'R'] {
+            place_on_nth_free_column(0, figure);
+        }
+        let white_row: String = ground_row.iter().map(|figure| figure.expect("all columns are taken")).collect();
+        GameState::from_fen(&format!("{}/pppppppp/8/8/8/8/PPPPPPPP/{white_row} w KQkq - 0 1", white_row.to_ascii_lowercase()))
+    }
+
+    pub fn from_manual_config(
+        turn_by: Color,
+        en_passant_intercept_pos: Option<Position>,
+        positioned_figures: Vec<FigureAndPosition>
+    ) -> Result<GameState, ChessError> {
+        let mut board = Board::empty();
+        let mut opt_white_king_pos: Option<Position> = None;
+        let mut opt_black_king_pos: Option<Position> = None;
+
+        for figure_and_pos in positioned_figures {
+            let field_was_already_in_use = board.set_figure(figure_and_pos.pos, figure_and_pos.figure);
+            if field_was_already_in_use.is_some() {
+                return Err(ChessError::new(ErrorKind::IllegalConfig, format!("multiple figures placed on {}", figure_and_pos.pos)))
+            }
+            match figure_and_pos.figure.fig_type {
+                FigureType::Pawn => {
+                    let pawn_pos_row = figure_and_pos.pos.row;
+                    if pawn_pos_row==0 || pawn_pos_row==7 {
+                        return Err(ChessError::new(ErrorKind::IllegalConfig, format!("can't place a pawn on {}", figure_and_pos.pos)))
+                    }
+                },
+                FigureType::King => {
+                    match figure_and_pos.figure.color {
+                        Color::White => {
+                            if opt_white_king_pos.is_some() {
+                                return Err(ChessError::new(
+                                    ErrorKind::IllegalConfig,
+                                    format!("can't place a pawn on {}. That row isn't reachable for a pawn.", figure_and_pos.pos),
+                                ))
+                            }
+                            opt_white_king_pos = Some(figure_and_pos.pos);
+                        },
+                        Color::Black => {
+                            if opt_black_king_pos.is_some() {
+                                return Err(ChessError::new(
+                                    ErrorKind::IllegalConfig,
+                                    format!("can't place a pawn on {}. That row isn't reachable for a pawn.", figure_and_pos.pos),
+                                ))
+                            }
+                            opt_black_king_pos = Some(figure_and_pos.pos);
+                        },
+                    }
+                },
+                _ => {},
+            };
+        }
+
+        // a player can't have more figures (or pawns) than at the start of a game
+        for color in [Color::White, Color::Black] {
+            let figure_types: Vec<FigureType> = USIZE_RANGE_063
+                .filter_map(|index| board.get_figure(Position::from_index_unchecked(index)))
+                .filter(|figure| figure.color == color)
+                .map(|figure| figure.fig_type)
+                .collect();
+            let nr_of_pawns = figure_types.iter().filter(|fig_type| **fig_type == FigureType::Pawn).count();
+            if figure_types.len() > 16 || nr_of_pawns > 8 {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalConfig,
+                    format!("{color} has {} figures ({nr_of_pawns} pawns), but at most 16 (8 pawns) are possible", figure_types.len()),
+                ))
+            }
+        }
+
+        // check en-passant
+        if let Some(en_passant_pos) = en_passant_intercept_pos {
+            let (
+                expected_row,
+                expected_row_in_text,
+                forward_dir,
+            ) = match turn_by {
+                Color::White => {
+                    (5_i8, 6_i8, Direction::Down)
+                }
+                Color::Black => {
+                    (2_i8, 3_i8, Direction::Up)
+                }
+            };
+            if en_passant_pos.row != expected_row {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalConfig,
+                    format!("it's {}'s turn so the en-passant position has to be on the {}th row but it's {}.", turn_by, expected_row_in_text, en_passant_pos),
+                ))
+            }
+            let forward_pawn_pos = en_passant_pos.step(forward_dir).unwrap();
+            let mut contains_correct_pawn = false;
+            if let Some(forward_figure) = board.get_figure(forward_pawn_pos) {
+                if forward_figure.fig_type==FigureType::Pawn && forward_figure.color!=turn_by {
+                    contains_correct_pawn = true;
+                }
+            }
+            if !contains_correct_pawn {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalConfig,
+                    format!("since {} is an en-passant pos, there should be a {} pawn on {} but isn't.", en_passant_pos, turn_by.toggle(), forward_pawn_pos),
+                ))
+            }
+
+            let backward_empty_pos = en_passant_pos.step(forward_dir.reverse()).unwrap();
+            if !board.is_empty(backward_empty_pos) {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalConfig,
+                    format!("since {} is an en-passant pos, the position behind it ({}) should be empty but isn't.", en_passant_pos, backward_empty_pos),
+                ))
+            }
+        }
+
+        let white_king_pos = match opt_white_king_pos {
+            Some(pos) => pos,
+            None => {
+                return Err(ChessError::new(ErrorKind::IllegalConfig, "no white king configured".to_string()))
+            },
+        };
+        let black_king_pos = match opt_black_king_pos {
+            Some(pos) => pos,
+            None => {
+                return Err(ChessError::new(ErrorKind::IllegalConfig, "no white king configured".to_string()))
+            },
+        };
+
+        fn board_contains_rook_at(pos: Position, color: Color, board: &Board) -> bool {
+            if let Some(figure) = board.get_figure(pos) {
+                figure.fig_type==FigureType::Rook && figure.color==color
+            } else {
+                false
+            }
+        }
+
+        let is_white_king_on_starting_pos = white_king_pos == WHITE_KING_STARTING_POS;
+        let is_black_king_on_starting_pos = black_king_pos == BLACK_KING_STARTING_POS;
+
+        let is_white_queen_side_rook_on_starting_pos = board_contains_rook_at(
+            WHITE_QUEEN_SIDE_ROOK_STARTING_POS, Color::White, &board,
+        );
+        let is_white_king_side_rook_on_starting_pos = board_contains_rook_at(
+            WHITE_KING_SIDE_ROOK_STARTING_POS, Color::White, &board,
+        );
+        let is_black_queen_side_rook_on_starting_pos = board_contains_rook_at(
+            BLACK_QUEEN_SIDE_ROOK_STARTING_POS, Color::Black, &board,
+        );
+        let is_black_king_side_rook_on_starting_pos = board_contains_rook_at(
+            BLACK_KING_SIDE_ROOK_STARTING_POS, Color::Black, &board,
+        );
+        let is_white_queen_side_castling_possible = Disallowable::new(is_white_king_on_starting_pos && is_white_queen_side_rook_on_starting_pos);
+        let is_white_king_side_castling_possible = Disallowable::new(is_white_king_on_starting_pos && is_white_king_side_rook_on_starting_pos);
+        let is_black_queen_side_castling_possible = Disallowable::new(is_black_king_on_starting_pos && is_black_queen_side_rook_on_starting_pos);
+        let is_black_king_side_castling_possible = Disallowable::new(is_black_king_on_starting_pos && is_black_king_side_rook_on_starting_pos);
+
+        let game_state = GameState {
+            board,
+            turn_by,
+            white_king_pos,
+            black_king_pos,
+            en_passant_intercept_pos,
+            is_white_queen_side_castling_still_allowed: is_white_queen_side_castling_possible,
+            is_white_king_side_castling_still_allowed: is_white_king_side_castling_possible,
+            is_black_queen_side_castling_still_allowed: is_black_queen_side_castling_possible,
+            is_black_king_side_castling_still_allowed: is_black_king_side_castling_possible,
+            castling_columns: CastlingColumns::CLASSIC,
+            moves_played_data: MovesPlayedData::new(),
+            position_hash: 0,
+        };
+
+        Ok(game_state.with_position_hash())
+    }
+
+    /// parses all six fields of a FEN (the castling rights must fit the positions of kings and rooks).
+    /// For chess960 the castling availability can name the files of the castling rooks (Shredder-FEN and X-FEN).
+    pub fn from_fen(fen: &str) -> Result<GameState, ChessError> {
+        let illegal_fen = |reason: String| ChessError::new(ErrorKind::IllegalFormat, format!("illegal FEN '{fen}': {reason}"));
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        let [board_field, turn_field, castling_field, en_passant_field, half_move_clock_field, full_move_number_field] = fields[..] else {
+            return Err(illegal_fen(format!("6 fields expected but found {}", fields.len())));
+        };
+
+        let rows: Vec<&str> = board_field.split('/').collect();
+        if rows.len() != 8 {
+            return Err(illegal_fen(format!("8 rows expected but found {}", rows.len())));
+        }
+        let mut positioned_figures: Vec<FigureAndPosition> = vec![];
+        for (row_index, row) in rows.iter().enumerate() {
+            let board_row = 7 - row_index as i8;
+            let mut column: i8 = 0;
+            for fen_char in row.chars() {
+                if let Some(nr_of_empty_fields) = fen_char.to_digit(10) {
+                    if !(1..=8).contains(&nr_of_empty_fields) {
+                        return Err(illegal_fen(format!("'{fen_char}' isn't a number of empty fields (1-8)")));
+                    }
+                    column += nr_of_empty_fields as i8;
+                } else {
+                    let Some(figure) = Figure::from_fen_char(fen_char) else {
+                        return Err(illegal_fen(format!("unknown figure '{fen_char}'")));
+                    };
+                    if column < 8 {
+                        positioned_figures.push(FigureAndPosition { figure, pos: Position::new_unchecked(column, board_row) });
+                    }
+                    column += 1;
+                }
+                // checked after every char, so the column can't overflow
+                if column > 8 {
+                    return Err(illegal_fen(format!("row '{row}' describes more than 8 fields")));
+                }
+            }
+            if column != 8 {
+                return Err(illegal_fen(format!("row '{row}' doesn't describe 8 fields")));
+            }
+        }
+
+        let turn_by = match turn_field {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err(illegal_fen(format!("active color has to be 'w' or 'b' but is '{turn_field}'"))),
+        };
+        let en_passant_intercept_pos = match en_passant_field {
+            "-" => None,
+            _ => Some(en_passant_field.parse::<Position>()?),
+        };
+        let mut game_state = GameState::from_manual_config(turn_by, en_passant_intercept_pos, positioned_figures)?;
+
+        let is_castling_char = |c: char| "KQkq".contains(c) || ('A'..='H').contains(&c.to_ascii_uppercase());
+        if castling_field != "-" && (castling_field.is_empty() || !castling_field.chars().all(is_castling_char)) {
+            return Err(illegal_fen(format!("castling availability '{castling_field}' can only contain 'K', 'Q', 'k', 'q' and the files of the rooks (or be '-')")));
+        }
+        let mut castling_rights = CastlingRights {
+            white_queen_side: Disallowable::new(false),
+            white_king_side: Disallowable::new(false),
+            black_queen_side: Disallowable::new(false),
+            black_king_side: Disallowable::new(false),
+            columns: CastlingColumns::CLASSIC,
+        };
+        // the columns are only taken from the castling rights that are given
+        let mut king_column: Option<i8> = None;
+        let mut rook_columns: [Option<i8>; 2] = [None, None];
+        for castling_char in castling_field.chars().filter(|c| *c != '-') {
+            let color = if castling_char.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let ground_row = color.get_ground_row();
+            let king_pos = game_state.get_king_pos(color);
+            let rook_columns_of_color: Vec<i8> = (0..8_i8)
+                .filter(|column| game_state.board.contains_figure(Position::new_unchecked(*column, ground_row), FigureType::Rook, color))
+                .collect();
+            // K and Q refer to the outermost rook (X-FEN), file letters to the rook on that file (Shredder-FEN)
+            let rook_column = match castling_char.to_ascii_uppercase() {
+                'K' => rook_columns_of_color.last().copied().filter(|column| *column > king_pos.column),
+                'Q' => rook_columns_of_color.first().copied().filter(|column| *column < king_pos.column),
+                file => Some(file as i8 - 'A' as i8).filter(|column| rook_columns_of_color.contains(column)),
+            };
+            let Some(rook_column) = rook_column.filter(|_| king_pos.row == ground_row) else {
+                return Err(illegal_fen(format!("castling '{castling_char}' isn't possible since king or rook aren't on their starting position")));
+            };
+            let castling_type = if rook_column > king_pos.column { KingSide } else { QueenSide };
+            let side_index = if castling_type == KingSide { 1 } else { 0 };
+            if *king_column.get_or_insert(king_pos.column) != king_pos.column
+                || *rook_columns[side_index].get_or_insert(rook_column) != rook_column {
+                return Err(illegal_fen(format!("castling availability '{castling_field}' needs the kings and castling rooks of both colors on the same files")));
+            }
+            match (color, castling_type) {
+                (Color::White, KingSide) => castling_rights.white_king_side = Disallowable::new(true),
+                (Color::White, QueenSide) => castling_rights.white_queen_side = Disallowable::new(true),
+                (Color::Black, KingSide) => castling_rights.black_king_side = Disallowable::new(true),
+                (Color::Black, QueenSide) => castling_rights.black_queen_side = Disallowable::new(true),
+            }
+        }
+        castling_rights.columns = CastlingColumns {
+            king: king_column.unwrap_or(CastlingColumns::CLASSIC.king),
+            queen_side_rook: rook_columns[0].unwrap_or(CastlingColumns::CLASSIC.queen_side_rook),
+            king_side_rook: rook_columns[1].unwrap_or(CastlingColumns::CLASSIC.king_side_rook),
+        };
+        game_state.is_white_queen_side_castling_still_allowed = castling_rights.white_queen_side;
+        game_state.is_white_king_side_castling_still_allowed = castling_rights.white_king_side;
+        game_state.is_black_queen_side_castling_still_allowed = castling_rights.black_queen_side;
+        game_state.is_black_king_side_castling_still_allowed = castling_rights.black_king_side;
+        game_state.castling_columns = castling_rights.columns;
+
+        let half_moves_without_progress = half_move_clock_field.parse::<u32>()
+            .map_err(|error| illegal_fen(format!("halfmove clock '{half_move_clock_field}' isn't a number")).with_source(error))?;
+        let full_move_number = match full_move_number_field.parse::<u32>() {
+            Ok(full_move_number) if full_move_number > 0 => full_move_number,
+            result => {
+                let error = illegal_fen(format!("fullmove number '{full_move_number_field}' isn't a positive number"));
+                return Err(match result {
+                    Err(parse_error) => error.with_source(parse_error),
+                    Ok(_) => error,
+                });
+            },
+        };
+        let Some(half_moves_played) = (full_move_number - 1).checked_mul(2).and_then(|half_moves| half_moves.checked_add(u32::from(turn_by == Color::Black))) else {
+            return Err(illegal_fen(format!("fullmove number '{full_move_number_field}' is too big")));
+        };
+        game_state.moves_played_data = MovesPlayedData {
+            half_moves_played,
+            half_moves_played_without_progress: half_moves_without_progress,
+            earlier_positions: PositionHistory::default(),
+        };
+        Ok(game_state.with_position_hash())
+    }
+
+    /**
+     * returns true if a_move.from points to a pawn and a_move.to is on the first or last row of the board
+     * (but doesn't check if the move is actually legal)
+     */
+    pub fn looks_like_pawn_promotion_move(&self, a_move: FromTo) -> bool {
+        let Some(Figure{fig_type: FigureType::Pawn, color: _}) = self.board.get_figure(a_move.from) else {
+            return false;
+        };
+        let pawn_to_row = a_move.to.row;
+        (pawn_to_row == 7) || (pawn_to_row == 0)
+    }
+
+    /**
+     * returns true if a_move.from points to a king and a_move.to points to rook of the same color
+     * (but doesn't check if the move is actually legal)
+     * returns error if a_move.to is the classic target field of the king. since that move can be ambiguous
+     * in a chess960 context.
+     */
+    pub fn looks_like_castling(&self, a_move: FromTo) -> Result<bool, ChessError> {
+        let Some(Figure{fig_type: FigureType::King, color: _}) = self.board.get_figure(a_move.from) else {
+            return Ok(false);
+        };
+        if let Some(Figure{fig_type: FigureType::Rook, color: rook_color}) = self.board.get_figure(a_move.to) {
+            if rook_color==self.turn_by {
+                return Ok(true);
+            };
+        };
+        let ground_row = self.turn_by.get_ground_row();
+        if a_move.from.get_row_distance(a_move.to) > 1 && a_move.from.row == ground_row && a_move.to.row == ground_row {
+            return Err(ChessError::new(
+                ErrorKind::IllegalFormat,
+                "It looks like you're trying to castle by pointing to the final position of the king. Point to the rook you're castling with instead!".to_string(),
+            ))
+        }
+        Ok(false)
+    }
+
+    /// returns true if a_move.from points to a pawn that moves diagonally onto an empty field,
+    /// which is only legal as an en-passant capture (but doesn't check if it actually is one)
+    pub fn looks_like_en_passant(&self, a_move: FromTo) -> bool {
+        let Some(Figure{fig_type: FigureType::Pawn, color: _}) = self.board.get_figure(a_move.from) else {
+            return false;
+        };
+        a_move.from.column != a_move.to.column && self.board.is_empty(a_move.to)
+    }
+
+    /// plays the move without checking if it's legal (see [GameState::is_legal]),
+    /// but returns an error instead of panicking for moves that can't be played at all
+    /// (e.g. a move from an empty field or one capturing a king)
+    pub fn do_move(&self, next_move: Move) -> Result<(GameState, MoveData), ChessError> {
+        let from = next_move.from_to.from;
+        let to = next_move.from_to.to;
+
+        let Some(moving_figure) = self.board.get_figure(from) else {
+            return Err(ChessError::new(ErrorKind::IllegalMove, format!("move {next_move} starts on an empty field on game {}", self.board)));
+        };
+        if to == self.white_king_pos || to == self.black_king_pos {
+            return Err(ChessError::new(ErrorKind::IllegalMove, format!("move {next_move} would capture a king on game {}", self.board)));
+        }
+        for (king_pos, color) in [(self.white_king_pos, Color::White), (self.black_king_pos, Color::Black)] {
+            if !self.board.contains_figure(king_pos, FigureType::King, color) {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalConfig,
+                    format!("couldn't find {color} king at {king_pos} on board {} (next_move {next_move})", self.board),
+                ));
+            }
+        }
+
+        let mut new_board = self.board.clone();
+
+        let (
+            new_white_king_pos,
+            new_black_king_pos,
+            new_en_passant_intercept_pos,
+            move_stats,
+        ) = match moving_figure.fig_type {
+            FigureType::King => {
+                let is_castling = match new_board.get_figure(to) {
+                    Some(Figure{fig_type: FigureType::Rook, color: rook_color }) => {
+                        rook_color == moving_figure.color
+                    }
+                    _ => false,
+                };
+
+                let (effective_king_move, figure_captured, castling_rook_move) = if is_castling {
+                    let (king_move, rook_move) = do_castling_move(&mut new_board, next_move.from_to, moving_figure.color);
+                    (king_move, None, Some(rook_move))
+                } else {
+                    let capture_info = do_normal_move(&mut new_board, next_move.from_to);
+                    (next_move.from_to, capture_info.get_captured_figure_type(), None)
+                };
+
+                let king_move_stats = {
+                    let mut stats = MoveData::new(next_move.from_to, FigureType::King, figure_captured);
+                    let move_type = if let Some(rook_move) = castling_rook_move {
+                        let castling_type = if rook_move.to.column==3 {
+                            QueenSide
+                        } else {
+                            KingSide
+                        };
+                        MoveType::Castling { castling_type, king_move: effective_king_move, rook_move }
+                    } else {
+                        MoveType::Normal
+                    };
+                    stats.move_type = move_type;
+                    stats
+                };
+
+
+                match moving_figure.color {
+                    Color::White => {
+                        (
+                            effective_king_move.to,
+                            self.black_king_pos,
+                            None,
+                            king_move_stats,
+                        )
+                    }
+                    Color::Black => {
+                        (
+                            self.white_king_pos,
+                            effective_king_move.to,
+                            None,
+                            king_move_stats,
+                        )
+                    }
+                }
+            },
+            FigureType::Pawn => {
+                fn compute_pawn_move_type(this: &GameState, pawn_move: Move) -> PawnMoveType {
+                    if let Some(promotion_type) = pawn_move.promotion_type {
+                        return PawnMoveType::Promotion(promotion_type);
+                    };
+                    let pawn_move_from = pawn_move.from_to.from;
+                    let pawn_move_to   = pawn_move.from_to.to;
+                    if pawn_move_from.get_row_distance(pawn_move_to) == 2 {
+                        return PawnMoveType::DoubleStep
+                    }
+                    if let Some(en_passant_pos) = this.en_passant_intercept_pos {
+                        if pawn_move_to == en_passant_pos {
+                            return PawnMoveType::EnPassantIntercept
+                        }
+                    }
+                    PawnMoveType::SingleStep
+                }
+                fn handle_pawn_promotion_after_move(new_board: &mut Board, pawn_move: Move, pawn_color: Color) {
+                    if let Some(promo_type) = pawn_move.promotion_type {
+                        new_board.set_figure(
+                            pawn_move.from_to.to,
+                            Figure{ fig_type: promo_type.get_figure_type(), color: pawn_color }
+                        );
+                    }
+                }
+
+                match compute_pawn_move_type(self, next_move) {
+                    PawnMoveType::Promotion(promotion_type) => {
+                        let capture_info: CaptureInfoOption = do_normal_move(&mut new_board, next_move.from_to);
+                        handle_pawn_promotion_after_move(&mut new_board, next_move, self.turn_by);
+                        let stats = MoveData::new_pawn_promotion(next_move.from_to, capture_info.get_captured_figure_type(), promotion_type);
+                        (
+                            self.white_king_pos, self.black_king_pos,
+                            None,
+                            stats,
+                        )
+                    },
+                    PawnMoveType::SingleStep => {
+                        let capture_info: CaptureInfoOption = do_normal_move(&mut new_board, next_move.from_to);
+                        handle_pawn_promotion_after_move(&mut new_board, next_move, self.turn_by);
+                        let stats = MoveData::new(next_move.from_to, FigureType::Pawn, capture_info.get_captured_figure_type());
+                        (
+                            self.white_king_pos, self.black_king_pos,
+                            None,
+                            stats,
+                        )
+                    },
+                    PawnMoveType::DoubleStep => {
+                        do_normal_move(&mut new_board, next_move.from_to);
+                        let stats = MoveData::new(next_move.from_to, FigureType::Pawn, None);
+                        (
+                            self.white_king_pos, self.black_king_pos,
+                            Some(Position::new_unchecked(
+                                to.column,
+                                (from.row + to.row) / 2,
+                            )),
+                            stats,
+                        )
+                    },
+                    PawnMoveType::EnPassantIntercept => {
+                        do_en_passant_move(&mut new_board, next_move.from_to)?;
+                        let a_move = MoveData::new_en_passant(next_move.from_to);
+                        (
+                            self.white_king_pos, self.black_king_pos,
+                            None,
+                            a_move,
+                        )
+                    },
+                }
+            },
+            _ => {
+                let capture_info = do_normal_move(&mut new_board, next_move.from_to);
+                (
+                    self.white_king_pos,
+                    self.black_king_pos,
+                    None,
+                    MoveData::new(next_move.from_to, moving_figure.fig_type, capture_info.get_captured_figure_type()),
+                )
+            },
+        };
+
+        let mut new_castling_rights = self.castling_rights();
+        new_castling_rights.update_for(&move_stats);
+
+        let mut new_game_state = GameState {
+            board: new_board,
+            turn_by: self.turn_by.toggle(),
+            white_king_pos: new_white_king_pos,
+            black_king_pos: new_black_king_pos,
+            en_passant_intercept_pos: new_en_passant_intercept_pos,
+            is_white_queen_side_castling_still_allowed: new_castling_rights.white_queen_side,
+            is_white_king_side_castling_still_allowed: new_castling_rights.white_king_side,
+            is_black_queen_side_castling_still_allowed: new_castling_rights.black_queen_side,
+            is_black_king_side_castling_still_allowed: new_castling_rights.black_king_side,
+            castling_columns: self.castling_columns,
+            moves_played_data: MovesPlayedData::new_after_move(&self.moves_played_data, &move_stats, self.position_hash),
+            position_hash: 0,
+        };
+        new_game_state.position_hash = zobrist_key_after_move(self, &move_stats, &new_game_state);
+        Ok((new_game_state, move_stats))
+    }
+
+    /// the Polyglot Zobrist key of the position (pieces, castling rights, en-passant and turn),
+    /// e.g. to look positions up in an opening book or to find repetitions.
+    /// It's updated incrementally by [GameState::do_move], changing the public fields directly doesn't update it.
+    pub fn position_hash(&self) -> u64 {
+        self.position_hash
+    }
+
+    fn with_position_hash(mut self) -> GameState {
+        self.position_hash = zobrist_key(&self);
+        self
+    }
+
+    pub fn castling_rights(&self) -> CastlingRights {
+        CastlingRights {
+            white_queen_side: self.is_white_queen_side_castling_still_allowed,
+            white_king_side: self.is_white_king_side_castling_still_allowed,
+            black_queen_side: self.is_black_queen_side_castling_still_allowed,
+            black_king_side: self.is_black_king_side_castling_still_allowed,
+            columns: self.castling_columns,
+        }
+    }
+
+    pub fn get_king_pos(&self, color: Color) -> Position {
+        match color {
+            Color::White => {self.white_king_pos}
+            Color::Black => {self.black_king_pos}
+        }
+    }
+
+    pub fn is_in_check(&self, color: Color) -> bool {
+        is_attacked_by(self.get_king_pos(color), color.toggle(), &self.board)
+    }
+
+    /**
+     * returns true if the player whose turn it is would be in check after the given move
+     * (the move itself has to be pseudo-legal)
+     */
+    pub fn leaves_king_in_check(&self, a_move: Move) -> bool {
+        match self.do_move(a_move) {
+            Ok((new_game_state, _)) => new_game_state.is_in_check(self.turn_by),
+            // a move that can't be played can't get the king out of check either
+            Err(_) => true,
+        }
+    }
+
+    /// checks a single move without generating all legal moves (e.g. to validate a drag-and-drop in a UI
+    /// before the move is pushed to an Encoder of chess_compress_urlsafe-codec).
+    /// Castling moves have to point to the rook.
+    pub fn is_legal(&self, a_move: Move) -> Result<(), IllegalMoveReason> {
+        let from_to = a_move.from_to;
+        match self.board.get_figure(from_to.from) {
+            None => return Err(IllegalMoveReason::NoFigureToMove),
+            Some(figure) if figure.color != self.turn_by => return Err(IllegalMoveReason::NotYourFigure),
+            Some(_) => {}
+        }
+        if self.looks_like_castling(from_to).unwrap_or(false) {
+            let castling_type = if from_to.to.column > from_to.from.column { KingSide } else { QueenSide };
+            return match self.get_castling_move(castling_type) {
+                Some(castling_move) if castling_move.from_to == from_to => Ok(()),
+                _ => Err(IllegalMoveReason::CastlingNotAllowed),
+            };
+        }
+        match self.board.get_figure(from_to.to) {
+            Some(figure) if figure.color == self.turn_by => return Err(IllegalMoveReason::CapturesOwnFigure),
+            Some(Figure{fig_type: FigureType::King, color: _}) => return Err(IllegalMoveReason::CapturesKing),
+            _ => {}
+        }
+        if self.looks_like_en_passant(from_to) && self.en_passant_intercept_pos != Some(from_to.to) {
+            return Err(IllegalMoveReason::NoEnPassant);
+        }
+        let is_reachable = get_positions_to_reach_target_from(from_to.to, self)
+            .is_ok_and(|origins| origins.contains(&from_to.from));
+        if !is_reachable {
+            return Err(IllegalMoveReason::UnreachableTarget);
+        }
+        match (self.looks_like_pawn_promotion_move(from_to), a_move.promotion_type) {
+            (true, None) => return Err(IllegalMoveReason::MissingPromotionType),
+            (false, Some(_)) => return Err(IllegalMoveReason::UnexpectedPromotionType),
+            _ => {}
+        }
+        if self.leaves_king_in_check_after(from_to) {
+            return Err(IllegalMoveReason::LeavesKingInCheck);
+        }
+        Ok(())
+    }
+
+    /// returns the legal moves of the player whose turn it is (castling moves point to the rook).
+    /// Pins, checks, castling out of or through check and en passant are taken into account (see [GameState::perft]).
+    /// The moves are ordered ascending by the index of the to-square (a1=0, b1=1, ..., h8=63), then by the index of
+    /// the from-square, then by promotion type (Queen, Rook, Knight, Bishop). Castling moves come last (king side first).
+    /// See [GameState::ordered_legal_moves] for moves ordered by from-square.
+    pub fn get_legal_moves(&self) -> Vec<Move> {
+        let mut legal_moves: Vec<Move> = Vec::with_capacity(EXPECTED_MAX_NUMBER_OF_MOVES);
+        self.for_each_legal_move(|a_move| legal_moves.push(a_move));
+        legal_moves
+    }
+
+    /**
+     * returns the legal moves of the player whose turn it is in a fixed order:
+     * ascending by the index of the from-square (a1=0, b1=1, ..., h8=63), then by the index of the to-square,
+     * then by promotion type (Queen, Rook, Knight, Bishop).
+     * Castling moves point to the rook, so they're ordered like a king move to the rook's square.
+     * The v2 format encodes a move as its index in this list, so this order must never change,
+     * otherwise already issued v2 tokens would decode to different games!
+     */
+    pub fn ordered_legal_moves(&self) -> Vec<Move> {
+        fn promotion_rank(promotion_type: Option<PromotionType>) -> u8 {
+            match promotion_type {
+                None | Some(PromotionType::Queen) => 0,
+                Some(PromotionType::Rook) => 1,
+                Some(PromotionType::Knight) => 2,
+                Some(PromotionType::Bishop) => 3,
+            }
+        }
+        let mut legal_moves = self.get_legal_moves();
+        legal_moves.sort_unstable_by_key(|a_move| (
+            a_move.from_to.from.index.get(),
+            a_move.from_to.to.index.get(),
+            promotion_rank(a_move.promotion_type),
+        ));
+        legal_moves
+    }
+
+    /// the number of legal moves of the player whose turn it is (without collecting them)
+    pub fn legal_move_count(&self) -> usize {
+        let mut count: usize = 0;
+        self.for_each_legal_move(|_| count += 1);
+        count
+    }
+
+    /// counts the leaf nodes of the tree of legal moves of the given depth (perft),
+    /// which can be compared to published values to verify the legal move generation
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        if depth == 1 {
+            return self.legal_move_count() as u64;
+        }
+        self.get_legal_moves().into_iter()
+            .filter_map(|a_move| self.do_move(a_move).ok())
+            .map(|(game_state, _)| game_state.perft(depth - 1))
+            .sum()
+    }
+
+    /// true if the player whose turn it is is in check and has no legal move
+    pub fn is_checkmate(&self) -> bool {
+        self.is_in_check(self.turn_by) && self.legal_move_count() == 0
+    }
+
+    /// true if the player whose turn it is isn't in check but has no legal move
+    pub fn is_stalemate(&self) -> bool {
+        !self.is_in_check(self.turn_by) && self.legal_move_count() == 0
+    }
+
+    /// the result as far as it can be told from this position (mate, stalemate or insufficient material)
+    pub fn get_game_result(&self) -> GameResult {
+        if !self.board.contains_sufficient_material_to_continue() {
+            return GameResult::Draw;
+        }
+        if self.legal_move_count() != 0 {
+            return GameResult::Undecided;
+        }
+        if !self.is_in_check(self.turn_by) {
+            return GameResult::Draw;
+        }
+        match self.turn_by {
+            Color::White => GameResult::BlackWins,
+            Color::Black => GameResult::WhiteWins,
+        }
+    }
+
+    /// the number of legal moves the given player has (or would have if it was their turn)
+    pub fn mobility(&self, color: Color) -> usize {
+        if color == self.turn_by {
+            return self.legal_move_count();
+        }
+        self.as_if_turn_by(color).legal_move_count()
+    }
+
+    /// the same position, but with the given player to move
+    pub(crate) fn as_if_turn_by(&self, color: Color) -> GameState {
+        let mut game_state = self.clone();
+        if color != self.turn_by {
+            game_state.turn_by = color;
+            // en-passant is only possible directly after the double step
+            game_state.en_passant_intercept_pos = None;
+            game_state = game_state.with_position_hash();
+        }
+        game_state
+    }
+
+    fn for_each_legal_move(&self, mut on_legal_move: impl FnMut(Move)) {
+        for target in USIZE_RANGE_063.map(Position::from_index_unchecked) {
+            if let Some(figure) = self.board.get_figure(target) {
+                if figure.color == self.turn_by || figure.fig_type == FigureType::King {
+                    continue;
+                }
+            }
+            let Ok(origins) = get_positions_to_reach_target_from(target, self) else {
+                continue;
+            };
+            for from in origins {
+                let from_to = FromTo::new(from, target);
+                if self.leaves_king_in_check_after(from_to) {
+                    continue;
+                }
+                if self.looks_like_pawn_promotion_move(from_to) {
+                    for promotion_type in [PromotionType::Queen, PromotionType::Rook, PromotionType::Knight, PromotionType::Bishop] {
+                        on_legal_move(Move::new_with_promotion(from_to, promotion_type));
+                    }
+                } else {
+                    on_legal_move(Move::new(from_to));
+                }
+            }
+        }
+        for castling_type in [KingSide, QueenSide] {
+            if let Some(castling_move) = self.get_castling_move(castling_type) {
+                on_legal_move(castling_move);
+            }
+        }
+    }
+
+    /// like leaves_king_in_check but only moves the figure on a copy of the board (castling isn't supported)
+    fn leaves_king_in_check_after(&self, from_to: FromTo) -> bool {
+        let mut board = self.board.clone();
+        let moving_figure = board.get_figure(from_to.from).expect("a move starts at a figure");
+        let is_en_passant = moving_figure.fig_type == FigureType::Pawn
+            && from_to.from.column != from_to.to.column
+            && board.is_empty(from_to.to);
+        if is_en_passant {
+            board.clear_field(Position::new_unchecked(from_to.to.column, from_to.from.row));
+        }
+        board.clear_field(from_to.from);
+        board.set_figure(from_to.to, moving_figure);
+        let king_pos = if moving_figure.fig_type == FigureType::King {
+            from_to.to
+        } else {
+            self.get_king_pos(self.turn_by)
+        };
+        is_attacked_by(king_pos, self.turn_by.toggle(), &board)
+    }
+
+    /// returns the castling move (the king capturing its own rook) if it's legal
+    pub fn get_castling_move(&self, castling_type: CastlingType) -> Option<Move> {
+        let active_color = self.turn_by;
+        let still_allowed = match (active_color, castling_type) {
+            (Color::White, KingSide) => self.is_white_king_side_castling_still_allowed,
+            (Color::White, QueenSide) => self.is_white_queen_side_castling_still_allowed,
+            (Color::Black, KingSide) => self.is_black_king_side_castling_still_allowed,
+            (Color::Black, QueenSide) => self.is_black_queen_side_castling_still_allowed,
+        };
+        let ground_row = active_color.get_ground_row();
+        let king_pos = self.get_king_pos(active_color);
+        let (rook_column, king_target_column, rook_target_column) = match castling_type {
+            KingSide => (self.castling_columns.king_side_rook, 6, 5),
+            QueenSide => (self.castling_columns.queen_side_rook, 2, 3),
+        };
+        let rook_pos = Position::new_unchecked(rook_column, ground_row);
+        if !still_allowed.is_still_allowed() || king_pos.row != ground_row
+            || !self.board.contains_figure(rook_pos, FigureType::Rook, active_color) {
+            return None;
+        }
+
+        // in chess960 king and rook can start on (or beyond) each other's targets,
+        // so the fields they cross only have to be free of other figures
+        let mut board_without_king_and_rook = self.board.clone();
+        board_without_king_and_rook.clear_field(king_pos);
+        board_without_king_and_rook.clear_field(rook_pos);
+        let columns_between = |a: i8, b: i8| a.min(b)..=a.max(b);
+        let is_path_free = columns_between(king_pos.column, king_target_column)
+            .chain(columns_between(rook_column, rook_target_column))
+            .all(|column| board_without_king_and_rook.is_empty(Position::new_unchecked(column, ground_row)));
+        // the king may neither castle out of, through or into check
+        let is_path_safe = || columns_between(king_pos.column, king_target_column).all(|column| {
+            !is_attacked_by(Position::new_unchecked(column, ground_row), active_color.toggle(), &board_without_king_and_rook)
+        });
+        (is_path_free && is_path_safe()).then(|| Move::new(FromTo::new(king_pos, rook_pos)))
+    }
+
+    /// 'K' or 'Q' if the castling rook is the outermost rook on its side (X-FEN), otherwise its file (Shredder-FEN)
+    fn get_castling_char(&self, color: Color, castling_type: CastlingType) -> char {
+        let ground_row = color.get_ground_row();
+        let rook_column = self.castling_columns.rook(castling_type);
+        let outer_columns = match castling_type {
+            KingSide => (rook_column + 1)..8,
+            QueenSide => 0..rook_column,
+        };
+        let is_outermost_rook = !outer_columns.into_iter()
+            .any(|column| self.board.contains_figure(Position::new_unchecked(column, ground_row), FigureType::Rook, color));
+        match (is_outermost_rook, castling_type) {
+            (true, KingSide) => 'K',
+            (true, QueenSide) => 'Q',
+            (false, _) => (b'A' + rook_column as u8) as char,
+        }
+    }
+
+    #[allow(dead_code)]
+    fn get_passive_king_pos(&self) -> Position {
+        match self.turn_by {
+            Color::Black => {self.white_king_pos}
+            Color::White => {self.black_king_pos}
+        }
+    }
+
+    pub fn get_fen(&self) -> String {
+        let mut fen = self.get_fen_part1to4();
+        fen.push(' ');
+        fen.push_str((self.moves_played_data.half_moves_played_without_progress).to_string().as_str());
+        fen.push(' ');
+        fen.push_str(self.moves_played_data.current_round().to_string().as_str());
+        fen
+    }
+
+    /// the number of the current move (the fullmove number of the FEN), it's increased after every move of black
+    pub fn get_fullmove_number(&self) -> u32 {
+        self.moves_played_data.current_round()
+    }
+
+    /// the half moves played since the last capture or pawn move (the counter of the fifty-move rule)
+    pub fn get_half_moves_without_progress(&self) -> u32 {
+        self.moves_played_data.half_moves_played_without_progress
+    }
+
+    /// the same position with other counters (the halfmove clock and the fullmove number of the FEN)
+    pub fn with_move_counters(mut self, half_moves_without_progress: u32, fullmove_number: u32) -> GameState {
+        // positions before the last capture or pawn move can't occur again
+        let earlier_positions = self.moves_played_data.earlier_positions.truncated(half_moves_without_progress as usize);
+        self.moves_played_data = MovesPlayedData {
+            half_moves_played: fullmove_number.saturating_sub(1).saturating_mul(2).saturating_add(u32::from(self.turn_by == Color::Black)),
+            half_moves_played_without_progress: half_moves_without_progress,
+            earlier_positions,
+        };
+        self
+    }
+
+    /// whether the game can be claimed a draw (or is drawn automatically) by repetition or the fifty-move rule.
+    /// Only the positions reached by [GameState::do_move] from this state's origin count as repetitions,
+    /// so the history before a FEN (or a deserialized state) is unknown.
+    pub fn draw_status(&self) -> Option<DrawStatus> {
+        let position_key = self.position_hash;
+        let repetitions = 1 + self.moves_played_data.earlier_positions.keys()
+            .filter(|key| *key == position_key)
+            .count();
+        // a mate on the last move counts, the fifty- and seventy-five-move rules don't apply then
+        let half_moves_without_progress = match self.moves_played_data.half_moves_played_without_progress {
+            half_moves if half_moves >= 100 && self.is_checkmate() => 0,
+            half_moves => half_moves,
+        };
+        if repetitions >= 5 {
+            Some(DrawStatus::FivefoldForced)
+        } else if half_moves_without_progress >= 150 {
+            Some(DrawStatus::SeventyFiveForced)
+        } else if repetitions >= 3 {
+            Some(DrawStatus::ThreefoldAvailable)
+        } else if half_moves_without_progress >= 100 {
+            Some(DrawStatus::FiftyMoveAvailable)
+        } else {
+            None
+        }
+    }
+
+    /// the part of the FEN that identifies a position (when checking for repetitions)
+    pub fn get_fen_part1to4(&self) -> String {
+        let mut fen_part1to4 = self.board.get_fen_part1();
+        fen_part1to4.push(' ');
+        fen_part1to4.push(self.turn_by.get_fen_char());
+        fen_part1to4.push(' ');
+        let mut has_castling_rights = false;
+        for (castling_still_allowed, color, castling_type) in [
+            (self.is_white_king_side_castling_still_allowed, Color::White, KingSide),
+            (self.is_white_queen_side_castling_still_allowed, Color::White, QueenSide),
+            (self.is_black_king_side_castling_still_allowed, Color::Black, KingSide),
+            (self.is_black_queen_side_castling_still_allowed, Color::Black, QueenSide),
+        ] {
+            if castling_still_allowed.is_still_allowed() {
+                let castling_char = self.get_castling_char(color, castling_type);
+                fen_part1to4.push(if color == Color::White { castling_char } else { castling_char.to_ascii_lowercase() });
+                has_castling_rights = true;
+            }
+        }
+        if !has_castling_rights {
+            fen_part1to4.push('-');
+        }
+        fen_part1to4.push(' ');
+        match self.en_passant_intercept_pos {
+            None => { fen_part1to4.push('-');}
+            Some(pos) => { fen_part1to4.push_str(format!("{}", pos).as_str());}
+        }
+        fen_part1to4
+    }
+}
+
+impl str::FromStr for GameState {
+    type Err = ChessError;
+
+    fn from_str(desc: &str) -> Result<Self, Self::Err> {
+        let trimmed_desc = desc.trim();
+        if trimmed_desc.is_empty() {
+            return Ok(GameState::classic())
+        }
+        // the board field of a FEN separates the rows with '/'
+        if trimmed_desc.split(' ').next().is_some_and(|first_token| first_token.contains('/')) {
+            return GameState::from_fen(trimmed_desc);
+        }
+        let token_iter = trimmed_desc.split(' ');
+
+        // let desc_contains_figures: bool = "♔♕♗♘♖♙♚♛♝♞♜♟".chars().any(|symbol|{desc.contains(symbol)});
+        let desc_contains_moves: bool = trimmed_desc.is_empty() || !(trimmed_desc.starts_with("white") || trimmed_desc.starts_with("black"));
+        println!("'{desc_contains_moves}', '{trimmed_desc}'");
+        if desc_contains_moves {
+            game_by_moves_from_start(token_iter)
+        } else {
+            game_by_figures_on_board(token_iter)
+        }
+    }
+}
+
+fn game_by_moves_from_start(token_iter: str::Split<char>) -> Result<GameState, ChessError> {
+    let mut game_state = GameState::classic();
+    for token in token_iter {
+        let basic_move = token.parse::<Move>()?;
+        let (new_game_state, _) = game_state.do_move(basic_move)?;
+        game_state = new_game_state;
+    }
+    Ok(game_state)
+}
+
+fn game_by_figures_on_board(mut token_iter: str::Split<char>) -> Result<GameState, ChessError> {
+    let first_token = token_iter.next().unwrap();
+    let turn_by = match first_token {
+        "white" => Color::White,
+        "black" => Color::Black,
+        _ => {
+            return Err(ChessError::new(
+                ErrorKind::IllegalConfig,
+                format!("the first token has to be either 'white' or 'black' but was {}", first_token),
+            ))
+        },
+    };
+
+    let mut positioned_figures: Vec<FigureAndPosition> = vec![];
+    let mut opt_en_passant_pos: Option<Position> = None;
+
+    for token in token_iter {
+        // tokens should either start with a figure char (from "♔♕♗♘♖♙♚♛♝♞♜♟") or E (for en-passant)
+        // followed by a position between "a1" and "h8"
+        if let Some(stripped_token) = token.strip_prefix('E') {
+            let en_passant_pos = stripped_token.parse::<Position>()?;
+            if let Some(old_en_passant_pos) = opt_en_passant_pos {
+                return Err(ChessError::new(
+                    ErrorKind::IllegalConfig,
+                    format!("there are two en-passant tokens present (on {} and {}) but only one is allowed.", old_en_passant_pos, en_passant_pos),
+                ))
+            }
+            opt_en_passant_pos = Some(en_passant_pos);
+        } else {
+            let figure_and_pos = token.parse::<FigureAndPosition>()?;
+            positioned_figures.push(figure_and_pos);
+        }
+    }
+
+    let game_state = GameState::from_manual_config(turn_by, opt_en_passant_pos, positioned_figures)?;
+    Ok(game_state)
+}
+
+/**
+ * returns the figure that was caught (if any) and the position it was caught on
+ */
+fn do_normal_move(
+    new_board: &mut Board,
+    next_move: FromTo,
+) -> CaptureInfoOption {
+    let moving_figure: Figure = new_board.get_figure(next_move.from).expect("field the figure moves from is empty");
+    new_board.clear_field(next_move.from);
+    new_board.set_figure(next_move.to, moving_figure)
+}
+
+/**
+ * this function assumes that the king move given this function actually moves to the position of the rook
+ * (this makes castling moves distinguishable from normal king moves in all initial positions of chess960,
+ * e.g. think of initial position with king on f1 and rook on h1: would move f1g1 come with intend to castle or not?)
+ * returns the effective move of king and rook
+ * e.g. giving classic initial position, then king_move: e1h1 would return (e1g1, h1f1)
+ */
+fn do_castling_move(
+    new_board: &mut Board,
+    king_move: FromTo,
+    king_color: Color,
+) -> (FromTo, FromTo) {
+    new_board.clear_field(king_move.from);
+    new_board.clear_field(king_move.to);
+    let move_row = king_move.to.row;
+    let castling_type = if king_move.to.column > king_move.from.column {
+        KingSide
+    } else {
+        QueenSide
+    };
+    let (king_to_pos, rook_to_pos) = if castling_type== KingSide {
+        let rook_to_pos = Position::new_unchecked(5, move_row);
+        let king_to_pos = Position::new_unchecked(6, move_row);
+        new_board.set_figure(king_to_pos, Figure{ fig_type: FigureType::King, color: king_color });
+        new_board.set_figure(rook_to_pos, Figure{ fig_type: FigureType::Rook, color: king_color });
+        (king_to_pos, rook_to_pos)
+    } else {
+        let rook_to_pos = Position::new_unchecked(3, move_row);
+        let king_to_pos = Position::new_unchecked(2, move_row);
+        new_board.set_figure(king_to_pos, Figure{ fig_type: FigureType::King, color: king_color });
+        new_board.set_figure(rook_to_pos, Figure{ fig_type: FigureType::Rook, color: king_color });
+        (king_to_pos, rook_to_pos)
+    };
+
+    (FromTo::new(king_move.from, king_to_pos), FromTo::new(king_move.to, rook_to_pos))
+}
+
+fn do_en_passant_move(
+    new_board: &mut Board,
+    next_move: FromTo,
+) -> Result<CaptureInfoOption, ChessError> {
+    let double_stepped_pawn_pos =
+        Position::new_unchecked(next_move.to.column, next_move.from.row);
+    let Some(pawn_captured) = new_board.get_figure(double_stepped_pawn_pos) else {
+        return Err(ChessError::new(
+            ErrorKind::IllegalMove,
+            format!("en-passant move {next_move} finds no pawn to capture on {double_stepped_pawn_pos}"),
+        ));
+    };
+    do_normal_move(new_board, next_move);
+    new_board.clear_field(double_stepped_pawn_pos);
+    Ok(CaptureInfoOption::from_some(pawn_captured, double_stepped_pawn_pos))
+}
+
+enum PawnMoveType {
+    SingleStep, DoubleStep, EnPassantIntercept, Promotion(PromotionType),
+}
+
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}'s turn", self.turn_by)?;
+        if self.is_in_check(self.turn_by) {
+            write!(f, " (in check)")?;
+        }
+        write!(f, "{}", self.board)?;
+        let fen = self.get_fen();
+        let fen_parts: Vec<&str> = fen.split(' ').collect();
+        writeln!(
+            f, "castling: {}, en passant: {}, halfmove clock: {}, fullmove number: {}",
+            fen_parts[2], fen_parts[3], fen_parts[4], fen_parts[5],
+        )
+    }
+}
+
+/// why [GameState::is_legal] rejected a move
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum IllegalMoveReason {
+    /// the from-square is empty
+    NoFigureToMove,
+    /// the figure on the from-square belongs to the player who isn't to move
+    NotYourFigure,
+    CapturesOwnFigure,
+    CapturesKing,
+    /// the figure on the from-square can't go to the to-square (not even if its king was ignored)
+    UnreachableTarget,
+    /// a pawn moves diagonally onto an empty field, but no pawn double-stepped past it in the previous move
+    NoEnPassant,
+    /// the castling right is lost, a figure is in the way or the king is in or passes through check
+    CastlingNotAllowed,
+    MissingPromotionType,
+    /// only pawns that reach the last row can be promoted
+    UnexpectedPromotionType,
+    LeavesKingInCheck,
+}
+
+impl fmt::Display for IllegalMoveReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            IllegalMoveReason::NoFigureToMove => "there is no figure to move",
+            IllegalMoveReason::NotYourFigure => "the figure belongs to the other player",
+            IllegalMoveReason::CapturesOwnFigure => "it captures a figure of the own color",
+            IllegalMoveReason::CapturesKing => "kings can't be captured",
+            IllegalMoveReason::UnreachableTarget => "no figure of the player to move can go there",
+            IllegalMoveReason::NoEnPassant => "a pawn can only move diagonally onto an empty field when capturing en passant",
+            IllegalMoveReason::CastlingNotAllowed => "castling isn't allowed (the castling right is lost, a figure is in the way or the king is in or passes through check)",
+            IllegalMoveReason::MissingPromotionType => "the promotion type is missing",
+            IllegalMoveReason::UnexpectedPromotionType => "only pawns reaching the last row can be promoted",
+            IllegalMoveReason::LeavesKingInCheck => "it leaves the own king in check",
+        };
+        write!(f, "{reason}")
+    }
+}
+
+/// see [GameState::draw_status]. If more than one applies, the forced draw (and then the repetition) is returned.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DrawStatus {
+    /// 50 moves by each player without a capture or pawn move, either player may claim a draw
+    FiftyMoveAvailable,
+    /// 75 moves by each player without a capture or pawn move, the game is drawn
+    SeventyFiveForced,
+    /// the position occurred for the third time, either player may claim a draw
+    ThreefoldAvailable,
+    /// the position occurred for the fifth time, the game is drawn
+    FivefoldForced,
+}
+
+/// the castlings that are still allowed (see [GameState::castling_rights]).
+/// A castling right requires king and rook to be on their starting positions (see [CastlingColumns]),
+/// so it's lost for good once either of them left it or the rook was captured there.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CastlingRights {
+    pub white_queen_side: Disallowable,
+    pub white_king_side: Disallowable,
+    pub black_queen_side: Disallowable,
+    pub black_king_side: Disallowable,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub columns: CastlingColumns,
+}
+
+/// the files king and castling rooks start on, they're the same for both colors.
+/// In chess960 they depend on the start position (given by the castling availability of its FEN).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CastlingColumns {
+    pub king: i8,
+    pub queen_side_rook: i8,
+    pub king_side_rook: i8,
+}
+
+impl CastlingColumns {
+    pub const CLASSIC: CastlingColumns = CastlingColumns {
+        king: 4,
+        queen_side_rook: 0,
+        king_side_rook: 7,
+    };
+
+    pub fn rook(&self, castling_type: CastlingType) -> i8 {
+        match castling_type {
+            KingSide => self.king_side_rook,
+            QueenSide => self.queen_side_rook,
+        }
+    }
+}
+
+impl Default for CastlingColumns {
+    fn default() -> Self {
+        CastlingColumns::CLASSIC
+    }
+}
+
+impl CastlingRights {
+    /// disallows the castlings the move made impossible. This is what [GameState::do_move] does,
+    /// so external state trackers can keep track of the castling rights the same way.
+    pub fn update_for(&mut self, move_data: &MoveData) {
+        let FromTo { from, to } = move_data.given_from_to;
+        let columns = self.columns;
+        let white_row = Color::White.get_ground_row();
+        let black_row = Color::Black.get_ground_row();
+        // a king that still has castling rights is on its starting position
+        if move_data.figure_moved == FigureType::King {
+            if from == Position::new_unchecked(columns.king, white_row) {
+                self.white_queen_side.disallow();
+                self.white_king_side.disallow();
+            }
+            if from == Position::new_unchecked(columns.king, black_row) {
+                self.black_queen_side.disallow();
+                self.black_king_side.disallow();
+            }
+        }
+        // the rook moves away or is captured (by a normal move or a promotion, en passant only captures pawns)
+        for (rook_starting_pos, castling_right) in [
+            (Position::new_unchecked(columns.queen_side_rook, white_row), &mut self.white_queen_side),
+            (Position::new_unchecked(columns.king_side_rook, white_row), &mut self.white_king_side),
+            (Position::new_unchecked(columns.queen_side_rook, black_row), &mut self.black_queen_side),
+            (Position::new_unchecked(columns.king_side_rook, black_row), &mut self.black_king_side),
+        ] {
+            if from == rook_starting_pos || to == rook_starting_pos {
+                castling_right.disallow();
+            }
+        }
+    }
+}
+
+pub const WHITE_KING_STARTING_POS: Position = E1;
+const WHITE_KING_SIDE_ROOK_STARTING_POS: Position = H1;
+const WHITE_QUEEN_SIDE_ROOK_STARTING_POS: Position = A1;
+pub const BLACK_KING_STARTING_POS: Position = E8;
+const BLACK_KING_SIDE_ROOK_STARTING_POS: Position = H8;
+const BLACK_QUEEN_SIDE_ROOK_STARTING_POS: Position = A8;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct MovesPlayedData {
+    half_moves_played: u32,
+    pub half_moves_played_without_progress: u32,
+    earlier_positions: PositionHistory,
+}
+
+impl MovesPlayedData {
+    fn new() -> MovesPlayedData {
+        MovesPlayedData {
+            half_moves_played: 0,
+            half_moves_played_without_progress: 0,
+            earlier_positions: PositionHistory::default(),
+        }
+    }
+
+    fn new_after_move(&self, move_data: &MoveData, position_key_before_move: u64) -> MovesPlayedData {
+        let new_half_moves_played = self.half_moves_played.saturating_add(1);
+
+        let (new_half_moves_played_without_progress, new_earlier_positions) = if move_data.is_pawn_move() || move_data.did_catch_figure() {
+            (0, PositionHistory::default())
+        }  else {
+            (self.half_moves_played_without_progress.saturating_add(1), self.earlier_positions.with_key(position_key_before_move))
+        };
+        MovesPlayedData {
+            half_moves_played: new_half_moves_played,
+            half_moves_played_without_progress: new_half_moves_played_without_progress,
+            earlier_positions: new_earlier_positions,
+        }
+    }
+
+    // current round starting at 1, is increased after black moves
+    fn current_round(&self) -> u32 {
+        (self.half_moves_played / 2) + 1
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    impl GameState {
+        pub fn toggle_colors(&self) -> GameState {
+            fn toggle_figures_on_board_to(color: Color, figure_array: [Option<(FigureType, Position)>; 16], board: &mut Board) {
+                for opt_figure_type_and_pos in figure_array.iter() {
+                    if let Some((figure_type, pos)) = opt_figure_type_and_pos {
+                        board.set_figure(pos.toggle_row(), Figure{ fig_type: *figure_type, color });
+                    } else {
+                        break;
+                    }
+                }
+            }
+            let mut toggled_board = Board::empty();
+            let (array_of_opt_white_figures, array_of_opt_black_figures) = self.board.get_white_and_black_figures();
+            toggle_figures_on_board_to(Color::Black, array_of_opt_white_figures, &mut toggled_board);
+            toggle_figures_on_board_to(Color::White, array_of_opt_black_figures, &mut toggled_board);
+
+            GameState {
+                board: toggled_board,
+                turn_by: self.turn_by.toggle(),
+                white_king_pos: self.black_king_pos.toggle_row(),
+                black_king_pos: self.white_king_pos.toggle_row(),
+                en_passant_intercept_pos: self.en_passant_intercept_pos.map(|pos|{pos.toggle_row()}),
+                is_white_queen_side_castling_still_allowed: self.is_black_queen_side_castling_still_allowed,
+                is_white_king_side_castling_still_allowed: self.is_black_king_side_castling_still_allowed,
+                is_black_queen_side_castling_still_allowed: self.is_white_queen_side_castling_still_allowed,
+                is_black_king_side_castling_still_allowed: self.is_white_king_side_castling_still_allowed,
+                castling_columns: self.castling_columns,
+                moves_played_data: self.moves_played_data.clone(),
+                position_hash: 0,
+            }.with_position_hash()
+        }
+    }
+
+    use super::*;
+    use rstest::*;
+    use crate::base::color::Color;
+    use crate::base::util::tests::parse_to_vec;
+    use crate::base::util::vec_to_str;
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        _game_state,
+        case(""),
+        case("e2e4 e7e5"),
+        case("white ♖a1 ♔e1 ♖h1 ♙a2 ♜h2 ♚e8"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_game_from_str(
+        _game_state: GameState,
+    ) {}
+
+    #[rstest(
+        game_state, expected_display,
+        case("", "white's turn\n♜♞♝♛♚♝♞♜ 8\n♟♟♟♟♟♟♟♟ 7\n________ 6\n________ 5\n________ 4\n________ 3\n♙♙♙♙♙♙♙♙ 2\n♖♘♗♕♔♗♘♖ 1\nabcdefgh\ncastling: KQkq, en passant: -, halfmove clock: 0, fullmove number: 1\n"),
+        case("f2f3 e7e5 g2g4 d8h4", "white's turn (in check)\n♜♞♝_♚♝♞♜ 8\n♟♟♟♟_♟♟♟ 7\n________ 6\n____♟___ 5\n______♙♛ 4\n_____♙__ 3\n♙♙♙♙♙__♙ 2\n♖♘♗♕♔♗♘♖ 1\nabcdefgh\ncastling: KQkq, en passant: -, halfmove clock: 1, fullmove number: 3\n"),
+        case("e2e4 e7e5 e1e2", "black's turn\n♜♞♝♛♚♝♞♜ 8\n♟♟♟♟_♟♟♟ 7\n________ 6\n____♟___ 5\n____♙___ 4\n________ 3\n♙♙♙♙♔♙♙♙ 2\n♖♘♗♕_♗♘♖ 1\nabcdefgh\ncastling: kq, en passant: -, halfmove clock: 1, fullmove number: 2\n"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_display(
+        game_state: GameState,
+        expected_display: &str,
+    ) {
+        assert_eq!(game_state.to_string(), expected_display);
+    }
+
+    #[rstest(
+        game_state, expected_nr_of_legal_moves,
+        case("", 20),
+        case("e2e4 e7e5", 29),
+        case("e2e4 a7a6", 30),
+        case("a2a4 h7h6 a4a5 b7b5", 23), // en-passant
+        case("white ♖a1 ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", 12), // castling
+        case("white ♔e1 ♖h1 ♟e2 ♚e8", 12), // no castling, since the pawn attacks f1
+        case("white ♔h1 ♙b7 ♚e8", 7), // promotion
+        case("f2f3 e7e5 g2g4 d8h4", 0), // checkmate
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_legal_moves(
+        game_state: GameState,
+        expected_nr_of_legal_moves: usize,
+    ) {
+        assert_eq!(game_state.get_legal_moves().len(), expected_nr_of_legal_moves);
+        assert_eq!(game_state.legal_move_count(), expected_nr_of_legal_moves);
+        assert_eq!(game_state.mobility(game_state.turn_by), expected_nr_of_legal_moves);
+    }
+
+    #[rstest(
+        game_state, expected_white_mobility, expected_black_mobility,
+        case("", 20, 20),
+        case("e2e4", 30, 20),
+        case("e2e4 d7d5", 31, 29),
+        case("white ♔e1 ♖h1 ♚e8", 15, 5),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_mobility(
+        game_state: GameState,
+        expected_white_mobility: usize,
+        expected_black_mobility: usize,
+    ) {
+        assert_eq!(game_state.mobility(Color::White), expected_white_mobility);
+        assert_eq!(game_state.mobility(Color::Black), expected_black_mobility);
+    }
+
+    // the order is part of the v2 format, so these expectations must never be changed!
+    #[rstest(
+        game_state, expected_ordered_moves,
+        case("", "[b1a3 b1c3 g1f3 g1h3 a2a3 a2a4 b2b3 b2b4 c2c3 c2c4 d2d3 d2d4 e2e3 e2e4 f2f3 f2f4 g2g3 g2g4 h2h3 h2h4]"),
+        case("e2e4", "[a7a5 a7a6 b7b5 b7b6 c7c5 c7c6 d7d5 d7d6 e7e5 e7e6 f7f5 f7f6 g7g5 g7g6 h7h5 h7h6 b8a6 b8c6 g8f6 g8h6]"),
+        case("white ♔e1 ♖h1 ♙g7 ♚a8", "[e1d1 e1f1 e1h1 e1d2 e1e2 e1f2 h1f1 h1g1 h1h2 h1h3 h1h4 h1h5 h1h6 h1h7 h1h8 g7g8Q g7g8R g7g8N g7g8B]"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_ordered_legal_moves(
+        game_state: GameState,
+        expected_ordered_moves: &str,
+    ) {
+        assert_eq!(vec_to_str(&game_state.ordered_legal_moves(), " "), expected_ordered_moves);
+    }
+
+    #[rstest(
+        game_state, expected_moves,
+        case("", "[b1a3 a2a3 b2b3 b1c3 c2c3 d2d3 e2e3 g1f3 f2f3 g2g3 g1h3 h2h3 a2a4 b2b4 c2c4 d2d4 e2e4 f2f4 g2g4 h2h4]"),
+        case("white ♔e1 ♖h1 ♙g7 ♚a8", "[e1d1 e1f1 h1f1 h1g1 e1d2 e1e2 e1f2 h1h2 h1h3 h1h4 h1h5 h1h6 h1h7 g7g8Q g7g8R g7g8N g7g8B h1h8 e1h1]"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_legal_moves_order(
+        game_state: GameState,
+        expected_moves: &str,
+    ) {
+        assert_eq!(vec_to_str(&game_state.get_legal_moves(), " "), expected_moves);
+    }
+
+    #[rstest(
+        index, expected_fen,
+        case(0, "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"),
+        case(518, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        case(959, "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_chess960(index: u16, expected_fen: &str) {
+        assert_eq!(GameState::chess960(index).unwrap().get_fen(), expected_fen);
+    }
+
+    #[test]
+    fn test_chess960_positions_are_unique() {
+        let fens: std::collections::HashSet<String> = (0..960).map(|index| GameState::chess960(index).unwrap().get_fen()).collect();
+        assert_eq!(fens.len(), 960);
+        assert!(matches!(GameState::chess960(960).unwrap_err().kind, ErrorKind::IllegalConfig));
+    }
+
+    #[rstest(
+        fen,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        case("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"),
+        case("r3k2r/8/8/8/8/8/8/R3K2R b Kq - 12 40"),
+        case("8/5k2/8/8/8/8/1K6/8 w - - 99 120"),
+        case("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1"),
+        case("rr2k3/8/8/8/8/8/8/RR2K3 w Bb - 0 1"),
+        case("4k3/8/8/8/8/8/8/RK4RR w GQ - 0 1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_fen(fen: &str) {
+        assert_eq!(GameState::from_fen(fen).unwrap().get_fen(), fen);
+        assert_eq!(fen.parse::<GameState>().unwrap().get_fen(), fen);
+    }
+
+    #[rstest(
+        fen,
+        case(""),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1"),
+        case("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBN1 w KQkq - 0 1"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e3 0 1"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0"),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 4294967295"),
+        case("99999999999999999/8/8/8/8/8/8/8 w - - 0 1"),
+        case("9999999999999999999999999999k92/8/8/8/8/8/8/4K3 w - - 0 1"),
+        case("4k3/8/8/8/8/8/08/4K3 w - - 0 1"),
+        case("4k3/8/8/8/8/8/9/4K3 w - - 0 1"),
+        case("4k3/8/8/8/8/8/44p/4K3 w - - 0 1"),
+        case("4k3/8/8/8/8/8/8/4K3 w C - 0 1"),
+        case("r3k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1"),
+        case("1r1k2r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1"),
+        case("4k3/8/8/8/PPPPPPPP/PPPPPPPP/PPPPPPPP/4K3 w - - 0 1"),
+        case("4k3/8/8/8/8/1P6/PPPPPPPP/4K3 w - - 0 1"),
+        case("4k3/8/8/8/8/QQQQQQQQ/PPPPPPPP/QQQQQQK1 w - - 0 1"),
+        case("4k3/pppppppp/8/1p6/8/8/8/4K3 b - - 0 1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_fen_rejects_illegal_fens(fen: &str) {
+        assert!(GameState::from_fen(fen).is_err());
+    }
+
+    #[rstest(
+        fen, expected_has_source,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1", true),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 x", true),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0", false),
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1", false),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_fen_errors_keep_their_source(fen: &str, expected_has_source: bool) {
+        let error: Box<dyn std::error::Error> = Box::new(GameState::from_fen(fen).unwrap_err());
+        assert_eq!(error.source().is_some_and(|source| source.is::<std::num::ParseIntError>()), expected_has_source);
+    }
+
+    #[rstest(
+        fen, depth, expected_nodes,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 3, 8902),
+        case("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 2, 2039), // castling, pins, promotions
+        case("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 3, 2812), // en passant discovering a check
+        case("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 2, 264),
+        case("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", 3, 12189), // chess960
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_perft(fen: &str, depth: u32, expected_nodes: u64) {
+        assert_eq!(GameState::from_fen(fen).unwrap().perft(depth), expected_nodes);
+    }
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        game_state, expected_result, expected_is_checkmate, expected_is_stalemate,
+        case("", GameResult::Undecided, false, false),
+        case("f2f3 e7e5 g2g4 d8h4", GameResult::BlackWins, true, false),
+        case("black ♔b6 ♕c7 ♚a8", GameResult::Draw, false, true),
+        case("black ♔b6 ♕c6 ♚a8", GameResult::Undecided, false, false),
+        case("white ♔a1 ♘b1 ♚h8", GameResult::Draw, false, false), // insufficient material
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_game_result(
+        game_state: GameState,
+        expected_result: GameResult,
+        expected_is_checkmate: bool,
+        expected_is_stalemate: bool,
+    ) {
+        assert_eq!(game_state.get_game_result(), expected_result);
+        assert_eq!(game_state.is_checkmate(), expected_is_checkmate);
+        assert_eq!(game_state.is_stalemate(), expected_is_stalemate);
+    }
+
+    #[rstest(
+        game_state, move_str, expected_result,
+        case("", "e2e4", Ok(())),
+        case("", "g1f3", Ok(())),
+        case("", "e3e4", Err(IllegalMoveReason::NoFigureToMove)),
+        case("", "e7e5", Err(IllegalMoveReason::NotYourFigure)),
+        case("", "d1d2", Err(IllegalMoveReason::CapturesOwnFigure)),
+        case("", "g1g3", Err(IllegalMoveReason::UnreachableTarget)),
+        case("e2e4 e7e5 e1e2 d8h4 e2e3 h4h3", "e3f3", Err(IllegalMoveReason::LeavesKingInCheck)),
+        case("d2d4 e7e6 e2e4 f8b4", "c2c3", Ok(())),
+        case("d2d4 e7e6 e2e4 f8b4 c2c3 b4c3", "d4d5", Err(IllegalMoveReason::LeavesKingInCheck)),
+        case("a2a4 h7h6 a4a5 b7b5", "a5b6", Ok(())),
+        case("a2a4 h7h6 a4a5 b7b6", "a5b6", Ok(())),
+        case("a2a4 b7b5 a4a5 h7h5", "a5b6", Err(IllegalMoveReason::NoEnPassant)),
+        case("e2e4 a7a6 e4e5 h7h6", "e5d6", Err(IllegalMoveReason::NoEnPassant)),
+        case("white ♖a1 ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", "e1h1", Ok(())),
+        case("white ♖a1 ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", "e1a1", Ok(())),
+        case("white ♔e1 ♖h1 ♟e2 ♚e8", "e1h1", Err(IllegalMoveReason::CastlingNotAllowed)),
+        case("4k3/8/8/8/8/8/8/1RK2R2 w KQ - 0 1", "c1b1", Ok(())),
+        case("4k3/8/8/8/8/8/8/1RK2R2 w KQ - 0 1", "c1f1", Ok(())),
+        case("4k3/8/8/8/8/8/8/1RKB1R2 w KQ - 0 1", "c1b1", Err(IllegalMoveReason::CastlingNotAllowed)),
+        case("4k3/8/8/8/8/8/8/1RKB1R2 w KQ - 0 1", "c1f1", Err(IllegalMoveReason::CastlingNotAllowed)),
+        // the castling rook doesn't shield the king on its target from the black rook
+        case("4k3/8/8/8/8/8/8/rRK5 w Q - 0 1", "c1b1", Err(IllegalMoveReason::CastlingNotAllowed)),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8Q", Ok(())),
+        case("white ♔h1 ♙b7 ♚e8", "b7b8", Err(IllegalMoveReason::MissingPromotionType)),
+        case("white ♔h1 ♙b6 ♚e8", "b6b7Q", Err(IllegalMoveReason::UnexpectedPromotionType)),
+        case("white ♔h1 ♕e7 ♚e8", "e7e8", Err(IllegalMoveReason::CapturesKing)),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_is_legal(
+        game_state: GameState,
+        move_str: &str,
+        expected_result: Result<(), IllegalMoveReason>,
+    ) {
+        let a_move = move_str.parse::<Move>().unwrap();
+        assert_eq!(game_state.is_legal(a_move), expected_result);
+        if game_state.looks_like_pawn_promotion_move(a_move.from_to) == a_move.promotion_type.is_some() {
+            assert_eq!(game_state.get_legal_moves().contains(&a_move), expected_result.is_ok());
+        }
+    }
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        game_state, next_move_str, expected_catches_figure,
+        case("white ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", "e1d1", false),
+        case("white ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", "e1g1", false),
+        case("white ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", "a2a3", false),
+        case("white ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", "a2a4", false),
+        case("white ♔e1 ♖h1 ♙a2 ♜h2 ♚e8", "h1h2", true),
+        case("b2b4 a7a6 b4b5 c7c5", "b5c6", true),
+        case("b2b4 a7a6 b4b5 c7c5", "b5a6", true),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_do_move_catches_figure(
+        game_state: GameState,
+        next_move_str: &str,
+        expected_catches_figure: bool,
+    ) {
+        let white_move = next_move_str.parse::<Move>().unwrap();
+        let ( _, move_stats) = game_state.do_move(white_move).unwrap();
+        assert_eq!(move_stats.did_catch_figure(), expected_catches_figure, "white catches figure");
+
+
+        let toggled_game_state = game_state.toggle_colors();
+        let ( _, move_stats) = toggled_game_state.do_move(white_move.toggle_rows()).unwrap();
+        assert_eq!(move_stats.did_catch_figure(), expected_catches_figure, "black catches figure");
+    }
+
+    #[rstest(
+        game_state, next_move_str,
+        case("", "e3e4"),
+        case("white ♔e1 ♖h1 ♚h8", "h1h8"),
+        case("white ♔e1 ♚e2", "e1e2"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_do_move_rejects_moves_that_cant_be_played(
+        game_state: GameState,
+        next_move_str: &str,
+    ) {
+        let next_move = next_move_str.parse::<Move>().unwrap();
+        assert!(matches!(game_state.do_move(next_move), Err(ChessError { kind: ErrorKind::IllegalMove, .. })));
+    }
+
+    #[test]
+    fn test_game_state_toggle_colors() {
+        let game_state = "white ♔b1 ♜h2 Eh6 ♟h5 ♚g7".parse::<GameState>().unwrap();
+        let white_move = "b1c1".parse::<Move>().unwrap();
+        assert_eq!(game_state.turn_by, Color::White);
+        assert_eq!(game_state.get_passive_king_pos(), "g7".parse::<Position>().unwrap());
+        assert_eq!(game_state.en_passant_intercept_pos.unwrap(), "h6".parse::<Position>().unwrap());
+        // do_move includes some runtime validation
+        game_state.do_move(white_move).unwrap();
+
+
+        let toggled_game_state = game_state.toggle_colors();
+        assert_eq!(toggled_game_state.turn_by, Color::Black);
+        assert_eq!(toggled_game_state.get_passive_king_pos(), "g2".parse::<Position>().unwrap(), "game_state {}", &toggled_game_state);
+        assert_eq!(toggled_game_state.en_passant_intercept_pos.unwrap(), "h3".parse::<Position>().unwrap(), "game_state {}", &toggled_game_state);
+        toggled_game_state.do_move(white_move.toggle_rows()).unwrap();
+    }
+
+    #[rstest(
+        game_state, expected_color,
+        case("black ♔b6 ♙a7 ♚a8", Color::Black),
+        case("white ♔h8 ♚f8 ♜e7 ♟e6 ♟d7", Color::White),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_turn_by(
+        game_state: GameState,
+        expected_color: Color,
+    ) {
+        assert_eq!(game_state.turn_by, expected_color);
+    }
+
+    #[rstest(
+        game_state, promoting_move,
+        case("white ♔b6 ♙a7 ♚h6", "a7a8Q"),
+        case("white ♔b6 ♙a7 ♚h6", "a7a8R"),
+        case("white ♔b6 ♙a7 ♚h6", "a7a8N"),
+        case("white ♔b6 ♙a7 ♚h6", "a7a8B"),
+        case("white ♔b6 ♙a7 ♞b8 ♚h6", "a7b8Q"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_pawn_promo_works(
+        game_state: GameState,
+        promoting_move: Move,
+    ) {
+        let expected_color_of_promoted_figure = game_state.turn_by;
+        let expected_promo_figure_type = if let Some(promoted_to) = promoting_move.promotion_type {
+            promoted_to.get_figure_type()
+        } else {
+            panic!("expected move that includes a pawn promotion, but got {}", promoting_move)
+        };
+        let (new_game_state, _) = game_state.do_move(promoting_move).unwrap();
+        let promoted_figure = new_game_state.board.get_figure(promoting_move.from_to.to);
+        if let Some(figure) = promoted_figure {
+            println!("{}", new_game_state.get_fen_part1to4());
+            assert_eq!(figure.color, expected_color_of_promoted_figure);
+            assert_eq!(figure.fig_type, expected_promo_figure_type);
+        } else {
+            panic!("expected a figure on promotion square")
+        }
+    }
+
+    #[rstest(
+        game_state, castling_move, expected_updated_board_fen,
+        case("white ♖a1 ♔e1 ♖h1 ♜a8 ♚e8 ♜h8", "e1a1", "r3k2r/8/8/8/8/8/8/2KR3R"),
+        case("white ♖a1 ♔e1 ♖h1 ♜a8 ♚e8 ♜h8", "e1h1", "r3k2r/8/8/8/8/8/8/R4RK1"),
+        case("black ♖a1 ♔e1 ♖h1 ♜a8 ♚e8 ♜h8", "e8a8", "2kr3r/8/8/8/8/8/8/R3K2R"),
+        case("black ♖a1 ♔e1 ♖h1 ♜a8 ♚e8 ♜h8", "e8h8", "r4rk1/8/8/8/8/8/8/R3K2R"),
+        case("4k3/8/8/8/8/8/8/6KR w K - 0 1", "g1h1", "4k3/8/8/8/8/8/8/5RK1"),
+        case("4k3/8/8/8/8/8/8/RK6 w Q - 0 1", "b1a1", "4k3/8/8/8/8/8/8/2KR4"),
+        case("4k3/8/8/8/8/8/8/1RK2R2 w KQ - 0 1", "c1f1", "4k3/8/8/8/8/8/8/1R3RK1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_castling_works(
+        game_state: GameState,
+        castling_move: Move,
+        expected_updated_board_fen: &str,
+    ) {
+        let (new_game_state, _) = game_state.do_move(castling_move).unwrap();
+        let actual_updated_board_fen = new_game_state.board.get_fen_part1();
+        assert_eq!(actual_updated_board_fen, expected_updated_board_fen);
+    }
+
+    #[rstest(
+        start, moves, expected_draw_status,
+        case("", "", None),
+        case("", "g1f3 g8f6 f3g1 f6g8", None),
+        case("", "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8", Some(DrawStatus::ThreefoldAvailable)),
+        case("", "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8 e2e4 e7e5 g1f3 g8f6 f3g1 f6g8", None),
+        case("", "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8", Some(DrawStatus::FivefoldForced)),
+        case("4k3/8/8/8/8/8/8/R3K3 w - - 98 60", "a1a2", None),
+        case("4k3/8/8/8/8/8/8/R3K3 w - - 99 60", "a1a2", Some(DrawStatus::FiftyMoveAvailable)),
+        case("4k3/8/8/8/8/8/8/R3K3 w - - 149 80", "a1a2", Some(DrawStatus::SeventyFiveForced)),
+        case("4k3/8/4K3/8/8/8/8/R7 w - - 149 80", "a1a8", None),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_draw_status(
+        start: GameState,
+        moves: &str,
+        expected_draw_status: Option<DrawStatus>,
+    ) {
+        let mut game_state = start;
+        for next_move in moves.split_whitespace() {
+            game_state = game_state.do_move(next_move.parse::<Move>().unwrap()).unwrap().0;
+        }
+        assert_eq!(game_state.draw_status(), expected_draw_status);
+    }
+
+    #[test]
+    fn test_position_history_is_shared_between_game_states() {
+        let shuffle: Vec<Move> = ["g1f3", "g8f6", "f3g1", "f6g8"].iter().map(|it| it.parse().unwrap()).collect();
+        let mut game_states: Vec<GameState> = vec![GameState::classic()];
+        for next_move in shuffle.iter().cycle().take(8000) {
+            let game_state = game_states.last().unwrap().do_move(*next_move).unwrap().0;
+            game_states.push(game_state);
+        }
+        // every state only adds its predecessor's key to the history instead of copying it
+        assert!(game_states.windows(2).all(|states| states[0].moves_played_data.earlier_positions
+            .is_continued_by(&states[1].moves_played_data.earlier_positions)));
+        let final_state = game_states.last().unwrap();
+        assert_eq!(final_state.moves_played_data.earlier_positions.keys().count(), 8000);
+        assert_eq!(final_state.draw_status(), Some(DrawStatus::FivefoldForced));
+    }
+
+    #[rstest(
+        game_state, next_move, expected_fen,
+        case("r3k2r/6P1/8/8/8/8/8/R3K2R w KQkq - 0 1", "g7h8Q", "r3k2Q/8/8/8/8/8/8/R3K2R b KQq - 0 1"),
+        case("r3k2r/8/8/8/8/8/1p6/R3K2R b KQkq - 0 1", "b2a1N", "r3k2r/8/8/8/8/8/8/n3K2R w Kkq - 0 2"),
+        case("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "a1a8", "R3k2r/8/8/8/8/8/8/4K2R b Kk - 0 1"),
+        case("r3k2r/8/8/8/Pp6/8/8/R3K2R b KQkq a3 0 1", "b4a3", "r3k2r/8/8/8/8/p7/8/R3K2R w KQkq - 0 2"),
+        case("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1f1", "r3k2r/8/8/8/8/8/8/R4K1R b kq - 1 1"),
+        case("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "e8a8", "2kr3r/8/8/8/8/8/8/R3K2R w KQ - 1 2"),
+        case("r3k3/8/8/8/8/8/8/4K2R w Kq - 0 1", "h1h8", "r3k2R/8/8/8/8/8/8/4K3 b q - 1 1"),
+        // chess960, white and black castling rooks start on the same files (b and g)
+        case("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1", "b1b8", "1R2k1r1/8/8/8/8/8/8/4K1R1 b Kk - 0 1"),
+        case("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1", "g1g8", "1r2k1R1/8/8/8/8/8/8/1R2K3 b Qq - 0 1"),
+        case("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1", "e1g1", "1r2k1r1/8/8/8/8/8/8/1R3RK1 b kq - 1 1"),
+        case("1r2k1r1/8/8/8/8/8/8/1R2K1R1 b KQkq - 0 1", "e8b8", "2kr2r1/8/8/8/8/8/8/1R2K1R1 w KQ - 1 2"),
+        case("rr2k3/8/8/8/8/8/8/RR2K3 w Bb - 0 1", "a1a2", "rr2k3/8/8/8/8/8/R7/1R2K3 b Qb - 1 1"),
+        case("rr2k3/8/8/8/8/8/8/RR2K3 w Bb - 0 1", "b1b2", "rr2k3/8/8/8/8/8/1R6/R3K3 b b - 1 1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_castling_rights_update(
+        game_state: GameState,
+        next_move: Move,
+        expected_fen: &str,
+    ) {
+        let (new_game_state, move_data) = game_state.do_move(next_move).unwrap();
+        assert_eq!(new_game_state.get_fen(), expected_fen);
+        let mut castling_rights = game_state.castling_rights();
+        castling_rights.update_for(&move_data);
+        assert_eq!(castling_rights, new_game_state.castling_rights());
+    }
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+        game_config, expected_fen,
+        case("", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        case("e2e4", "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"),
+        case("e2e4 e7e5", "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"),
+        case("b1a3 g8h6 g1h3", "rnbqkb1r/pppppppp/7n/8/8/N6N/PPPPPPPP/R1BQKB1R b KQkq - 3 2"),
+        case("b1a3 g8h6 a1b1", "rnbqkb1r/pppppppp/7n/8/8/N7/PPPPPPPP/1RBQKBNR b Kkq - 3 2"),
+        case("b1a3 g8h6 a1b1 h8g8", "rnbqkbr1/pppppppp/7n/8/8/N7/PPPPPPPP/1RBQKBNR w Kq - 4 3"),
+        case("white ♔d1 ♖h1 ♚e8", "4k3/8/8/8/8/8/8/3K3R w - - 0 1"),
+        case("black ♖a1 ♔e1 ♖h1 ♜a8 ♚e8 ♜h8", "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_fen(
+        game_config: &str,
+        expected_fen: &str,
+    ) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        let actual_fen = game_state.get_fen();
+        assert_eq!(actual_fen, String::from(expected_fen));
+    }
+
+    fn get_latest_move_data_after(moves: Vec<Move>) -> MoveData {
+        let mut latest_game_state = GameState::classic();
+        let mut latest_move_data = MoveData::new_castling("e1h1".parse::<FromTo>().unwrap());
+        for next_move in moves {
+            (latest_game_state, latest_move_data) = latest_game_state.do_move(next_move).unwrap();
+        };
+        latest_move_data
+    }
+
+    #[rstest(
+        moves_made, expected_did_last_move_make_progress,
+        case("e2e4", true),
+        case("e2e4 g8f6", false),
+        case("e2e3", true),
+        case("e2e4 d7d5", true),
+        case("e2e4 d7d5 e4d5", true),
+        case("b1c3 d7d5 c3d5", true),
+        case("b1c3 e7e5 c3d5", false),
+        case("e2e4 d7d5 d1e2", false),
+        case("e2e4 d7d5 e1e2", false),
+        case("e2e4 d7d5 f1e2", false),
+        case("e2e4 d7d5 e4e5 f7f5 e5f6 f8g7 f6g7 a7a6 g7h8R", true),
+        case("e2e4 d7d5 e4e5 f7f5 e5f6 f8g7 f6g7 a7a6 g7g8B", true),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_did_last_move_make_progress(
+        moves_made: &str,
+        expected_did_last_move_make_progress: bool,
+    ) {
+        let latest_move_data = {
+            let moves: Vec<Move> = parse_to_vec::<Move>(moves_made, " ").expect("invalid moves");
+            get_latest_move_data_after(moves)
+        };
+        let actual_did_last_move_make_progress = latest_move_data.did_make_progress();
+        assert_eq!(actual_did_last_move_make_progress, expected_did_last_move_make_progress, "moves made: {}", moves_made);
+    }
+
+    #[rstest(
+        moves_made, expected_latest_moved_figure,
+        case("e2e4", "Pawn"),
+        case("e2e4 g8f6", "Knight"),
+        case("e2e4 d7d5 e4d5", "Pawn"),
+        case("b1c3 d7d5 c3d5", "Knight"),
+        case("b1c3 e7e5 c3d5", "Knight"),
+        case("e2e4 d7d5 d1e2", "Queen"),
+        case("e2e4 d7d5 e1e2", "King"),
+        case("e2e4 d7d5 f1e2", "Bishop"),
+        case("g1f3 d7d6 g2g3 d6d5 f1g2 d5d4 e1h1", "King"),
+        case("g1f3 d7d6 h1g1", "Rook"),
+        case("e2e4 d7d5 e4e5 f7f5 e5f6", "Pawn"),
+        case("e2e4 d7d5 e4e5 f7f5 e5f6 f8g7 f6g7 a7a6 g7h8R", "Pawn"),
+        case("e2e4 d7d5 e4e5 f7f5 e5f6 f8g7 f6g7 a7a6 g7g8B", "Pawn"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_move_data_figure_moved(
+        moves_made: &str,
+        expected_latest_moved_figure: &str,
+    ) {
+        let latest_move_data = {
+            let moves: Vec<Move> = parse_to_vec::<Move>(moves_made, " ").expect("invalid moves");
+            get_latest_move_data_after(moves)
+        };
+        let expected_figure_type = match expected_latest_moved_figure {
+            "Pawn" => FigureType::Pawn,
+            "Rook" => FigureType::Rook,
+            "Knight" => FigureType::Knight,
+            "Bishop" => FigureType::Bishop,
+            "Queen" => FigureType::Queen,
+            "King" => FigureType::King,
+            _ => panic!("unknown figure type: {expected_latest_moved_figure}")
+        };
+
+        assert_eq!(latest_move_data.figure_moved, expected_figure_type, "moves made: {}", moves_made);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest(
+        game_state,
+        case(""),
+        case("e2e4 d7d5 e4e5 f7f5"),
+        case("g1f3 g8f6 g2g3 g7g6 f1g2 f8g7 e1h1"),
+        case("white ♔h1 ♙b7 ♜a8 ♚e8"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_serde_round_trip(
+        game_state: GameState,
+    ) {
+        let bytes = postcard::to_allocvec(&game_state).unwrap();
+        let deserialized_game_state: GameState = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized_game_state.get_fen(), game_state.get_fen());
+        assert_eq!(
+            deserialized_game_state.board.contains_sufficient_material_to_continue(),
+            game_state.board.contains_sufficient_material_to_continue(),
+        );
+    }
+}
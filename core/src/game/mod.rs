@@ -0,0 +1,5 @@
+pub mod game_state;
+pub mod game_result;
+pub mod board;
+pub(crate) mod zobrist;
+pub(crate) mod position_history;
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// the Zobrist keys of the positions since the last pawn move or capture (excluding the current one),
+/// positions from before can't occur again.
+///
+/// The keys are linked from the latest to the earliest one, so the game states of a game share their history
+/// instead of each holding a copy of it (which would take quadratic memory for long games without progress).
+#[derive(Clone, Default)]
+pub(crate) struct PositionHistory {
+    latest: Option<Arc<EarlierPosition>>,
+}
+
+struct EarlierPosition {
+    key: u64,
+    previous: Option<Arc<EarlierPosition>>,
+}
+
+impl PositionHistory {
+    /// the history after a move that was played in the position with `key`
+    pub(crate) fn with_key(&self, key: u64) -> PositionHistory {
+        PositionHistory {
+            latest: Some(Arc::new(EarlierPosition {
+                key,
+                previous: self.latest.clone(),
+            })),
+        }
+    }
+
+    /// from the latest to the earliest key
+    pub(crate) fn keys(&self) -> impl Iterator<Item=u64> + '_ {
+        std::iter::successors(self.latest.as_deref(), |earlier_position| earlier_position.previous.as_deref())
+            .map(|earlier_position| earlier_position.key)
+    }
+
+    /// only the latest `len` keys are kept
+    pub(crate) fn truncated(&self, len: usize) -> PositionHistory {
+        if self.keys().nth(len).is_none() {
+            return self.clone();
+        }
+        let kept_keys: Vec<u64> = self.keys().take(len).collect();
+        from_earliest_to_latest(kept_keys.into_iter().rev())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_continued_by(&self, later: &PositionHistory) -> bool {
+        match (&self.latest, later.latest.as_ref().and_then(|latest| latest.previous.as_ref())) {
+            (Some(latest), Some(previous_of_later)) => Arc::ptr_eq(latest, previous_of_later),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+fn from_earliest_to_latest(keys: impl Iterator<Item=u64>) -> PositionHistory {
+    keys.fold(PositionHistory::default(), |history, key| history.with_key(key))
+}
+
+impl Drop for EarlierPosition {
+    fn drop(&mut self) {
+        // unlinks the history iteratively, dropping it recursively could overflow the stack for long histories
+        let mut previous = self.previous.take();
+        while let Some(mut earlier_position) = previous.and_then(Arc::into_inner) {
+            previous = earlier_position.previous.take();
+        }
+    }
+}
+
+impl fmt::Debug for PositionHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<u64> = self.keys().collect();
+        keys.reverse();
+        f.debug_list().entries(keys).finish()
+    }
+}
+
+/// the history is serialized as the sequence of its keys from the earliest to the latest one
+#[cfg(feature = "serde")]
+impl serde::Serialize for PositionHistory {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut keys: Vec<u64> = self.keys().collect();
+        keys.reverse();
+        serializer.collect_seq(keys)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PositionHistory {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<PositionHistory, D::Error> {
+        let keys = <Vec<u64> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(from_earliest_to_latest(keys.into_iter()))
+    }
+}
@@ -0,0 +1,13 @@
+// the rules of chess, they must not depend on the compression formats or notations of chess_compress_urlsafe-codec
+pub mod base;
+pub mod figure;
+pub mod game;
+#[cfg(feature = "reference")]
+pub mod reference;
+pub mod trainer;
+
+pub use base::color::Color;
+pub use figure::figure::FigureType;
+pub use figure::functions::is_reachable_by::origin_candidates;
+pub use game::board::Board;
+pub use game::game_state::{CastlingColumns, CastlingRights, DrawStatus, GameState, IllegalMoveReason};
@@ -0,0 +1,194 @@
+use std::fmt;
+use shakmaty::{CastlingMode, Chess, EnPassantMode, Role, Square};
+use shakmaty::fen::Fen;
+use shakmaty::Position as _;
+use crate::base::a_move::{FromTo, Move, PromotionType};
+use crate::base::position::Position;
+use crate::game::game_state::GameState;
+
+/// where this crate and the reference implementation ([shakmaty](https://crates.io/crates/shakmaty))
+/// disagree about the same position
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Discrepancy {
+    /// the reference doesn't accept the FEN written by [GameState::get_fen]
+    FenRejected { reason: String },
+    /// the reference writes the position it read from the FEN differently
+    FenMismatch { reference_fen: String },
+    /// a move only this crate considers legal
+    ExtraMove { a_move: Move },
+    /// a move only the reference considers legal
+    MissingMove { a_move: Move },
+    InCheckMismatch { in_check: bool },
+    CheckmateMismatch { checkmate: bool },
+    StalemateMismatch { stalemate: bool },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::FenRejected { reason } => write!(f, "the reference rejects the FEN: {reason}"),
+            Discrepancy::FenMismatch { reference_fen } => write!(f, "the reference writes the FEN as \"{reference_fen}\""),
+            Discrepancy::ExtraMove { a_move } => write!(f, "{a_move} is only legal here"),
+            Discrepancy::MissingMove { a_move } => write!(f, "{a_move} is only legal in the reference"),
+            Discrepancy::InCheckMismatch { in_check } => write!(f, "in check is {in_check} here, but not in the reference"),
+            Discrepancy::CheckmateMismatch { checkmate } => write!(f, "checkmate is {checkmate} here, but not in the reference"),
+            Discrepancy::StalemateMismatch { stalemate } => write!(f, "stalemate is {stalemate} here, but not in the reference"),
+        }
+    }
+}
+
+/// all discrepancies found for one position
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DiscrepancyReport {
+    /// the position as written by [GameState::get_fen]
+    pub fen: String,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl fmt::Display for DiscrepancyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.fen)?;
+        for discrepancy in &self.discrepancies {
+            write!(f, "\n  {discrepancy}")?;
+        }
+        Ok(())
+    }
+}
+
+/// cross-checks the FEN, the legal moves and check/mate/stalemate of the position against the reference implementation,
+/// e.g. on the positions of fuzzed games. Castling moves are compared in the form of this crate (king to rook).
+pub fn verify_against_reference(game_state: &GameState) -> Result<(), DiscrepancyReport> {
+    let fen = game_state.get_fen();
+    let discrepancies = find_discrepancies(game_state, &fen);
+    if discrepancies.is_empty() {
+        Ok(())
+    } else {
+        Err(DiscrepancyReport { fen, discrepancies })
+    }
+}
+
+fn find_discrepancies(game_state: &GameState, fen: &str) -> Vec<Discrepancy> {
+    let reference: Chess = match fen.parse::<Fen>() {
+        // the chess960 mode also covers the castlings of the classic start position
+        Ok(parsed_fen) => match parsed_fen.into_position(CastlingMode::Chess960) {
+            Ok(reference) => reference,
+            Err(error) => return vec![Discrepancy::FenRejected { reason: error.to_string() }],
+        },
+        Err(error) => return vec![Discrepancy::FenRejected { reason: error.to_string() }],
+    };
+
+    let mut discrepancies: Vec<Discrepancy> = Vec::new();
+    let reference_fen = Fen::from_position(&reference, EnPassantMode::Always).to_string();
+    if reference_fen != fen {
+        discrepancies.push(Discrepancy::FenMismatch { reference_fen });
+    }
+
+    let legal_moves = game_state.ordered_legal_moves();
+    let reference_moves: Vec<Move> = reference.legal_moves().into_iter().filter_map(from_reference_move).collect();
+    for a_move in &legal_moves {
+        if !reference_moves.contains(a_move) {
+            discrepancies.push(Discrepancy::ExtraMove { a_move: *a_move });
+        }
+    }
+    for a_move in &reference_moves {
+        if !legal_moves.contains(a_move) {
+            discrepancies.push(Discrepancy::MissingMove { a_move: *a_move });
+        }
+    }
+
+    let in_check = game_state.is_in_check(game_state.turn_by);
+    if in_check != reference.is_check() {
+        discrepancies.push(Discrepancy::InCheckMismatch { in_check });
+    }
+    let checkmate = game_state.is_checkmate();
+    if checkmate != reference.is_checkmate() {
+        discrepancies.push(Discrepancy::CheckmateMismatch { checkmate });
+    }
+    let stalemate = game_state.is_stalemate();
+    if stalemate != reference.is_stalemate() {
+        discrepancies.push(Discrepancy::StalemateMismatch { stalemate });
+    }
+    discrepancies
+}
+
+// drops (which only exist in crazyhouse) have no counterpart
+fn from_reference_move(reference_move: shakmaty::Move) -> Option<Move> {
+    let to_position = |square: Square| Position::from_index_unchecked(usize::from(square));
+    let (from, to) = match reference_move {
+        shakmaty::Move::Castle { king, rook } => (king, rook),
+        _ => (reference_move.from()?, reference_move.to()),
+    };
+    let from_to = FromTo::new(to_position(from), to_position(to));
+    Some(match reference_move.promotion() {
+        None => Move::new(from_to),
+        Some(role) => Move::new_with_promotion(from_to, match role {
+            Role::Rook => PromotionType::Rook,
+            Role::Knight => PromotionType::Knight,
+            Role::Bishop => PromotionType::Bishop,
+            _ => PromotionType::Queen,
+        }),
+    })
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[rstest(
+        fen,
+        case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        case("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"),
+        case("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1"),
+        case("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1"),
+        case("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8"),
+        case("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"),
+        case("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1"),
+        case("rr2k3/8/8/8/8/8/8/RR2K3 w Bb - 0 1"),
+        case("4k3/8/8/8/8/8/8/1RKB1R2 w KQ - 0 1"),
+        case("4k3/8/8/8/8/8/8/rRK5 w Q - 0 1"),
+        case("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_verify_against_reference(fen: &str) {
+        let game_state = GameState::from_fen(fen).unwrap();
+        if let Err(report) = verify_against_reference(&game_state) {
+            panic!("{report}");
+        }
+    }
+
+    #[rstest(
+        start_position,
+        case(None),
+        case(Some(0)),
+        case(Some(213)),
+        case(Some(587)),
+        case(Some(959)),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_verify_against_reference_on_random_games(start_position: Option<u16>) {
+        // a linear congruential generator, so failures can be reproduced
+        let mut seed: u64 = 0x2545F4914F6CDD1D;
+        for _ in 0..20 {
+            let mut game_state = match start_position {
+                None => GameState::classic(),
+                Some(chess960_index) => GameState::chess960(chess960_index).unwrap(),
+            };
+            for _ in 0..200 {
+                if let Err(report) = verify_against_reference(&game_state) {
+                    panic!("{report}");
+                }
+                let legal_moves = game_state.ordered_legal_moves();
+                if legal_moves.is_empty() {
+                    break;
+                }
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let a_move = legal_moves[(seed >> 33) as usize % legal_moves.len()];
+                game_state = game_state.do_move(a_move).unwrap().0;
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::base::a_move::{FromTo, Move};
+use crate::game::game_state::GameState;
+
+/// evaluates positions, e.g. by asking an engine
+pub trait EvalProvider {
+    /// the value of the position in centipawns from the point of view of the player whose turn it is
+    fn evaluate(&self, game_state: &GameState) -> i32;
+}
+
+/// guesses that are at most this many centipawns worse than the played move count as equivalent
+pub const EQUIVALENT_TOLERANCE_IN_CENTIPAWNS: u32 = 20;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GuessScore {
+    /// the guess is the played move
+    Exact,
+    /// the guess is a different move, but (according to the oracle) at least about as good as the played move
+    Equivalent,
+    /// the guess is (according to the oracle) worse than the played move
+    Worse { centipawn_loss: u32 },
+    /// the guess is a different move and no oracle was given to compare it with the played move
+    Unrated,
+    /// the guess isn't a legal move
+    Illegal,
+}
+
+/// compares the guess of a guess-the-move trainer with the move that was played in the game
+pub fn evaluate_guess(state: &GameState, guess: Move, played: Move, oracle: Option<&impl EvalProvider>) -> GuessScore {
+    if guess == played {
+        return GuessScore::Exact;
+    }
+    if !state.get_legal_moves().contains(&guess) {
+        return GuessScore::Illegal;
+    }
+    let Some(oracle) = oracle else {
+        return GuessScore::Unrated;
+    };
+    // the evaluation after a move is from the point of view of the opponent
+    let evaluate_move = |a_move: Move| state.do_move(a_move).ok().map(|(state_after_move, _)| -oracle.evaluate(&state_after_move));
+    let (Some(played_evaluation), Some(guess_evaluation)) = (evaluate_move(played), evaluate_move(guess)) else {
+        return GuessScore::Unrated;
+    };
+    let centipawn_loss = (played_evaluation - guess_evaluation).max(0) as u32;
+    if centipawn_loss <= EQUIVALENT_TOLERANCE_IN_CENTIPAWNS {
+        GuessScore::Equivalent
+    } else {
+        GuessScore::Worse { centipawn_loss }
+    }
+}
+
+/// the captures and checks the player to move could play, followed by the ones the opponent threatens
+/// (i.e. could play if it was their turn). Whose move it is can be told by the color of the figure on `from`.
+/// Both groups are ordered by the index of `from` and `to` (promotions to different figures are only listed once).
+pub fn threats(state: &GameState) -> Vec<FromTo> {
+    let mut threats = threats_by(state);
+    threats.extend(threats_by(&state.as_if_turn_by(state.turn_by.toggle())));
+    threats
+}
+
+fn threats_by(state: &GameState) -> Vec<FromTo> {
+    let mut threats: Vec<FromTo> = state.get_legal_moves().into_iter()
+        .filter(|a_move| {
+            let Ok((state_after_move, move_data)) = state.do_move(*a_move) else {
+                return false;
+            };
+            let opponent = state.turn_by.toggle();
+            move_data.did_catch_figure() || (state_after_move.is_in_check(opponent) && !state.is_in_check(opponent))
+        })
+        .map(|a_move| a_move.from_to)
+        .collect();
+    threats.sort_by_key(|from_to| (from_to.from.index.get(), from_to.to.index.get()));
+    threats.dedup();
+    threats
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+    use crate::base::position::Position;
+    use crate::figure::figure::FigureType;
+
+    /// counts material (pawn 100, knight and bishop 300, rook 500, queen 900)
+    struct MaterialCounter;
+
+    impl EvalProvider for MaterialCounter {
+        fn evaluate(&self, game_state: &GameState) -> i32 {
+            (0..64).filter_map(|index| game_state.board.get_figure(Position::from_index_unchecked(index))).map(|figure| {
+                let value = match figure.fig_type {
+                    FigureType::Pawn => 100,
+                    FigureType::Knight | FigureType::Bishop => 300,
+                    FigureType::Rook => 500,
+                    FigureType::Queen => 900,
+                    FigureType::King => 0,
+                };
+                if figure.color == game_state.turn_by { value } else { -value }
+            }).sum()
+        }
+    }
+
+    #[rstest(
+        game_state, guess, played, expected_score,
+        case("e2e4 d7d5", "e4d5", "e4d5", GuessScore::Exact),
+        case("e2e4 d7d5", "b1c3", "e4d5", GuessScore::Worse { centipawn_loss: 100 }),
+        case("e2e4 d7d5", "g1f3", "b1c3", GuessScore::Equivalent),
+        case("e2e4 d7d5", "e4e6", "e4d5", GuessScore::Illegal),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_evaluate_guess(game_state: GameState, guess: &str, played: &str, expected_score: GuessScore) {
+        let score = evaluate_guess(&game_state, guess.parse().unwrap(), played.parse().unwrap(), Some(&MaterialCounter));
+        assert_eq!(score, expected_score);
+    }
+
+    #[test]
+    fn test_evaluate_guess_without_oracle() {
+        let game_state = GameState::classic();
+        let score = evaluate_guess(&game_state, "d2d4".parse().unwrap(), "e2e4".parse().unwrap(), None::<&MaterialCounter>);
+        assert_eq!(score, GuessScore::Unrated);
+    }
+
+    #[rstest(
+        game_state, expected_threats,
+        case("", ""),
+        // white can check with the bishop and take on d5, black can take on e4
+        case("e2e4 d7d5", "f1b5 e4d5 d5e4"),
+        // black has to take the checking bishop, white threatens the pawn on e5
+        case("e2e4 e7e5 g1f3 b8c6 f1c4 g8f6 c4f7", "e8f7 f3e5"),
+        ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_threats(game_state: GameState, expected_threats: &str) {
+        let actual_threats: Vec<String> = threats(&game_state).iter().map(|from_to| from_to.to_string()).collect();
+        assert_eq!(actual_threats.join(" "), expected_threats);
+    }
+}
@@ -0,0 +1,44 @@
+#![no_main]
+
+use chess_compress_urlsafe::alphabet::Alphabet;
+use chess_compress_urlsafe::decompress::{decompress, decompress_lossy, decompress_with_options, DecompressOptions};
+use chess_compress_urlsafe::summary::{diff, preview, summarize};
+use chess_compress_urlsafe::unfurl::{unfurl_board_svg, unfurl_board_unicode, unfurl_text};
+use libfuzzer_sys::fuzz_target;
+
+// decompressing (and everything that builds on it) must return a ChessError for every malformed input
+// instead of panicking. The first byte selects the options, the rest is the encoded game.
+fuzz_target!(|data: &[u8]| {
+    let Some((&flags, encoded)) = data.split_first() else {
+        return;
+    };
+    let Ok(encoded) = std::str::from_utf8(encoded) else {
+        return;
+    };
+    let _ = decompress(encoded);
+    let _ = decompress_lossy(encoded);
+    let options = DecompressOptions {
+        alphabet: match flags & 0b11 {
+            0 => Alphabet::UrlSafeBase64,
+            1 => Alphabet::QrAlphanumeric,
+            _ => Alphabet::Base32,
+        },
+        scramble_key: (flags & 0b100 != 0).then(|| "key".to_string()),
+        stop_at_draw: flags & 0b1000 != 0,
+        zobrist_keys: flags & 0b1_0000 != 0,
+        san: flags & 0b10_0000 != 0,
+        allow_spoilers: flags & 0b100_0000 != 0,
+        repair_confusables: flags & 0b1000_0000 != 0,
+        ..DecompressOptions::default()
+    };
+    if let Ok(game) = decompress_with_options(encoded, &options) {
+        let _ = game.infer_result();
+    }
+    let _ = summarize(encoded);
+    let _ = diff(encoded, "ckGV");
+    if let Ok(game_preview) = preview(encoded) {
+        let _ = unfurl_text(&game_preview);
+        let _ = unfurl_board_unicode(&game_preview);
+        let _ = unfurl_board_svg(&game_preview);
+    }
+});
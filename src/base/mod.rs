@@ -1,7 +0,0 @@
-pub mod a_move;
-pub(crate) mod direction;
-pub(crate) mod errors;
-pub(crate) mod position;
-pub(crate) mod color;
-pub(crate) mod util;
-
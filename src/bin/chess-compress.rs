@@ -0,0 +1,148 @@
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+use chess_compress_urlsafe::a_move::Move;
+use chess_compress_urlsafe::completion::complete_move;
+use chess_compress_urlsafe::compress::compress;
+use chess_compress_urlsafe::decompress::decompress;
+use chess_compress_urlsafe::encoder::Encoder;
+use chess_compress_urlsafe::errors::ChessError;
+use chess_compress_urlsafe::san::to_san;
+use chess_compress_urlsafe::summary::{diff, summarize};
+
+const USAGE: &str = "usage:
+  chess-compress compress <move>...   compresses moves given in coordinate notation (e.g. e2e4 e7e5 g1f3)
+  chess-compress decompress <token>   prints the FEN of every position of the game
+  chess-compress play [token]         enter moves interactively, starting from the given game
+  chess-compress diff <a> <b>         reports where two games part ways
+  chess-compress stats <token>        prints length, half moves, captures, result and opening of a game";
+
+const PLAY_HELP: &str = "enter a move (e.g. \"e4\", \"Nf3\" or \"g1f3\"), a part of a move followed by '?' to list the matching moves,
+\"undo\" to take back the last move or \"quit\" to stop";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(|arg| arg.as_str()) {
+        Some("compress") => run_compress(&args[1..]),
+        Some("decompress") if args.len() == 2 => run_decompress(&args[1]),
+        Some("play") if args.len() <= 2 => run_play(args.get(1).map(|token| token.as_str())),
+        Some("diff") if args.len() == 3 => run_diff(&args[1], &args[2]),
+        Some("stats") if args.len() == 2 => run_stats(&args[1]),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_compress(moves: &[String]) -> Result<(), ChessError> {
+    let moves = moves.iter().map(|a_move| a_move.parse::<Move>()).collect::<Result<Vec<Move>, ChessError>>()?;
+    println!("{}", compress(moves)?);
+    Ok(())
+}
+
+fn run_decompress(token: &str) -> Result<(), ChessError> {
+    let (positions, _) = decompress(token)?;
+    for position in positions {
+        println!("{}", position.fen);
+    }
+    Ok(())
+}
+
+fn run_diff(token_a: &str, token_b: &str) -> Result<(), ChessError> {
+    let game_diff = diff(token_a, token_b)?;
+    if game_diff.are_equal() {
+        println!("the games are equal ({} half moves)", game_diff.common_half_moves);
+        return Ok(());
+    }
+    let describe = |next_san: &Option<String>, half_moves: usize| match next_san {
+        Some(next_san) => format!("continues with {next_san} ({} more half moves)", half_moves - game_diff.common_half_moves),
+        None => "ends".to_string(),
+    };
+    println!("common half moves: {}", game_diff.common_half_moves);
+    println!("position: {}", game_diff.fen_at_divergence);
+    println!("a {}", describe(&game_diff.next_san_a, game_diff.half_moves_a));
+    println!("b {}", describe(&game_diff.next_san_b, game_diff.half_moves_b));
+    Ok(())
+}
+
+fn run_stats(token: &str) -> Result<(), ChessError> {
+    let summary = summarize(token)?;
+    println!("length: {}", summary.encoded_len);
+    println!("half moves: {}", summary.half_moves);
+    println!("captures: {}", summary.captures);
+    println!("result: {}", summary.result);
+    println!("opening: {}", summary.opening.unwrap_or("unknown"));
+    Ok(())
+}
+
+fn run_play(token: Option<&str>) -> Result<(), ChessError> {
+    let mut encoder = Encoder::new();
+    if let Some(token) = token {
+        let (_, moves) = decompress(token)?;
+        for move_data in moves {
+            encoder.push_move(move_data.given_move())?;
+        }
+    }
+    println!("{PLAY_HELP}");
+    print_game(&encoder);
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush().expect("stdout should be writable");
+        let Some(Ok(line)) = lines.next() else {
+            return Ok(());
+        };
+        let input = line.trim();
+        match input {
+            "" => {}
+            "quit" | "exit" => return Ok(()),
+            "undo" => match encoder.pop_move() {
+                Some(_) => print_game(&encoder),
+                None => println!("no move to take back"),
+            },
+            _ => {
+                let game_state = encoder.get_game_state().clone();
+                let (partial, list_only) = match input.strip_suffix('?') {
+                    Some(partial) => (partial, true),
+                    None => (input, false),
+                };
+                let matching_moves = complete_move(&game_state, partial);
+                match matching_moves.as_slice() {
+                    [] => println!("no legal move matches \"{partial}\""),
+                    [a_move] if !list_only => {
+                        encoder.push_move(*a_move)?;
+                        print_game(&encoder);
+                    }
+                    _ => {
+                        let sans: Vec<String> = matching_moves.iter().map(|a_move| to_san(*a_move, &game_state)).collect();
+                        println!("{}", sans.join(" "));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn print_game(encoder: &Encoder) {
+    let fen = encoder.get_game_state().get_fen();
+    let (placement, rest) = fen.split_once(' ').expect("a FEN consists of several fields");
+    for (rank, row) in (1..=8).rev().zip(placement.split('/')) {
+        let squares: String = row.chars().map(|c| match c.to_digit(10) {
+            Some(empty_squares) => ".".repeat(empty_squares as usize),
+            None => c.to_string(),
+        }).collect();
+        println!("{rank} {squares}");
+    }
+    println!("  abcdefgh");
+    let turn_by = if rest.starts_with('w') { "white" } else { "black" };
+    println!("{turn_by} to move, token: {}", encoder.as_str());
+}
@@ -1 +0,0 @@
-pub(crate) mod is_reachable_by;
@@ -1,2 +0,0 @@
-pub(crate) mod functions;
-pub mod figure;
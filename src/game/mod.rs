@@ -1,3 +0,0 @@
-pub(crate) mod game_state;
-pub(crate) mod board;
-
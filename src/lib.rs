@@ -1,8 +1,8 @@
-mod base;
-mod figure;
-mod game;
-mod compression;
-
-pub use base::*;
-pub use compression::*;
-pub use figure::figure::FigureType;
\ No newline at end of file
+// re-exports the rules of chess (chess_compress_urlsafe-core) and the compression formats and notations
+// (chess_compress_urlsafe-codec) under the paths they had before the crate was split into a workspace
+pub use chess_compress_urlsafe_core::base::{a_move, errors, position};
+pub use chess_compress_urlsafe_codec::*;
+#[cfg(feature = "reference")]
+pub use chess_compress_urlsafe_core::reference;
+pub use chess_compress_urlsafe_core::trainer;
+pub use chess_compress_urlsafe_core::{origin_candidates, Board, CastlingColumns, CastlingRights, Color, DrawStatus, FigureType, GameState, IllegalMoveReason};